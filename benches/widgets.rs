@@ -0,0 +1,132 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use yoru::{div, math, Color, Div, Element, Label, Overlays, PixmapPool, RenderContext, Root, Sizing, TextContext};
+use yoru::tracking::{ReadableSignal, RwSignal};
+
+const VIEWPORT: math::Size = math::Size { horizontal: 1280.0, vertical: 720.0 };
+
+const PARAGRAPH: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Donec tincidunt nunc lacus, nec finibus dolor sollicitudin tristique. Suspendisse sed magna sed felis fringilla tempus vel sit amet arcu.";
+
+struct Model {
+    counter: RwSignal<i32>
+}
+
+fn nested_divs(rows: usize, columns: usize) -> Element<Model> {
+    let mut outer = div!(width=Sizing::Expand(1.0), height=Sizing::Expand(1.0));
+    for _ in 0..rows {
+        let mut row = Div::new();
+        row.set_width(Sizing::Expand(1.0));
+        for _ in 0..columns {
+            row.add_child(div!(width=Sizing::Fixed(4.0), height=Sizing::Fixed(4.0), background=Color::LIGHT_GRAY));
+        }
+        outer.add_child(row);
+    }
+    outer.into()
+}
+
+fn counter_tree(rows: usize) -> Element<Model> {
+    let mut outer = div!(width=Sizing::Expand(1.0));
+    for _ in 0..rows {
+        outer.add_child(div!([
+            Label::new(|_| "static".into())
+        ]));
+    }
+    outer.add_child(Label::new(|model: &mut Model| model.counter.get().to_string()));
+    outer.into()
+}
+
+fn text_tree(paragraphs: usize) -> Element<Model> {
+    let mut outer = div!(width=Sizing::Expand(1.0));
+    for _ in 0..paragraphs {
+        outer.add_child(Label::new(|_| PARAGRAPH.into()));
+    }
+    outer.into()
+}
+
+// a chain of `depth` divs, each with `width` static siblings, around a clickable counter
+fn deep_tree(depth: usize, width: usize) -> Element<Model> {
+    let mut inner: Element<Model> = Label::new(|model: &mut Model| model.counter.get().to_string())
+        .into();
+    inner = inner.on_click(|model| model.counter.update(|counter| *counter += 1));
+    for _ in 0..depth {
+        let mut level = Div::new();
+        for _ in 0..width {
+            level.add_child(Element::from(div!(width=Sizing::Fixed(4.0), height=Sizing::Fixed(4.0))).on_click(|_| ()));
+        }
+        level.add_child(inner);
+        inner = level.into();
+    }
+    inner
+}
+
+fn layout_nested_divs(c: &mut Criterion) {
+    let text = TextContext::new();
+
+    c.bench_function("layout 5000 nested divs", |b| {
+        b.iter_batched(
+            || (Model { counter: RwSignal::new(0) }, Root::new(nested_divs(50, 100))),
+            |(mut model, mut root)| {
+                root.update(&mut model);
+                root.layout(&text, VIEWPORT, 1.0);
+                black_box(root)
+            },
+            BatchSize::LargeInput
+        )
+    });
+}
+
+fn relayout_after_signal_change(c: &mut Criterion) {
+    let text = TextContext::new();
+    let mut model = Model { counter: RwSignal::new(0) };
+    let mut root = Root::new(counter_tree(1000));
+    root.update(&mut model);
+    root.layout(&text, VIEWPORT, 1.0);
+
+    c.bench_function("relayout after single signal change", |b| {
+        b.iter(|| {
+            model.counter.update(|counter| *counter += 1);
+            root.update(&mut model);
+            root.layout(&text, VIEWPORT, 1.0);
+        })
+    });
+}
+
+fn interactions_after_hot_spot_change(c: &mut Criterion) {
+    let text = TextContext::new();
+    let mut model = Model { counter: RwSignal::new(0) };
+    let mut root = Root::new(deep_tree(30, 50));
+    root.update(&mut model);
+    root.layout(&text, VIEWPORT, 1.0);
+    root.interactions();
+
+    c.bench_function("interactions after hot spot change in deep tree", |b| {
+        b.iter(|| {
+            model.counter.update(|counter| *counter = (*counter + 1) % 20);
+            root.update(&mut model);
+            root.layout(&text, VIEWPORT, 1.0);
+            root.interactions();
+        })
+    });
+}
+
+fn redraw_text_heavy(c: &mut Criterion) {
+    let text = TextContext::new();
+    let mut model = Model { counter: RwSignal::new(0) };
+    let mut root = Root::new(text_tree(40));
+    root.update(&mut model);
+    root.layout(&text, VIEWPORT, 1.0);
+
+    let pool = PixmapPool::new();
+    let theme = root.theme();
+    let mut pixmap = tiny_skia::Pixmap::new(VIEWPORT.width() as u32, VIEWPORT.height() as u32).unwrap();
+    c.bench_function("redraw text heavy", |b| {
+        b.iter(|| {
+            pixmap.fill(Color::WHITE.into());
+            let mut context = RenderContext { canvas: pixmap.as_mut(), text: &text, pool: &pool, theme: &theme, overlays: &Overlays::new() };
+            root.draw(&mut context);
+        })
+    });
+}
+
+criterion_group!(benches, layout_nested_divs, relayout_after_signal_change, interactions_after_hot_spot_change, redraw_text_heavy);
+criterion_main!(benches);
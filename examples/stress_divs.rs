@@ -0,0 +1,37 @@
+//! Opens a window containing a large grid of nested `Div`s, with a button that changes a single
+//! label, to observe how the caches behave with large trees.
+
+use yoru::{div, Application, Color, Div, Element, Label, Root, Sizing};
+use yoru::widgets::Button;
+use yoru::tracking::{ReadableSignal, RwSignal};
+
+const ROWS: usize = 60;
+const COLUMNS: usize = 80;
+
+struct Model {
+    clicks: RwSignal<i32>
+}
+
+fn main() -> yoru::Result<()> {
+    let model = Model { clicks: RwSignal::new(0) };
+
+    let mut grid = div!(width=Sizing::Expand(1.0), height=Sizing::Expand(1.0));
+    for _ in 0..ROWS {
+        let mut row = Div::new();
+        row.set_width(Sizing::Expand(1.0));
+        for _ in 0..COLUMNS {
+            row.add_child(div!(width=Sizing::Fixed(4.0), height=Sizing::Fixed(4.0), background=Color::LIGHT_GRAY));
+        }
+        grid.add_child(row);
+    }
+
+    let root: Element<Model> = div!(width=Sizing::Expand(1.0), height=Sizing::Expand(1.0), [
+        Button::new(
+            Label::new(|model: &mut Model| format!("Clicked {} times", model.clicks.get())),
+            |model| model.clicks.update(|clicks| *clicks += 1)
+        ),
+        grid
+    ]).into();
+
+    Application::new(model, Root::new(root)).run()
+}
@@ -0,0 +1,35 @@
+//! Opens a window with many paragraphs of text whose contents depend on a counter, so that every
+//! click reshapes and redraws a large amount of text.
+
+use yoru::{div, Application, Element, Label, Root, Sizing};
+use yoru::widgets::Button;
+use yoru::tracking::{ReadableSignal, RwSignal};
+
+const PARAGRAPHS: usize = 200;
+
+const PARAGRAPH: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Donec tincidunt nunc lacus, nec finibus dolor sollicitudin tristique. Suspendisse sed magna sed felis fringilla tempus vel sit amet arcu.";
+
+struct Model {
+    generation: RwSignal<i32>
+}
+
+fn main() -> yoru::Result<()> {
+    let model = Model { generation: RwSignal::new(0) };
+
+    let mut paragraphs = div!(width=Sizing::Expand(1.0));
+    for index in 0..PARAGRAPHS {
+        paragraphs.add_child(Label::new(move |model: &mut Model| {
+            format!("{} ({}) {}", index, model.generation.get(), PARAGRAPH)
+        }));
+    }
+
+    let root: Element<Model> = div!(width=Sizing::Expand(1.0), [
+        Button::new(
+            Label::new(|_| "Regenerate".into()),
+            |model: &mut Model| model.generation.update(|generation| *generation += 1)
+        ),
+        paragraphs
+    ]).into();
+
+    Application::new(model, Root::new(root)).run()
+}
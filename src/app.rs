@@ -1,17 +1,30 @@
-use std::num::NonZeroU32;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
-use tiny_skia::PixmapMut;
+use winit::dpi::LogicalSize;
+use winit::error::ExternalError;
+use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy};
+use winit::window::{CursorIcon, Icon, ResizeDirection, WindowAttributes, WindowId, Window};
 
-use winit::event::WindowEvent;
-use winit::event_loop::{ActiveEventLoop, EventLoop};
-use winit::window::{WindowAttributes, WindowId, Window};
-use softbuffer::Surface;
-
-use crate::style::Color;
+use crate::channel::{self, WakingSender};
 use crate::element::Root;
-use crate::{math, RenderContext};
-use crate::interact::InteractionState;
+use crate::{math, Overlays, PixmapPool, RenderContext};
+use crate::error::{Error, Result};
+use crate::idle::IdleMonitor;
+use crate::interact::{Cursor, InteractionState};
+use crate::present::{Presenter, SoftbufferPresenter};
+use crate::shortcut::{Shortcut, Shortcuts};
+use crate::timer::Timers;
+use crate::tracking::{self, Derived, ReadableSignal};
+#[cfg(feature = "text")]
+use crate::text::TextRendering;
+use crate::text::TextContext;
+use crate::theme::Theme;
+#[cfg(feature = "tray")]
+use crate::tray::Tray;
+#[cfg(feature = "notify")]
+use crate::notify::Notifier;
 
 fn timed<T>(message: &str, f: impl FnOnce() -> T) -> T {
     // f()
@@ -25,13 +38,83 @@ fn timed<T>(message: &str, f: impl FnOnce() -> T) -> T {
 }
 
 struct ActiveApplication {
-    window: Rc<Window>,
-    _context: softbuffer::Context<Rc<Window>>,
-    surface: Surface<Rc<Window>, Rc<Window>>,
+    window: Rc<Window>
 }
 
+impl ActiveApplication {
+    fn new(event_loop: &ActiveEventLoop, attributes: WindowAttributes, presenter: &mut dyn Presenter) -> Result<ActiveApplication> {
+        let window = Rc::new(event_loop.create_window(attributes)?);
+        presenter.attach(Rc::clone(&window))?;
+        Ok(ActiveApplication { window })
+    }
+}
+
+// the edges and corners of the window, in the order they are checked
+fn resize_direction(position: math::Point, size: math::Size, thickness: f32) -> Option<ResizeDirection> {
+    let left = position.x < thickness;
+    let right = position.x >= size.width() - thickness;
+    let top = position.y < thickness;
+    let bottom = position.y >= size.height() - thickness;
+    match (left, right, top, bottom) {
+        (true, _, true, _) => Some(ResizeDirection::NorthWest),
+        (_, true, true, _) => Some(ResizeDirection::NorthEast),
+        (true, _, _, true) => Some(ResizeDirection::SouthWest),
+        (_, true, _, true) => Some(ResizeDirection::SouthEast),
+        (true, _, _, _) => Some(ResizeDirection::West),
+        (_, true, _, _) => Some(ResizeDirection::East),
+        (_, _, true, _) => Some(ResizeDirection::North),
+        (_, _, _, true) => Some(ResizeDirection::South),
+        _ => None
+    }
+}
+
+fn resize_cursor(direction: ResizeDirection) -> CursorIcon {
+    match direction {
+        ResizeDirection::North => CursorIcon::NResize,
+        ResizeDirection::South => CursorIcon::SResize,
+        ResizeDirection::East => CursorIcon::EResize,
+        ResizeDirection::West => CursorIcon::WResize,
+        ResizeDirection::NorthEast => CursorIcon::NeResize,
+        ResizeDirection::NorthWest => CursorIcon::NwResize,
+        ResizeDirection::SouthEast => CursorIcon::SeResize,
+        ResizeDirection::SouthWest => CursorIcon::SwResize
+    }
+}
+
+fn cursor_icon(cursor: Cursor) -> CursorIcon {
+    match cursor {
+        Cursor::Default => CursorIcon::Default,
+        Cursor::Pointer => CursorIcon::Pointer,
+        Cursor::Text => CursorIcon::Text,
+        Cursor::Move => CursorIcon::Move,
+        Cursor::Grab => CursorIcon::Grab,
+        Cursor::Grabbing => CursorIcon::Grabbing,
+        Cursor::Crosshair => CursorIcon::Crosshair,
+        Cursor::NotAllowed => CursorIcon::NotAllowed,
+        Cursor::Wait => CursorIcon::Wait,
+        Cursor::ResizeHorizontal => CursorIcon::EwResize,
+        Cursor::ResizeVertical => CursorIcon::NsResize
+    }
+}
+
+type IdleCallback<A> = Box<dyn Fn(&mut A)>;
+// called with the time since the previous frame, returning whether to keep being called
+type FrameCallback<A> = Box<dyn Fn(&mut A, Duration) -> bool>;
+// hands every message waiting in one proxy's channel to its handler, returning whether there were any
+type Dispatcher<A> = Box<dyn Fn(&mut A) -> bool>;
+
 pub struct Application<A> {
     active: Option<ActiveApplication>,
+    presenter: Box<dyn Presenter>,
+    // what the window is created with, apart from the decorations
+    attributes: WindowAttributes,
+    title_binding: Option<Derived<A, String>>,
+    size_binding: Option<Derived<A, math::Size>>,
+    decorations: bool,
+    resize_border: f32,
+    resize_zone: Option<ResizeDirection>,
+    // the cursor last shown, so that it is only set when it changes
+    cursor: CursorIcon,
 
     viewport: math::Size,
     scale_factor: f32,
@@ -39,13 +122,39 @@ pub struct Application<A> {
     state: A,
     to_draw: Root<A>,
 
-    interaction_state: InteractionState
+    text: TextContext,
+    pool: PixmapPool,
+    interaction_state: InteractionState,
+    shortcuts: Shortcuts<A>,
+    idle: IdleMonitor,
+    // callbacks, their timeouts, and whether they already ran since the last input
+    idle_callbacks: Vec<(Duration, IdleCallback<A>, bool)>,
+    timers: Option<Timers<A>>,
+    frame_callbacks: Vec<FrameCallback<A>>,
+    last_frame: Option<Instant>,
+    dispatchers: Vec<Dispatcher<A>>,
+
+    proxy: Option<EventLoopProxy<()>>,
+    #[cfg(feature = "tray")]
+    tray: Option<Tray<A>>,
+    #[cfg(feature = "notify")]
+    notifier: Option<Notifier<A>>,
+
+    error: Option<Error>
 }
 
 impl<A> Application<A> {
     pub fn new(state: A, to_draw: Root<A>) -> Self {
         Application {
             active: None,
+            presenter: Box::new(SoftbufferPresenter::new()),
+            attributes: WindowAttributes::default(),
+            title_binding: None,
+            size_binding: None,
+            decorations: true,
+            resize_border: 6.0,
+            resize_zone: None,
+            cursor: CursorIcon::Default,
 
             viewport: math::Size::new(0.0, 0.0),
             scale_factor: 1.0,
@@ -53,35 +162,343 @@ impl<A> Application<A> {
             state,
             to_draw,
 
-            interaction_state: InteractionState::new()
+            text: TextContext::new(),
+            pool: PixmapPool::new(),
+            interaction_state: InteractionState::new(),
+            shortcuts: Shortcuts::new(),
+            idle: IdleMonitor::new(),
+            idle_callbacks: Vec::new(),
+            timers: None,
+            frame_callbacks: Vec::new(),
+            last_frame: None,
+            dispatchers: Vec::new(),
+
+            proxy: None,
+            #[cfg(feature = "tray")]
+            tray: None,
+            #[cfg(feature = "notify")]
+            notifier: None,
+
+            error: None
+        }
+    }
+
+    /// Use `text` for font loading and glyph caching instead of a context owned by this
+    /// application, e.g. `TextContext::shared()` to share fonts with other applications.
+    pub fn with_text_context(mut self, text: TextContext) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Shows frames through `presenter` instead of copying them to the window on the CPU.
+    pub fn with_presenter(mut self, presenter: impl Presenter + 'static) -> Self {
+        self.presenter = Box::new(presenter);
+        self
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.attributes.title = title.into();
+        self
+    }
+
+    /// Sets the size of the window's content area in logical pixels.
+    pub fn with_inner_size(mut self, width: f32, height: f32) -> Self {
+        self.attributes.inner_size = Some(LogicalSize::new(width, height).into());
+        self
+    }
+
+    /// Whether the user can resize the window.
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.attributes.resizable = resizable;
+        self
+    }
+
+    /// Sets the icon shown in the window's titlebar and the taskbar, from rows of RGBA pixels.
+    pub fn with_window_icon_rgba(mut self, rgba: Vec<u8>, width: u32, height: u32) -> Result<Self> {
+        self.attributes.window_icon = Some(Icon::from_rgba(rgba, width, height)?);
+        Ok(self)
+    }
+
+    /// Computes the window's title from the model, updating it whenever a signal it reads changes.
+    pub fn bind_title(mut self, title: impl Fn(&mut A) -> String + 'static) -> Self {
+        self.title_binding = Some(Derived::new(title));
+        self
+    }
+
+    /// Computes the size of the window's content area, in logical pixels, from the model, and
+    /// asks for the window to be resized whenever a signal it reads changes. The platform may
+    /// not resize the window, e.g. while it is maximized.
+    pub fn bind_inner_size(mut self, size: impl Fn(&mut A) -> math::Size + 'static) -> Self {
+        self.size_binding = Some(Derived::new(size));
+        self
+    }
+
+    /// Changes the window's title, right away if the window is open.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.attributes.title = title.into();
+        if let Some(active) = &self.active {
+            active.window.set_title(&self.attributes.title);
+        }
+    }
+
+    /// Changes the size of the window's content area in logical pixels, asking for the window to
+    /// be resized right away if it is open.
+    pub fn set_inner_size(&mut self, width: f32, height: f32) {
+        let size = LogicalSize::new(width, height);
+        self.attributes.inner_size = Some(size.into());
+        if let Some(active) = &self.active {
+            let _ = active.window.request_inner_size(size);
+        }
+    }
+
+    /// Whether the window gets the platform's titlebar and borders. Undecorated windows can still
+    /// be resized by dragging near their edges, see `with_resize_border`.
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Sets how close to the edge of an undecorated window, before scaling, a drag resizes the
+    /// window. Zero disables resizing by dragging.
+    pub fn with_resize_border(mut self, thickness: f32) -> Self {
+        self.resize_border = thickness.max(0.0);
+        self
+    }
+
+    /// Draws the window with `theme`, see `Root::with_theme`.
+    pub fn with_theme(self, theme: Theme) -> Self {
+        self.to_draw.set_theme(theme);
+        self
+    }
+
+    /// Runs `action` whenever `shortcut` is pressed while the window has focus.
+    pub fn with_shortcut(mut self, shortcut: Shortcut, action: impl Fn(&mut A) + 'static) -> Self {
+        self.shortcuts.add(shortcut, action);
+        self
+    }
+
+    /// Sets how glyphs are rasterized. This changes the current text context, so it also affects
+    /// other applications sharing it.
+    #[cfg(feature = "text")]
+    pub fn with_text_rendering(self, rendering: TextRendering) -> Self {
+        self.text.set_rendering(rendering);
+        self
+    }
+
+    /// Reports input to `idle` instead of a monitor owned by this application, so the model can
+    /// query it.
+    pub fn with_idle_monitor(mut self, idle: IdleMonitor) -> Self {
+        self.idle = idle;
+        self
+    }
+
+    /// Runs `on_idle` once there has been no input for `timeout`, and again after every later
+    /// period of inactivity.
+    pub fn on_idle(mut self, timeout: Duration, on_idle: impl Fn(&mut A) + 'static) -> Self {
+        self.idle_callbacks.push((timeout, Box::new(on_idle), false));
+        self
+    }
+
+    /// Runs the timers started through `timers` from the event loop, see `Timers`.
+    pub fn with_timers(mut self, timers: Timers<A>) -> Self {
+        self.timers = Some(timers);
+        self
+    }
+
+    /// Runs `on_frame` on every pass of the event loop with the time since the previous one, e.g.
+    /// to advance a game or a simulation, until it returns false. While any such callback is
+    /// running, the event loop polls continuously instead of waiting for events.
+    pub fn on_frame(mut self, on_frame: impl Fn(&mut A, Duration) -> bool + 'static) -> Self {
+        self.frame_callbacks.push(Box::new(on_frame));
+        self
+    }
+
+    // returns whether any callback wants to keep running
+    fn run_frame_callbacks(&mut self) -> bool {
+        if self.frame_callbacks.is_empty() {
+            return false;
+        }
+        let now = Instant::now();
+        let elapsed = self.last_frame.map_or(Duration::ZERO, |last| now - last);
+        self.frame_callbacks.retain(|on_frame| on_frame(&mut self.state, elapsed));
+        self.last_frame = (!self.frame_callbacks.is_empty()).then_some(now);
+        self.last_frame.is_some()
+    }
+
+    /// Creates a sender through which other threads, e.g. ones doing file or network IO, can hand
+    /// results back to the model. Sending wakes the event loop, and `handler` is run on the model
+    /// with each message before the tree is next updated.
+    ///
+    /// yoru doesn't run async tasks itself; spawn them on a thread or a runtime of your choice
+    /// and give them a clone of the sender. Messages sent before the application runs are handled
+    /// once it starts.
+    pub fn proxy<M: 'static>(&mut self, handler: impl Fn(&mut A, M) + 'static) -> WakingSender<M> {
+        let (sender, receiver) = channel::channel();
+        self.dispatchers.push(Box::new(move |model| {
+            receiver.try_iter().fold(false, |_, message| {
+                handler(model, message);
+                true
+            })
+        }));
+        sender
+    }
+
+    fn dispatch_messages(&mut self) -> bool {
+        self.dispatchers.iter().fold(false, |handled, dispatch| dispatch(&mut self.state) | handled)
+    }
+
+    #[cfg(feature = "tray")]
+    pub fn with_tray(mut self, tray: Tray<A>) -> Self {
+        self.tray = Some(tray);
+        self
+    }
+
+    #[cfg(feature = "notify")]
+    pub fn with_notifier(mut self, notifier: Notifier<A>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Runs the event loop until the window is closed, or until an error that the application
+    /// cannot recover from (such as failing to create the window) occurs.
+    pub fn run(&mut self) -> Result<()> {
+        let _ = env_logger::try_init();
+
+        let event_loop = EventLoop::new()?;
+        self.proxy = Some(event_loop.create_proxy());
+        if let Some(proxy) = self.proxy.clone() {
+            channel::set_waker(move || { let _ = proxy.send_event(()); });
+        }
+        #[cfg(feature = "notify")]
+        if let (Some(notifier), Some(proxy)) = (&self.notifier, &self.proxy) {
+            notifier.attach(proxy.clone());
+        }
+        event_loop.run_app(self)?;
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(())
         }
     }
 
-    pub fn run(&mut self) {
-        env_logger::init();
+    fn fail(&mut self, event_loop: &ActiveEventLoop, error: Error) {
+        self.error = Some(error);
+        self.active = None;
+        event_loop.exit();
+    }
+
+    fn record_input(&mut self) {
+        self.idle.record_input();
+        for (_, _, fired) in &mut self.idle_callbacks {
+            *fired = false;
+        }
+    }
 
-        let event_loop = EventLoop::new().unwrap();
-        event_loop.run_app(self).unwrap();
+    /// Runs idle callbacks which are due and returns when the next timeout passes.
+    fn poll_idle(&mut self) -> Option<Instant> {
+        let mut next = self.idle.poll();
+        let last_input = self.idle.last_input();
+        for (timeout, on_idle, fired) in &mut self.idle_callbacks {
+            if *fired {
+                continue;
+            }
+            let deadline = last_input + *timeout;
+            if deadline <= Instant::now() {
+                on_idle(&mut self.state);
+                *fired = true;
+            } else {
+                next = Some(next.map_or(deadline, |next: Instant| next.min(deadline)));
+            }
+        }
+        next
+    }
+
+    // applies the title and size bindings whose inputs changed to the window
+    fn apply_window_bindings(&mut self) {
+        let Some(active) = &self.active else { return; };
+        if let Some(title) = &self.title_binding {
+            if title.maybe_update(&mut self.state) {
+                self.attributes.title = title.get_untracked();
+                active.window.set_title(&self.attributes.title);
+            }
+        }
+        if let Some(size) = &self.size_binding {
+            if size.maybe_update(&mut self.state) {
+                let size = size.get_untracked();
+                let size = LogicalSize::new(size.width(), size.height());
+                self.attributes.inner_size = Some(size.into());
+                let _ = active.window.request_inner_size(size);
+            }
+        }
+    }
+
+    fn redraw(&mut self) -> Result<()> {
+        let Application { active, presenter, state, to_draw, text, pool, viewport, scale_factor, .. } = self;
+        let Some(ActiveApplication { window }) = active else { return Ok(()); };
+
+        presenter.present(window, &mut |pixmap| {
+            timed("Update Model", || to_draw.update(state));
+            timed("Update Layout", || to_draw.layout(text, *viewport, *scale_factor));
+
+            timed("Update Interactions", || to_draw.interactions());
+
+            let theme = to_draw.theme();
+            let mut render_context = RenderContext {
+                canvas: pixmap,
+                text,
+                pool,
+                theme: &theme,
+                overlays: &Overlays::new()
+            };
+            timed("Drawing", || to_draw.draw_damage(&mut render_context))
+        })
     }
 }
 
 impl<A> winit::application::ApplicationHandler for Application<A> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let window = Rc::new(event_loop.create_window(WindowAttributes::default()).unwrap());
-        let context = softbuffer::Context::new(Rc::clone(&window)).unwrap();
-        let surface = Surface::new(&context, Rc::clone(&window)).unwrap();
-
-        // self.viewport =
-        self.scale_factor = window.scale_factor() as f32;
-        self.active = Some(ActiveApplication {
-            window,
-            _context: context,
-            surface
-        })
+        let attributes = self.attributes.clone().with_decorations(self.decorations);
+        match ActiveApplication::new(event_loop, attributes, self.presenter.as_mut()) {
+            Ok(active) => {
+                // self.viewport =
+                self.scale_factor = active.window.scale_factor() as f32;
+                self.active = Some(active);
+                self.apply_window_bindings();
+            }
+            Err(error) => self.fail(event_loop, error)
+        }
+
+        #[cfg(feature = "tray")]
+        if let (Some(tray), Some(proxy)) = (&mut self.tray, &self.proxy) {
+            if let Err(error) = tray.activate(proxy) {
+                self.fail(event_loop, error);
+            }
+        }
+    }
+
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, _event: ()) {
+        let mut handled = channel::pump_channels();
+        handled |= self.dispatch_messages();
+        #[cfg(feature = "tray")]
+        if let Some(tray) = &self.tray {
+            handled |= tray.poll(&mut self.state);
+        }
+        #[cfg(feature = "notify")]
+        if let Some(notifier) = &self.notifier {
+            handled |= notifier.poll(&mut self.state);
+        }
+
+        if handled && self.to_draw.needs_redraw() {
+            if let Some(active) = &self.active {
+                active.window.request_redraw();
+            }
+        }
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _window_id: WindowId, event: WindowEvent) {
-        let Some(ActiveApplication { window, _context: _, surface }) = &mut self.active else { return; };
+        if matches!(event, WindowEvent::CursorMoved { .. } | WindowEvent::MouseInput { .. } | WindowEvent::MouseWheel { .. } | WindowEvent::KeyboardInput { .. } | WindowEvent::Touch(_)) {
+            self.record_input();
+        }
+        let Some(ActiveApplication { window, .. }) = &mut self.active else { return; };
 
         match event {
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
@@ -92,25 +509,53 @@ impl<A> winit::application::ApplicationHandler for Application<A> {
                 window.request_redraw();
             }
             WindowEvent::RedrawRequested => {
-                let size = window.inner_size();
-                let (Some(width), Some(height)) = (NonZeroU32::new(size.width), NonZeroU32::new(size.height)) else { return; };
-                surface.resize(width, height).unwrap();
-                let mut buffer = surface.buffer_mut().unwrap();
-                let mut pixmap = PixmapMut::from_bytes(bytemuck::must_cast_slice_mut(buffer.as_mut()), size.width, size.height).unwrap();
-                pixmap.fill(Color::WHITE.into());
-
-                timed("Update Model", || self.to_draw.update(&mut self.state));
-                timed("Update Layout", || self.to_draw.layout(self.viewport, self.scale_factor));
-
-                timed("Update Interactions", || self.to_draw.interactions());
-
-                let mut render_context = RenderContext {
-                    canvas: pixmap
-                };
-                timed("Drawing", || self.to_draw.draw(&mut render_context));
-
-                window.pre_present_notify();
-                buffer.present().unwrap();
+                if let Err(error) = self.redraw() {
+                    self.fail(event_loop, error);
+                }
+            }
+            WindowEvent::KeyboardInput { event, .. } => {
+                let mut handled = false;
+                if let Some(input) = self.interaction_state.key_input(&event) {
+                    handled = self.to_draw.handle_key(input, &mut self.state) || self.shortcuts.handle(&input, &mut self.state);
+                }
+                if let Some(input) = self.interaction_state.key_release(&event) {
+                    handled |= self.to_draw.handle_key_release(input, &mut self.state);
+                }
+                if let Some((text, modifiers)) = self.interaction_state.text_input(&event) {
+                    handled |= self.to_draw.handle_text(text, modifiers, &mut self.state);
+                }
+                if handled && self.to_draw.needs_redraw() {
+                    window.request_redraw();
+                }
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = math::Point::new(position.x as f32, position.y as f32);
+                if !self.decorations {
+                    self.resize_zone = resize_direction(position, self.viewport, self.resize_border * self.scale_factor);
+                }
+                let was_handled = self.interaction_state.handle_window_event(event, |interact| self.to_draw.handle_interaction(&interact, &mut self.state));
+                // the resize arrows near the edges win over whatever the widgets there ask for
+                let cursor = self.resize_zone.map_or_else(|| cursor_icon(self.to_draw.cursor(position)), resize_cursor);
+                if cursor != self.cursor {
+                    window.set_cursor(cursor);
+                    self.cursor = cursor;
+                }
+                if was_handled && self.to_draw.needs_redraw() {
+                    window.request_redraw();
+                }
+            }
+            WindowEvent::MouseInput { button: MouseButton::Left, state: ElementState::Pressed, .. } if self.resize_zone.is_some() => {
+                if let Some(direction) = self.resize_zone {
+                    match window.drag_resize_window(direction) {
+                        Ok(()) => {}
+                        // the platform can't resize windows this way, so the edges stop offering it
+                        Err(ExternalError::NotSupported(_)) => {
+                            self.resize_border = 0.0;
+                            self.resize_zone = None;
+                        }
+                        Err(error) => self.fail(event_loop, error.into())
+                    }
+                }
             }
             WindowEvent::CloseRequested => {
                 self.active = None;
@@ -125,11 +570,24 @@ impl<A> winit::application::ApplicationHandler for Application<A> {
         }
     }
 
-    // fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
-    //     if self.active.is_none() {
-    //         event_loop.exit();
-    //     }
-    // }
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let next_timer = self.timers.as_ref().and_then(|timers| timers.run_due(&mut self.state));
+        let next = [self.poll_idle(), tracking::poll_wakeups(), next_timer].into_iter().flatten().min();
+        if self.run_frame_callbacks() {
+            event_loop.set_control_flow(ControlFlow::Poll);
+        } else {
+            event_loop.set_control_flow(next.map_or(ControlFlow::Wait, ControlFlow::WaitUntil));
+        }
+        // values sent without waking the loop are picked up along with other events
+        channel::pump_channels();
+        self.dispatch_messages();
+        self.apply_window_bindings();
+        if let Some(active) = &self.active {
+            if self.to_draw.needs_redraw() {
+                active.window.request_redraw();
+            }
+        }
+    }
 }
 
 
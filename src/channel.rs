@@ -0,0 +1,118 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::sync::mpsc::{self, Receiver, SendError, Sender};
+use std::sync::{Arc, Mutex};
+
+use crate::tracking::{ReadableSignal, RwSignal};
+
+
+type Waker = Arc<dyn Fn() + Send + Sync>;
+
+static WAKER: Mutex<Option<Waker>> = Mutex::new(None);
+
+trait Pump {
+    fn pump(&self) -> bool;
+}
+
+thread_local! {
+    static PUMPS: RefCell<Vec<Weak<dyn Pump>>> = const { RefCell::new(Vec::new()) };
+}
+
+// called by the application once its event loop exists
+#[cfg_attr(not(feature = "window"), allow(dead_code))]
+pub(crate) fn set_waker(wake: impl Fn() + Send + Sync + 'static) {
+    *WAKER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(wake));
+}
+
+/// Moves the latest value from every channel into its `ReceiverSignal`, returning whether any
+/// signal changed. The application does this whenever its event loop wakes up; code that drives a
+/// tree itself should call it too.
+pub fn pump_channels() -> bool {
+    let pumps: Vec<_> = PUMPS.with_borrow_mut(|pumps| {
+        pumps.retain(|pump| pump.strong_count() > 0);
+        pumps.iter().filter_map(Weak::upgrade).collect()
+    });
+    pumps.iter().fold(false, |changed, pump| pump.pump() | changed)
+}
+
+/// Creates a channel whose sender wakes the event loop, so values sent from a worker thread reach
+/// a `ReceiverSignal` without waiting for other events.
+pub fn channel<T>() -> (WakingSender<T>, Receiver<T>) {
+    let (sender, receiver) = mpsc::channel();
+    (WakingSender { sender }, receiver)
+}
+
+/// The sending half of `channel`.
+pub struct WakingSender<T> {
+    sender: Sender<T>
+}
+
+impl<T> WakingSender<T> {
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.sender.send(value)?;
+        let waker = WAKER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        if let Some(wake) = waker {
+            wake();
+        }
+        Ok(())
+    }
+}
+
+impl<T> Clone for WakingSender<T> {
+    fn clone(&self) -> Self {
+        WakingSender { sender: self.sender.clone() }
+    }
+}
+
+struct ReceiverInner<T> {
+    signal: RwSignal<T>,
+    receiver: Receiver<T>
+}
+
+impl<T> Pump for ReceiverInner<T> {
+    fn pump(&self) -> bool {
+        // only the latest value matters, intermediate ones would be overwritten before being drawn
+        let Some(latest) = self.receiver.try_iter().last() else { return false; };
+        self.signal.update(|value| *value = latest);
+        true
+    }
+}
+
+/// A signal holding the latest value received from a channel, see `RwSignal::from_receiver`.
+/// Clones share the same signal.
+pub struct ReceiverSignal<T> {
+    inner: Rc<ReceiverInner<T>>
+}
+
+impl<T: 'static> ReceiverSignal<T> {
+    pub(crate) fn new(initial: T, receiver: Receiver<T>) -> ReceiverSignal<T> {
+        let inner = Rc::new(ReceiverInner { signal: RwSignal::new(initial), receiver });
+        let pump: Rc<dyn Pump> = inner.clone();
+        PUMPS.with_borrow_mut(|pumps| pumps.push(Rc::downgrade(&pump)));
+        ReceiverSignal { inner }
+    }
+
+    pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        self.inner.signal.with(f)
+    }
+}
+
+impl<T> Clone for ReceiverSignal<T> {
+    fn clone(&self) -> Self {
+        ReceiverSignal { inner: Rc::clone(&self.inner) }
+    }
+}
+
+impl<T: Clone> ReadableSignal<T> for ReceiverSignal<T> {
+    fn get(&self) -> T {
+        self.inner.signal.get()
+    }
+
+    fn get_untracked(&self) -> T {
+        self.inner.signal.get_untracked()
+    }
+
+    fn track(&self) {
+        self.inner.signal.track()
+    }
+}
@@ -0,0 +1,83 @@
+use std::cell::RefCell;
+
+
+/// Somewhere copied text can be put and read back from, e.g. the system clipboard.
+pub trait Clipboard {
+    fn set_text(&mut self, text: String);
+    fn text(&mut self) -> Option<String>;
+}
+
+/// Keeps copied text in memory, so it can only be pasted within the same thread. Used when there
+/// is no system clipboard, e.g. in tests or without the `window` feature.
+#[derive(Default)]
+pub struct MemoryClipboard {
+    text: Option<String>
+}
+
+impl Clipboard for MemoryClipboard {
+    fn set_text(&mut self, text: String) {
+        self.text = Some(text);
+    }
+
+    fn text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+}
+
+/// The system clipboard, shared with other applications.
+#[cfg(feature = "window")]
+pub struct SystemClipboard {
+    clipboard: arboard::Clipboard
+}
+
+#[cfg(feature = "window")]
+impl SystemClipboard {
+    pub fn new() -> crate::Result<SystemClipboard> {
+        Ok(SystemClipboard { clipboard: arboard::Clipboard::new()? })
+    }
+}
+
+#[cfg(feature = "window")]
+impl Clipboard for SystemClipboard {
+    // the system can refuse the text, e.g. while another application holds the clipboard, and
+    // then nothing is copied
+    fn set_text(&mut self, text: String) {
+        let _ = self.clipboard.set_text(text);
+    }
+
+    fn text(&mut self) -> Option<String> {
+        self.clipboard.get_text().ok()
+    }
+}
+
+// the system clipboard where there is one
+fn default_clipboard() -> Box<dyn Clipboard> {
+    #[cfg(feature = "window")]
+    if let Ok(clipboard) = SystemClipboard::new() {
+        return Box::new(clipboard);
+    }
+    Box::new(MemoryClipboard::default())
+}
+
+thread_local! {
+    static CLIPBOARD: RefCell<Box<dyn Clipboard>> = RefCell::new(default_clipboard());
+}
+
+/// Makes widgets copy to and paste from `clipboard` on this thread.
+///
+/// With the `window` feature, widgets use a `SystemClipboard` until this is called, or a
+/// `MemoryClipboard` if the system has none, as when running headless. Without it they always
+/// start with a `MemoryClipboard`.
+pub fn set_clipboard(clipboard: impl Clipboard + 'static) {
+    CLIPBOARD.with_borrow_mut(|current| *current = Box::new(clipboard));
+}
+
+/// Puts `text` on the clipboard, replacing what was there.
+pub fn copy(text: String) {
+    CLIPBOARD.with_borrow_mut(|clipboard| clipboard.set_text(text));
+}
+
+/// The text on the clipboard, if there is any.
+pub fn paste() -> Option<String> {
+    CLIPBOARD.with_borrow_mut(|clipboard| clipboard.text())
+}
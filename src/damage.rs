@@ -0,0 +1,97 @@
+use std::cell::RefCell;
+
+use tiny_skia::{Pixmap, PixmapMut};
+
+use crate::math;
+
+
+// past this many rects, the pending damage is merged into the one rect around all of them
+const MAX_RECTS: usize = 32;
+
+/// The part of the canvas a frame drew again, see `Root::draw_damage`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Damage {
+    /// The whole canvas.
+    Full,
+    /// Only these rects, in physical pixels and within the canvas. None if nothing changed.
+    Rects(Vec<math::Rect>)
+}
+
+impl Damage {
+    /// Whether the frame left the canvas as it was.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Damage::Rects(rects) if rects.is_empty())
+    }
+}
+
+// the rects of a tree which need to be drawn again, collected between frames
+#[derive(Default)]
+pub(crate) struct DamageRegion {
+    rects: RefCell<Vec<math::Rect>>
+}
+
+impl DamageRegion {
+    pub fn add(&self, rect: math::Rect) {
+        let mut rects = self.rects.borrow_mut();
+        if rect.w <= 0.0 || rect.h <= 0.0 || rects.iter().any(|damaged| covers(damaged, &rect)) {
+            return;
+        }
+        rects.retain(|damaged| !covers(&rect, damaged));
+        rects.push(rect);
+        if rects.len() > MAX_RECTS {
+            let merged = math::Rect::bounding_box(rects.drain(..));
+            rects.extend(merged);
+        }
+    }
+
+    pub fn take(&self) -> Vec<math::Rect> {
+        self.rects.take()
+    }
+}
+
+// the smallest rect of whole pixels around `rect` which lies within a canvas of `width` by `height`
+pub(crate) fn pixel_bounds(rect: math::Rect, width: u32, height: u32) -> Option<math::Rect> {
+    let canvas = math::Rect::from_xywh(0.0, 0.0, width as f32, height as f32);
+    let rounded = math::Rect::from_lrtb(rect.left().floor(), rect.right().ceil(), rect.top().floor(), rect.bottom().ceil());
+    rounded.intersection(&canvas)
+}
+
+// copies the pixels of `rect`, which has to lie on whole pixels within both, from `from` to `to`
+pub(crate) fn copy_rect(from: &Pixmap, to: &mut PixmapMut, rect: math::Rect) {
+    let (left, right) = (rect.left() as usize * 4, rect.right() as usize * 4);
+    let (from_stride, to_stride) = (from.width() as usize * 4, to.width() as usize * 4);
+    let (from_data, to_data) = (from.data(), to.data_mut());
+    for row in rect.top() as usize..rect.bottom() as usize {
+        to_data[row * to_stride + left..row * to_stride + right].copy_from_slice(&from_data[row * from_stride + left..row * from_stride + right]);
+    }
+}
+
+fn covers(outer: &math::Rect, inner: &math::Rect) -> bool {
+    outer.left() <= inner.left() && inner.right() <= outer.right() && outer.top() <= inner.top() && inner.bottom() <= outer.bottom()
+}
+
+thread_local! {
+    // the rects being drawn again while a root only draws its damage
+    static REDRAWN: RefCell<Option<Vec<math::Rect>>> = const { RefCell::new(None) };
+}
+
+// clears the rects being drawn again even if drawing panics
+struct RedrawnGuard;
+
+impl Drop for RedrawnGuard {
+    fn drop(&mut self) {
+        REDRAWN.set(None);
+    }
+}
+
+// runs `draw`, during which elements whose bounds miss every rect of `rects` aren't drawn
+pub(crate) fn redraw_only<T>(rects: &[math::Rect], draw: impl FnOnce() -> T) -> T {
+    REDRAWN.set(Some(rects.to_vec()));
+    let _guard = RedrawnGuard;
+    draw()
+}
+
+// whether an element drawing within `bounds` has to be drawn
+pub(crate) fn is_redrawn(bounds: math::Rect) -> bool {
+    REDRAWN.with_borrow(|rects| rects.as_ref().is_none_or(|rects| rects.iter().any(|rect| rect.intersects(&bounds))))
+}
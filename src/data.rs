@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::tracking::{ReadableSignal, RwSignal, TrackedVec};
+
+
+/// A source of rows for list and table widgets that does not have to keep every row in memory.
+///
+/// Reading `len` or a row registers a dependency on the provider, so computations which read it
+/// rerun when the provider reports a change.
+pub trait DataProvider {
+    type Row;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn row(&self, index: usize) -> Option<Self::Row>;
+
+    /// Registers a dependency on the provider's contents without reading anything.
+    fn track(&self);
+}
+
+impl<T: Clone> DataProvider for TrackedVec<T> {
+    type Row = T;
+
+    fn len(&self) -> usize {
+        self.with(|items| items.len())
+    }
+
+    fn row(&self, index: usize) -> Option<T> {
+        self.with(|items| items.get(index).cloned())
+    }
+
+    fn track(&self) {
+        self.with(|_| ());
+    }
+}
+
+
+/// Serves the rows of a CSV file on demand.
+///
+/// Opening the file only records where each row starts; rows are read and parsed when asked for.
+/// Quoted fields may contain commas and escaped quotes, but not line breaks.
+pub struct CsvProvider {
+    path: PathBuf,
+    reader: RefCell<BufReader<File>>,
+    header: Option<Vec<String>>,
+    offsets: Vec<u64>,
+    revision: RwSignal<u64>
+}
+
+impl CsvProvider {
+    /// Opens `path`, treating the first line as a header if `has_header` is set.
+    pub fn open(path: impl AsRef<Path>, has_header: bool) -> Result<CsvProvider> {
+        let path = path.as_ref().to_path_buf();
+        let mut reader = BufReader::new(File::open(&path)?);
+        let (header, offsets) = CsvProvider::index(&mut reader, has_header)?;
+        Ok(CsvProvider {
+            path,
+            reader: RefCell::new(reader),
+            header,
+            offsets,
+            revision: RwSignal::new(0)
+        })
+    }
+
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+
+    /// Reindexes the file after it has changed on disk, notifying everything that read from it.
+    pub fn reload(&mut self) -> Result<()> {
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let (header, offsets) = CsvProvider::index(&mut reader, self.header.is_some())?;
+        self.reader = RefCell::new(reader);
+        self.header = header;
+        self.offsets = offsets;
+        self.revision.update(|revision| *revision += 1);
+        Ok(())
+    }
+
+    fn index(reader: &mut BufReader<File>, has_header: bool) -> Result<(Option<Vec<String>>, Vec<u64>)> {
+        let mut header = None;
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            if has_header && header.is_none() {
+                header = Some(parse_line(&line));
+            } else if !line.trim_end_matches(['\r', '\n']).is_empty() {
+                offsets.push(offset);
+            }
+            offset += read as u64;
+        }
+        Ok((header, offsets))
+    }
+}
+
+impl DataProvider for CsvProvider {
+    type Row = Vec<String>;
+
+    fn len(&self) -> usize {
+        self.revision.track();
+        self.offsets.len()
+    }
+
+    fn row(&self, index: usize) -> Option<Vec<String>> {
+        self.revision.track();
+        let offset = *self.offsets.get(index)?;
+        let mut reader = self.reader.borrow_mut();
+        let mut line = String::new();
+        reader.seek(SeekFrom::Start(offset)).ok()?;
+        reader.read_line(&mut line).ok()?;
+        Some(parse_line(&line))
+    }
+
+    fn track(&self) {
+        self.revision.track();
+    }
+}
+
+fn parse_line(line: &str) -> Vec<String> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            (c, _) => field.push(c)
+        }
+    }
+    fields.push(field);
+    fields
+}
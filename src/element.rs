@@ -1,78 +1,623 @@
-use crate::{math, RenderContext, Widget};
-use crate::interact::{Interaction, InteractSet};
-use crate::layout::{PrelayoutInput, LayoutCharacteristics, LayoutInput};
-use crate::tracking::{Computed};
+use std::any::TypeId;
+use std::cell::Cell;
 
+use tiny_skia::Pixmap;
 
-pub struct Root<A>(Element<A>, Computed<()>);
+use crate::{math, Layout, RenderContext, Widget};
+use crate::damage::{self, Damage, DamageRegion};
+use crate::focus::{self, FocusFinder, FocusId};
+use crate::interact::{Cursor, Interaction, InteractSet};
+use crate::overflow::{Overflow, OverflowCheck, OverflowFinder};
+use crate::layout::{PrelayoutInput, LayoutCharacteristics, LayoutContext, LayoutInput};
+use crate::modal::Modals;
+use crate::print::BreakHint;
+use crate::safe_area::SafeArea;
+use crate::shortcut::{KeyInput, Modifiers};
+use crate::style::Color;
+use crate::text::TextContext;
+use crate::theme::Theme;
+use crate::tracking::{self, Computed, Computed2, Derived, ReadableSignal, RwSignal, Watch, WatchState};
+
+
+/// Whether a listener consumed an interaction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Handled {
+    Yes,
+    No
+}
+
+type Listener<A> = Box<dyn Fn(&Interaction, &mut A) -> Handled>;
+type OverflowListener = Box<dyn Fn(&Overflow)>;
+
+// the canvas and theme a root last drew with, and whether anything was drawn over the tree then
+#[derive(Copy, Clone, PartialEq)]
+struct DrawnFrame {
+    width: u32,
+    height: u32,
+    theme: Theme,
+    layered: bool
+}
+
+// drawn over the viewport beneath each modal
+const MODAL_DIM: Color = Color::from_rgba8(0, 0, 0, 96);
+
+/// The top of a widget tree, and the interface the application drives it through.
+///
+/// A frame calls `update`, `layout`, `interactions` and `draw`, in that order; each step relies on
+/// the caches filled in by the ones before it. Input goes through `handle_interaction` and
+/// `handle_key` between frames, and `needs_redraw` tells whether the model changed in a way the
+/// tree has not caught up with yet.
+pub struct Root<A> {
+    element: Element<A>,
+    update_cache: Computed<()>,
+    layout_cache: Computed2<LayoutInput, ()>,
+    safe_area: Option<SafeArea>,
+    overflow_check: OverflowCheck,
+    // what overflowed in the last layout pass
+    overflows: Vec<Overflow>,
+    on_overflow: Option<OverflowListener>,
+    focused: Option<FocusId>,
+    theme: RwSignal<Theme>,
+    // picks the theme from the model, see `bind_theme`
+    theme_binding: Option<Derived<A, Theme>>,
+    modals: Modals<A>,
+    // whether a modal was open at the last update, to notice one opening
+    modal_open: bool,
+    // where the tree changed since it was last drawn, see `draw_damage`
+    damage: DamageRegion,
+    // what `draw_damage` last drew, to tell whether the next frame can reuse it
+    last_drawn: Option<DrawnFrame>,
+    // the tree is redrawn here before the damaged parts are copied to the canvas
+    scratch: Option<Pixmap>,
+
+    before_listeners: Vec<Listener<A>>,
+    after_listeners: Vec<Listener<A>>
+}
 
 impl<A> Root<A> {
     pub fn new(element: Element<A>) -> Root<A> {
-        Root(element, Computed::new())
+        Root {
+            element,
+            update_cache: Computed::new(),
+            layout_cache: Computed2::new(),
+            safe_area: None,
+            overflow_check: OverflowCheck::Off,
+            overflows: Vec::new(),
+            on_overflow: None,
+            focused: None,
+            theme: RwSignal::new(Theme::default()),
+            theme_binding: None,
+            modals: Modals::new(),
+            modal_open: false,
+            damage: DamageRegion::default(),
+            last_drawn: None,
+            scratch: None,
+
+            before_listeners: Vec::new(),
+            after_listeners: Vec::new()
+        }
+    }
+
+    /// Lays the tree out inside `safe_area` instead of the whole viewport.
+    pub fn with_safe_area(mut self, safe_area: SafeArea) -> Root<A> {
+        self.safe_area = Some(safe_area);
+        self
+    }
+
+    pub fn with_theme(self, theme: Theme) -> Root<A> {
+        self.set_theme(theme);
+        self
+    }
+
+    /// Takes the theme from the model, so that the model can switch it at runtime, e.g. between
+    /// `Theme::light()` and `Theme::dark()`. The closure is rerun whenever a signal it reads
+    /// changes.
+    pub fn bind_theme(mut self, theme: impl Fn(&mut A) -> Theme + 'static) -> Root<A> {
+        self.theme_binding = Some(Derived::new_with_initial(Theme::default(), theme));
+        self
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme.get_untracked()
+    }
+
+    /// Switches to `theme`. The tree is relaid out and redrawn with it on the next frame.
+    pub fn set_theme(&self, theme: Theme) {
+        if self.theme.get_untracked() != theme {
+            self.theme.update(|current| *current = theme);
+        }
     }
 
+    /// Shows the modals pushed to `modals` over the tree, see `Modals`. A root has a stack of its
+    /// own otherwise, see `modals`.
+    pub fn with_modals(mut self, modals: Modals<A>) -> Root<A> {
+        self.modals = modals;
+        self
+    }
+
+    /// The stack of modals shown over the tree.
+    pub fn modals(&self) -> Modals<A> {
+        self.modals.clone()
+    }
+
+    // runs `f` on the element input goes to: the topmost modal, or the tree if none is open
+    fn with_target<O>(&mut self, f: impl FnOnce(&mut Element<A>) -> O) -> O {
+        match self.modals.top() {
+            Some(modal) => f(&mut modal.borrow_mut()),
+            None => f(&mut self.element)
+        }
+    }
+
+    /// Checks after every layout pass whether any element sticks out of its parent's content box,
+    /// and reports or highlights the ones that do. Meant for debugging, since it walks the
+    /// whole tree every frame.
+    pub fn with_overflow_check(mut self, overflow_check: OverflowCheck) -> Root<A> {
+        self.overflow_check = overflow_check;
+        self
+    }
+
+    /// Calls `listener` with each element which starts overflowing, see `OverflowCheck::Report`.
+    pub fn on_overflow(mut self, listener: impl Fn(&Overflow) + 'static) -> Root<A> {
+        self.on_overflow = Some(Box::new(listener));
+        self
+    }
+
+    /// The elements which overflowed in the last layout pass, while an overflow check is on.
+    pub fn overflows(&self) -> &[Overflow] {
+        &self.overflows
+    }
+
+    /// Calls `listener` with every interaction and key press before the tree sees it. Returning
+    /// `Handled::Yes` stops the interaction from reaching the tree, the application's shortcuts
+    /// and later listeners.
+    pub fn on_any_interaction(mut self, listener: impl Fn(&Interaction, &mut A) -> Handled + 'static) -> Root<A> {
+        self.before_listeners.push(Box::new(listener));
+        self
+    }
+
+    /// Calls `listener` with every interaction after the tree has handled it. Returning
+    /// `Handled::Yes` stops later listeners from seeing it.
+    pub fn after_any_interaction(mut self, listener: impl Fn(&Interaction, &mut A) -> Handled + 'static) -> Root<A> {
+        self.after_listeners.push(Box::new(listener));
+        self
+    }
+
+    fn insets(&self) -> math::SizeRect {
+        self.safe_area.as_ref().map_or(0.0.into(), |safe_area| safe_area.insets())
+    }
+
+    fn notify(listeners: &[Listener<A>], interaction: &Interaction, model: &mut A) -> Handled {
+        match listeners.iter().any(|listener| listener(interaction, model) == Handled::Yes) {
+            true => Handled::Yes,
+            false => Handled::No
+        }
+    }
+
+    /// Whether a signal the tree read has changed since the last `update` or `layout`.
     pub fn needs_redraw(&self) -> bool {
-        self.1.is_dirty()
+        self.update_cache.is_dirty() || self.layout_cache.is_dirty()
     }
 
+    /// Sends a pointer interaction to the widgets whose regions accept it. A click also moves the
+    /// focus to the innermost focusable widget under it, or clears it.
     pub fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
-        self.0.handle_interaction(interaction, model)
+        if Root::notify(&self.before_listeners, interaction, model) == Handled::Yes {
+            return;
+        }
+        if let &Interaction::Click(point) = interaction {
+            let mut finder = FocusFinder::new(point);
+            self.with_target(|target| target.visit(&mut finder));
+            self.set_focus(finder.found, model);
+        }
+        self.with_target(|target| {
+            if target.interactions().accepts(interaction) {
+                target.handle_interaction(interaction, model)
+            }
+        });
+        Root::notify(&self.after_listeners, interaction, model);
     }
 
+    /// The widget holding the keyboard focus, if any.
+    pub fn focused(&self) -> Option<FocusId> {
+        self.focused
+    }
+
+    /// Moves the keyboard focus to `focus`, or clears it, telling the widgets which gain or lose it.
+    pub fn set_focus(&mut self, focus: Option<FocusId>, model: &mut A) {
+        if focus != self.focused {
+            self.focused = focus;
+            let interaction = Interaction::Focus(focus);
+            self.with_target(|target| {
+                if target.interactions().accepts(&interaction) {
+                    target.handle_interaction(&interaction, model);
+                }
+            });
+        }
+    }
+
+    /// Sends a key press to the focused widget if there is one, and unless it acts on the key, to
+    /// the highest-priority active `InputScope`. Returns false if neither took it and the key
+    /// should go to the application's shortcuts instead.
+    pub fn handle_key(&mut self, input: KeyInput, model: &mut A) -> bool {
+        let scope = self.with_target(|target| target.interactions().key_scope);
+        let interaction = Interaction::Key(input, scope);
+        if Root::notify(&self.before_listeners, &interaction, model) == Handled::Yes {
+            return true;
+        }
+        let mut used = false;
+        if let Some(target) = self.focused {
+            let key_down = Interaction::KeyDown(input, target);
+            focus::take_key_used();
+            self.with_target(|target| {
+                if target.interactions().accepts(&key_down) {
+                    target.handle_interaction(&key_down, model);
+                }
+            });
+            used = focus::take_key_used();
+        }
+        if !used && scope > 0 {
+            self.with_target(|target| target.handle_interaction(&interaction, model));
+            used = true;
+        }
+        Root::notify(&self.after_listeners, &interaction, model);
+        used
+    }
+
+    /// Sends a key release to the focused widget, returning false if there is none.
+    pub fn handle_key_release(&mut self, input: KeyInput, model: &mut A) -> bool {
+        let Some(target) = self.focused else { return false; };
+        self.handle_interaction(&Interaction::KeyUp(input, target), model);
+        true
+    }
+
+    /// Sends typed text to the focused widget, returning false if there is none.
+    pub fn handle_text(&mut self, text: String, modifiers: Modifiers, model: &mut A) -> bool {
+        let Some(target) = self.focused else { return false; };
+        self.handle_interaction(&Interaction::Text { text, modifiers, target }, model);
+        true
+    }
+
+    /// Brings the tree up to date with the model. Must be called before `layout`.
     pub fn update(&mut self, model: &mut A) {
-        self.1.maybe_update(|| {
-            self.0.update(model)
+        self.element.collect_damage(|element| &element.update_watch, &self.damage);
+        self.update_cache.maybe_update(|| {
+            if let Some(safe_area) = &self.safe_area {
+                safe_area.track();
+            }
+            if let Some(binding) = &self.theme_binding {
+                if binding.maybe_update_if_changed(model) {
+                    self.set_theme(binding.get_untracked());
+                }
+                binding.track();
+            }
+            self.element.update(model);
+            for modal in self.modals.elements() {
+                modal.borrow().update(model);
+            }
         });
+        // the tree loses the focus and the hover once a modal covers it
+        let modal_open = self.modals.top().is_some();
+        if modal_open && !self.modal_open {
+            for interaction in [Interaction::Focus(None), Interaction::Hover(None)] {
+                if self.element.interactions().accepts(&interaction) {
+                    self.element.handle_interaction(&interaction, model);
+                }
+            }
+            self.focused = None;
+        }
+        self.modal_open = modal_open;
     }
 
-    pub fn layout(&mut self, viewport: math::Size, scale_factor: f32) {
-        let _ = self.0.layout(LayoutInput {
-            allocated: math::Rect::from_topleft_size((0.0, 0.0).into(), viewport),
-            scale_factor
-        });
+    /// The size the tree needs when given `available` space.
+    pub fn measure(&mut self, text: &TextContext, available: math::Size, scale_factor: f32) -> math::Size {
+        let insets = self.insets().sum_axes();
+        let viewport = math::Rect::from_topleft_size((0.0, 0.0).into(), available).shrink_by(self.insets()).clamp_positive();
+        let ctx = LayoutContext::new(text, &self.theme, viewport, &self.damage);
+        let characteristics = self.element.prelayout(&ctx, PrelayoutInput { available: available - insets, scale_factor });
+        characteristics.min_size + insets
     }
 
-    // todo does this really need to be called from the loop?
+    /// Lays the tree out to fill `viewport`, which is in physical pixels.
+    pub fn layout(&mut self, text: &TextContext, viewport: math::Size, scale_factor: f32) {
+        let allocated = math::Rect::from_topleft_size((0.0, 0.0).into(), viewport).shrink_by(self.insets()).clamp_positive();
+        let input = LayoutInput { allocated, scale_factor };
+        let mut laid_out = false;
+        self.element.collect_damage(|element| &element.layout_watch, &self.damage);
+        tracking::batch(|| self.layout_cache.maybe_update(input, |&input| {
+            // colors only show up when drawing, so a new theme needs a redraw even if no widget
+            // lays out differently with it
+            self.theme.track();
+            let ctx = LayoutContext::new(text, &self.theme, input.allocated, &self.damage);
+            self.element.layout(&ctx, input);
+            for modal in self.modals.elements() {
+                modal.borrow().layout(&ctx, input);
+            }
+            laid_out = true;
+        }));
+        if laid_out && self.overflow_check != OverflowCheck::Off {
+            self.check_overflow();
+        }
+    }
+
+    fn check_overflow(&mut self) {
+        let mut finder = OverflowFinder::default();
+        self.element.visit(&mut finder);
+        if let (OverflowCheck::Report, Some(listener)) = (self.overflow_check, &self.on_overflow) {
+            for overflow in &finder.found {
+                if !self.overflows.iter().any(|previous| previous.path == overflow.path) {
+                    listener(overflow);
+                }
+            }
+        }
+        self.overflows = finder.found;
+    }
+
+    /// Collects where the tree wants input after layout, so that the next interaction is routed
+    /// against the new positions.
     pub fn interactions(&mut self) {
-        self.0.interactions();
+        tracking::batch(|| {
+            self.element.interactions();
+            for modal in self.modals.elements() {
+                modal.borrow().interactions();
+            }
+        });
     }
 
+    /// Draws the tree as it was last laid out. Signals written while drawing only take effect
+    /// once drawing is done, see `tracking::batch`.
     pub fn draw(&mut self, context: &mut RenderContext) {
-        self.0.draw(context);
+        self.draw_layers(context);
+    }
+
+    // draws the tree, then each modal over it, and returns whether any overlays were composited
+    fn draw_layers(&mut self, context: &mut RenderContext) -> bool {
+        tracking::batch(|| self.element.draw(context));
+        let mut overlaid = context.overlays.composite(&mut context.canvas, context.pool);
+        for modal in self.modals.elements() {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(MODAL_DIM.into());
+            let (width, height) = (context.canvas.width() as f32, context.canvas.height() as f32);
+            if let Some(viewport) = tiny_skia::Rect::from_xywh(0.0, 0.0, width, height) {
+                context.canvas.fill_rect(viewport, &paint, tiny_skia::Transform::identity(), None);
+            }
+            tracking::batch(|| modal.borrow_mut().draw(context));
+            overlaid |= context.overlays.composite(&mut context.canvas, context.pool);
+        }
+        if self.overflow_check == OverflowCheck::Highlight {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(Color::RED.into());
+            let stroke = tiny_skia::Stroke { width: 2.0, ..Default::default() };
+            for overflow in &self.overflows {
+                if let Ok(rect) = tiny_skia::Rect::try_from(overflow.margin_box) {
+                    let path = tiny_skia::PathBuilder::from_rect(rect);
+                    context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+                }
+            }
+        }
+        overlaid
+    }
+
+    /// Like `draw`, but only draws the parts of the tree which changed since the last call, and
+    /// returns where it drew. `context.canvas` must still hold the frame that call drew; the
+    /// background is filled in wherever the tree is drawn again.
+    ///
+    /// The whole canvas is drawn when it was resized, when the theme changed, and while modals,
+    /// overlays or overflow highlights are shown, since those cover more than the elements which
+    /// changed.
+    pub fn draw_damage(&mut self, context: &mut RenderContext) -> Damage {
+        let (width, height) = (context.canvas.width(), context.canvas.height());
+        let frame = DrawnFrame { width, height, theme: *context.theme, layered: false };
+        let layered = self.modals.top().is_some() || self.overflow_check == OverflowCheck::Highlight;
+        let rects = self.damage.take();
+        if layered || self.last_drawn != Some(frame) {
+            return self.draw_full(context, frame);
+        }
+
+        let rects: Vec<math::Rect> = rects.into_iter()
+            .filter_map(|rect| damage::pixel_bounds(rect.grow_by(1.0.into()), width, height))
+            .collect();
+        if rects.is_empty() {
+            return Damage::Rects(rects);
+        }
+        let scratch = self.scratch.take().filter(|scratch| scratch.width() == width && scratch.height() == height);
+        let Some(mut scratch) = scratch.or_else(|| Pixmap::new(width, height)) else {
+            return self.draw_full(context, frame);
+        };
+
+        let mut paint = tiny_skia::Paint::default();
+        paint.set_color(context.theme.background.into());
+        for rect in &rects {
+            if let Ok(rect) = tiny_skia::Rect::try_from(*rect) {
+                scratch.fill_rect(rect, &paint, tiny_skia::Transform::identity(), None);
+            }
+        }
+        let mut scratch_context = RenderContext { canvas: scratch.as_mut(), text: context.text, pool: context.pool, theme: context.theme, overlays: context.overlays };
+        damage::redraw_only(&rects, || tracking::batch(|| self.element.draw(&mut scratch_context)));
+        // an overlay can cover anything, so it is drawn over the whole tree instead
+        let overlaid = !context.overlays.is_empty();
+        context.overlays.composite(&mut scratch.as_mut(), context.pool);
+        self.scratch = Some(scratch);
+        if overlaid {
+            return self.draw_full(context, frame);
+        }
+
+        if let Some(scratch) = &self.scratch {
+            for rect in &rects {
+                damage::copy_rect(scratch, &mut context.canvas, *rect);
+            }
+        }
+        Damage::Rects(rects)
+    }
+
+    fn draw_full(&mut self, context: &mut RenderContext, frame: DrawnFrame) -> Damage {
+        context.canvas.fill(context.theme.background.into());
+        let layered = self.draw_layers(context) || self.modals.top().is_some() || self.overflow_check == OverflowCheck::Highlight;
+        self.last_drawn = Some(DrawnFrame { layered, ..frame });
+        Damage::Full
+    }
+
+    pub fn page_breaks(&self) -> Vec<BreakHint> {
+        let mut hints = Vec::new();
+        self.element.page_breaks(&mut hints);
+        hints
+    }
+
+    /// The cursor to show while the pointer is at `point`, in physical pixels.
+    pub fn cursor(&self, point: math::Point) -> Cursor {
+        let cursor = match self.modals.top() {
+            Some(modal) => modal.borrow().cursor(point),
+            None => self.element.cursor(point)
+        };
+        cursor.unwrap_or_default()
+    }
+
+    /// Shows `visitor` every element in the tree, then every open modal, see `Element::visit`.
+    pub fn visit(&self, visitor: &mut impl Visitor) {
+        self.element.visit(visitor);
+        for modal in self.modals.elements() {
+            modal.borrow().visit(visitor);
+        }
     }
 }
 
 
-pub struct Element<A>(Box<dyn Widget<A>>);
+/// What a `Visitor` is shown of an element.
+#[derive(Debug)]
+pub struct ElementInfo {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    /// How many elements enclose this one, starting at zero for the element `visit` was called on.
+    pub depth: usize,
+    /// The layout from the last layout pass, if the widget has one of its own.
+    pub layout: Option<Layout>,
+    /// The id the element takes the keyboard focus under, see `Widget::focus_id`.
+    pub focus: Option<FocusId>,
+    /// Whether the element cuts its children off at its content box, see `Widget::clips_children`.
+    pub clips_children: bool,
+    pub interactions: InteractSet
+}
+
+/// Walks a tree of elements in depth-first order, see `Element::visit`.
+pub trait Visitor {
+    fn enter(&mut self, element: &ElementInfo);
+
+    /// Called once all of an element's descendants have been visited.
+    fn leave(&mut self, _element: &ElementInfo) { }
+}
+
+
+pub struct Element<A> {
+    widget: Box<dyn Widget<A>>,
+    type_id: TypeId,
+    type_name: &'static str,
+
+    update_watch: Watch,
+    // only keeps what the element's prelayout depends on from counting as a change of the
+    // layouts which read it, see `Watch`
+    prelayout_watch: Watch,
+    layout_watch: Watch,
+    // where the element and its descendants drew after the last layout, in physical pixels
+    bounds: Cell<Option<math::Rect>>
+}
 
 impl<A> Element<A> {
     pub fn new<W: Widget<A> + 'static>(widget: W) -> Element<A> {
-        Element(Box::new(widget))
+        Element {
+            widget: Box::new(widget),
+            type_id: TypeId::of::<W>(),
+            type_name: std::any::type_name::<W>(),
+
+            update_watch: Watch::new(),
+            prelayout_watch: Watch::new(),
+            layout_watch: Watch::new(),
+            bounds: Cell::new(None)
+        }
+    }
+
+    /// Shows `visitor` this element and every element it currently contains, without changing
+    /// anything. Meant for tooling such as inspectors or layout linters.
+    pub fn visit(&self, visitor: &mut impl Visitor) {
+        self.visit_at(visitor, 0);
+    }
+
+    fn visit_at(&self, visitor: &mut impl Visitor, depth: usize) {
+        let info = ElementInfo {
+            type_id: self.type_id,
+            type_name: self.type_name,
+            depth,
+            layout: self.widget.last_layout(),
+            focus: self.widget.focus_id(),
+            clips_children: self.widget.clips_children(),
+            interactions: self.widget.interactions()
+        };
+        visitor.enter(&info);
+        self.widget.for_each_child(&mut |child| child.visit_at(visitor, depth + 1));
+        visitor.leave(&info);
     }
 }
 
 impl<A> Element<A> {
     pub fn update(&self, model: &mut A) {
-        self.0.update(model)
+        self.update_watch.run(|| self.widget.update(model))
     }
 
     pub fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
-        self.0.handle_interaction(interaction, model)
+        self.widget.handle_interaction(interaction, model)
     }
 
-    pub fn prelayout(&self, input: PrelayoutInput) -> LayoutCharacteristics {
-        self.0.prelayout(input)
+    pub fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.prelayout_watch.run_shared(|| self.widget.prelayout(ctx, input))
     }
 
-    pub fn layout(&self, input: LayoutInput) {
-        self.0.layout(input)
+    pub fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_watch.run(|| self.widget.layout(ctx, input));
+
+        let own = input.allocated.grow_by(self.widget.paint_outset());
+        let mut bounds = own;
+        let clips = self.widget.clips_children();
+        self.widget.for_each_child(&mut |child| {
+            let child_bounds = child.bounds.get().and_then(|child_bounds| match clips {
+                true => child_bounds.intersection(&own),
+                false => Some(child_bounds)
+            });
+            bounds = math::Rect::bounding_box([bounds].into_iter().chain(child_bounds)).unwrap_or(bounds);
+        });
+        // whatever was drawn where the element was and will be drawn where it is now
+        if self.bounds.get() != Some(bounds) {
+            if let Some(previous) = self.bounds.replace(Some(bounds)) {
+                ctx.damage.add(previous);
+            }
+            ctx.damage.add(bounds);
+        }
+    }
+
+    // adds the bounds of the elements which changed since `watch` last ran to `damage`. Elements
+    // which only changed through their descendants leave it to them.
+    fn collect_damage(&self, watch: fn(&Element<A>) -> &Watch, damage: &DamageRegion) {
+        match watch(self).state() {
+            WatchState::Clean => {}
+            WatchState::Nested => self.widget.for_each_child(&mut |child| child.collect_damage(watch, damage)),
+            WatchState::Own => if let Some(bounds) = self.bounds.get() {
+                damage.add(bounds);
+            }
+        }
     }
 
     pub fn interactions(&self) -> InteractSet {
-        self.0.interactions()
+        self.widget.interactions()
     }
 
     pub fn draw(&mut self, context: &mut RenderContext) {
-        self.0.draw(context);
+        if self.bounds.get().is_none_or(damage::is_redrawn) {
+            self.widget.draw(context);
+        }
+    }
+    pub fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.widget.page_breaks(hints)
+    }
+
+    /// The cursor this element asks for at `point`, see `Widget::cursor`.
+    pub fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        self.widget.cursor(point)
     }
 }
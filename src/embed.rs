@@ -0,0 +1,181 @@
+use tiny_skia::Pixmap;
+
+use crate::{Color, math, Overlays, PixmapPool, RenderContext, Root};
+use crate::focus::FocusId;
+use crate::interact::{Cursor, Interaction};
+use crate::shortcut::{KeyInput, Modifiers, Shortcut, Shortcuts};
+use crate::text::TextContext;
+
+
+/// Runs an element tree inside another framework, such as a game engine or a different UI toolkit.
+///
+/// The host forwards input with the event methods, calls `render` when `needs_redraw` returns true,
+/// and uploads `pixmap` (premultiplied RGBA) to a texture. Nothing here depends on a windowing
+/// library.
+pub struct YoruEmbed<A> {
+    state: A,
+    root: Root<A>,
+
+    viewport: math::Size,
+    scale_factor: f32,
+    background: Color,
+    cursor_position: math::Point,
+    pressed: bool,
+    shortcuts: Shortcuts<A>,
+
+    target: Option<Pixmap>,
+    resized: bool,
+    text: TextContext,
+    pool: PixmapPool
+}
+
+impl<A> YoruEmbed<A> {
+    pub fn new(state: A, root: Root<A>) -> YoruEmbed<A> {
+        YoruEmbed {
+            state,
+            root,
+
+            viewport: math::Size::new(0.0, 0.0),
+            scale_factor: 1.0,
+            background: Color::TRANSPARENT,
+            cursor_position: math::Point::new(0.0, 0.0),
+            pressed: false,
+            shortcuts: Shortcuts::new(),
+
+            target: None,
+            resized: true,
+            text: TextContext::new(),
+            pool: PixmapPool::new()
+        }
+    }
+
+    /// Use `text` for font loading and glyph caching instead of a context owned by this embed.
+    pub fn with_text_context(mut self, text: TextContext) -> YoruEmbed<A> {
+        self.text = text;
+        self
+    }
+
+    pub fn with_background(mut self, background: Color) -> YoruEmbed<A> {
+        self.background = background;
+        self
+    }
+
+    pub fn with_shortcut(mut self, shortcut: Shortcut, action: impl Fn(&mut A) + 'static) -> YoruEmbed<A> {
+        self.shortcuts.add(shortcut, action);
+        self
+    }
+
+    pub fn state(&self) -> &A {
+        &self.state
+    }
+
+    /// Gives mutable access to the model. Changes to signals in it are picked up by the next
+    /// `needs_redraw`.
+    pub fn state_mut(&mut self) -> &mut A {
+        &mut self.state
+    }
+
+    /// Sets the size of the rendered texture in physical pixels.
+    pub fn resize(&mut self, width: u32, height: u32, scale_factor: f32) {
+        self.viewport = math::Size::new(width as f32, height as f32);
+        self.scale_factor = scale_factor;
+        self.resized = true;
+    }
+
+    /// Moves the cursor to `position`, in physical pixels relative to the top left of the texture.
+    pub fn cursor_moved(&mut self, position: math::Point) {
+        self.cursor_position = position;
+        self.root.handle_interaction(&Interaction::Hover(Some(position)), &mut self.state);
+        if self.pressed {
+            self.root.handle_interaction(&Interaction::Drag(position), &mut self.state);
+        }
+    }
+
+    /// Tells the tree that the cursor left the texture, so nothing is drawn as hovered.
+    pub fn cursor_left(&mut self) {
+        self.root.handle_interaction(&Interaction::Hover(None), &mut self.state);
+    }
+
+    /// The cursor the host should show at the last cursor position.
+    pub fn cursor(&self) -> Cursor {
+        self.root.cursor(self.cursor_position)
+    }
+
+    /// Presses the primary button at the last cursor position.
+    pub fn press(&mut self) {
+        self.pressed = true;
+        self.root.handle_interaction(&Interaction::Press(self.cursor_position), &mut self.state);
+    }
+
+    /// Releases the primary button at the last cursor position.
+    pub fn click(&mut self) {
+        if std::mem::take(&mut self.pressed) {
+            self.root.handle_interaction(&Interaction::Release(self.cursor_position), &mut self.state);
+        }
+        self.root.handle_interaction(&Interaction::Click(self.cursor_position), &mut self.state);
+    }
+
+    /// Clicks the secondary button at the last cursor position.
+    pub fn secondary_click(&mut self) {
+        self.root.handle_interaction(&Interaction::SecondaryClick(self.cursor_position), &mut self.state);
+    }
+
+    /// Scrolls by `delta` physical pixels at the last cursor position. Positive deltas scroll
+    /// down and right.
+    pub fn scroll(&mut self, delta: math::Vector) {
+        self.root.handle_interaction(&Interaction::Scroll { delta, position: self.cursor_position }, &mut self.state);
+    }
+
+    /// Presses a key, returning whether the focused widget, an input scope or a shortcut handled it.
+    pub fn key_pressed(&mut self, input: KeyInput) -> bool {
+        self.root.handle_key(input, &mut self.state) || self.shortcuts.handle(&input, &mut self.state)
+    }
+
+    /// Releases a key, returning whether a widget was focused to receive it.
+    pub fn key_released(&mut self, input: KeyInput) -> bool {
+        self.root.handle_key_release(input, &mut self.state)
+    }
+
+    /// Types `text`, returning whether a widget was focused to receive it.
+    pub fn text_input(&mut self, text: &str, modifiers: Modifiers) -> bool {
+        self.root.handle_text(text.to_string(), modifiers, &mut self.state)
+    }
+
+    pub fn focused(&self) -> Option<FocusId> {
+        self.root.focused()
+    }
+
+    /// Moves the keyboard focus to `focus`, or clears it.
+    pub fn set_focus(&mut self, focus: Option<FocusId>) {
+        self.root.set_focus(focus, &mut self.state)
+    }
+
+    pub fn needs_redraw(&mut self) -> bool {
+        self.resized || self.root.needs_redraw()
+    }
+
+    /// Updates, lays out and draws the tree into the texture, returning it. Returns `None` while
+    /// either side of the viewport is zero.
+    pub fn render(&mut self) -> Option<&Pixmap> {
+        let (width, height) = (self.viewport.width() as u32, self.viewport.height() as u32);
+        if self.target.as_ref().is_none_or(|target| target.width() != width || target.height() != height) {
+            self.target = Pixmap::new(width, height);
+        }
+        let target = self.target.as_mut()?;
+        target.fill(self.background.into());
+
+        self.root.update(&mut self.state);
+        self.root.layout(&self.text, self.viewport, self.scale_factor);
+        self.root.interactions();
+        let theme = self.root.theme();
+        self.root.draw(&mut RenderContext { canvas: target.as_mut(), text: &self.text, pool: &self.pool, theme: &theme, overlays: &Overlays::new() });
+        self.resized = false;
+
+        self.target.as_ref()
+    }
+
+    /// The last rendered texture.
+    pub fn pixmap(&self) -> Option<&Pixmap> {
+        self.target.as_ref()
+    }
+}
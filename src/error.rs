@@ -0,0 +1,210 @@
+use std::fmt;
+
+
+#[derive(Debug)]
+pub enum Error {
+    #[cfg(feature = "window")]
+    EventLoop(winit::error::EventLoopError),
+    #[cfg(feature = "window")]
+    Window(winit::error::OsError),
+    #[cfg(feature = "window")]
+    Surface(softbuffer::SoftBufferError),
+    #[cfg(feature = "window")]
+    WindowIcon(winit::window::BadIcon),
+    /// The platform refused a request made to the window, e.g. to resize it.
+    #[cfg(feature = "window")]
+    WindowRequest(winit::error::ExternalError),
+    #[cfg(feature = "window")]
+    Clipboard(arboard::Error),
+    /// Setting up or drawing through the GPU failed, see `WgpuPresenter`.
+    #[cfg(feature = "gpu")]
+    Gpu(String),
+    #[cfg(feature = "tray")]
+    Tray(tray_icon::Error),
+    #[cfg(feature = "tray")]
+    TrayMenu(tray_icon::menu::Error),
+    #[cfg(feature = "tray")]
+    TrayIcon(tray_icon::BadIcon),
+    #[cfg(feature = "notify")]
+    Notification(notify_rust::error::Error),
+    /// A sound cue could not be played, see `sound::play`.
+    #[cfg(feature = "sound")]
+    Sound(String),
+    Io(std::io::Error),
+    InvalidGeometry,
+    /// Image data which could not be decoded, with the decoder's reason.
+    InvalidImage(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "window")]
+            Error::EventLoop(error) => write!(f, "event loop error: {}", error),
+            #[cfg(feature = "window")]
+            Error::Window(error) => write!(f, "could not create window: {}", error),
+            #[cfg(feature = "window")]
+            Error::Surface(error) => write!(f, "surface error: {}", error),
+            #[cfg(feature = "window")]
+            Error::WindowIcon(error) => write!(f, "invalid window icon: {}", error),
+            #[cfg(feature = "window")]
+            Error::WindowRequest(error) => write!(f, "window request failed: {}", error),
+            #[cfg(feature = "window")]
+            Error::Clipboard(error) => write!(f, "clipboard error: {}", error),
+            #[cfg(feature = "gpu")]
+            Error::Gpu(reason) => write!(f, "GPU error: {}", reason),
+            #[cfg(feature = "tray")]
+            Error::Tray(error) => write!(f, "could not create tray icon: {}", error),
+            #[cfg(feature = "tray")]
+            Error::TrayMenu(error) => write!(f, "could not create tray menu: {}", error),
+            #[cfg(feature = "tray")]
+            Error::TrayIcon(error) => write!(f, "invalid tray icon: {}", error),
+            #[cfg(feature = "notify")]
+            Error::Notification(error) => write!(f, "could not show notification: {}", error),
+            #[cfg(feature = "sound")]
+            Error::Sound(reason) => write!(f, "sound error: {}", reason),
+            Error::Io(error) => write!(f, "i/o error: {}", error),
+            Error::InvalidGeometry => write!(f, "invalid geometry"),
+            Error::InvalidImage(reason) => write!(f, "invalid image: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "window")]
+            Error::EventLoop(error) => Some(error),
+            #[cfg(feature = "window")]
+            Error::Window(error) => Some(error),
+            #[cfg(feature = "window")]
+            Error::Surface(error) => Some(error),
+            #[cfg(feature = "window")]
+            Error::WindowIcon(error) => Some(error),
+            #[cfg(feature = "window")]
+            Error::WindowRequest(error) => Some(error),
+            #[cfg(feature = "window")]
+            Error::Clipboard(error) => Some(error),
+            #[cfg(feature = "gpu")]
+            Error::Gpu(_) => None,
+            #[cfg(feature = "tray")]
+            Error::Tray(error) => Some(error),
+            #[cfg(feature = "tray")]
+            Error::TrayMenu(error) => Some(error),
+            #[cfg(feature = "tray")]
+            Error::TrayIcon(error) => Some(error),
+            #[cfg(feature = "notify")]
+            Error::Notification(error) => Some(error),
+            #[cfg(feature = "sound")]
+            Error::Sound(_) => None,
+            Error::Io(error) => Some(error),
+            Error::InvalidGeometry => None,
+            Error::InvalidImage(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "window")]
+impl From<winit::error::EventLoopError> for Error {
+    fn from(value: winit::error::EventLoopError) -> Self {
+        Error::EventLoop(value)
+    }
+}
+
+#[cfg(feature = "window")]
+impl From<winit::error::OsError> for Error {
+    fn from(value: winit::error::OsError) -> Self {
+        Error::Window(value)
+    }
+}
+
+#[cfg(feature = "window")]
+impl From<softbuffer::SoftBufferError> for Error {
+    fn from(value: softbuffer::SoftBufferError) -> Self {
+        Error::Surface(value)
+    }
+}
+
+#[cfg(feature = "window")]
+impl From<winit::window::BadIcon> for Error {
+    fn from(value: winit::window::BadIcon) -> Self {
+        Error::WindowIcon(value)
+    }
+}
+
+#[cfg(feature = "window")]
+impl From<winit::error::ExternalError> for Error {
+    fn from(value: winit::error::ExternalError) -> Self {
+        Error::WindowRequest(value)
+    }
+}
+
+#[cfg(feature = "window")]
+impl From<arboard::Error> for Error {
+    fn from(value: arboard::Error) -> Self {
+        Error::Clipboard(value)
+    }
+}
+
+#[cfg(feature = "gpu")]
+impl From<wgpu::CreateSurfaceError> for Error {
+    fn from(value: wgpu::CreateSurfaceError) -> Self {
+        Error::Gpu(value.to_string())
+    }
+}
+
+#[cfg(feature = "gpu")]
+impl From<wgpu::RequestDeviceError> for Error {
+    fn from(value: wgpu::RequestDeviceError) -> Self {
+        Error::Gpu(value.to_string())
+    }
+}
+
+#[cfg(feature = "gpu")]
+impl From<wgpu::SurfaceError> for Error {
+    fn from(value: wgpu::SurfaceError) -> Self {
+        Error::Gpu(value.to_string())
+    }
+}
+
+#[cfg(feature = "tray")]
+impl From<tray_icon::Error> for Error {
+    fn from(value: tray_icon::Error) -> Self {
+        Error::Tray(value)
+    }
+}
+
+#[cfg(feature = "tray")]
+impl From<tray_icon::menu::Error> for Error {
+    fn from(value: tray_icon::menu::Error) -> Self {
+        Error::TrayMenu(value)
+    }
+}
+
+#[cfg(feature = "tray")]
+impl From<tray_icon::BadIcon> for Error {
+    fn from(value: tray_icon::BadIcon) -> Self {
+        Error::TrayIcon(value)
+    }
+}
+
+#[cfg(feature = "notify")]
+impl From<notify_rust::error::Error> for Error {
+    fn from(value: notify_rust::error::Error) -> Self {
+        Error::Notification(value)
+    }
+}
+
+impl From<yoru_core::math::InvalidGeometry> for Error {
+    fn from(_: yoru_core::math::InvalidGeometry) -> Self {
+        Error::InvalidGeometry
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
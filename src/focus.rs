@@ -0,0 +1,75 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::element::{ElementInfo, Visitor};
+use crate::interact::Capability;
+use crate::math;
+
+
+/// Identifies a widget which can hold the keyboard focus, see `widgets::Focusable`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FocusId(u64);
+
+impl FocusId {
+    /// An id no other `FocusId` has.
+    pub fn unique() -> FocusId {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        FocusId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+thread_local! {
+    // whether the focused widget acted on the key press being delivered to it
+    static KEY_USED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Tells `Root::handle_key` that the focused widget acted on the key press it is being sent, so
+/// that the key doesn't also reach input scopes or the application's shortcuts.
+pub(crate) fn mark_key_used() {
+    KEY_USED.set(true);
+}
+
+// clears the flag set by `mark_key_used`, returning whether it was set
+pub(crate) fn take_key_used() -> bool {
+    KEY_USED.replace(false)
+}
+
+/// Finds the innermost focusable element under a point, for click-to-focus.
+pub(crate) struct FocusFinder {
+    point: math::Point,
+    pub found: Option<FocusId>
+}
+
+impl FocusFinder {
+    pub fn new(point: math::Point) -> FocusFinder {
+        FocusFinder { point, found: None }
+    }
+}
+
+impl Visitor for FocusFinder {
+    fn enter(&mut self, element: &ElementInfo) {
+        let Some(focus) = element.focus else { return; };
+        // descendants are visited after their ancestors, so the last match is the innermost one
+        if element.interactions.region(Capability::Keyboard).is_some_and(|region| region.contains(self.point)) {
+            self.found = Some(focus);
+        }
+    }
+}
+
+/// Finds out whether a tree contains the focusable element with a given id.
+pub(crate) struct FocusSearch {
+    focus: FocusId,
+    pub found: bool
+}
+
+impl FocusSearch {
+    pub fn new(focus: FocusId) -> FocusSearch {
+        FocusSearch { focus, found: false }
+    }
+}
+
+impl Visitor for FocusSearch {
+    fn enter(&mut self, element: &ElementInfo) {
+        self.found |= element.focus == Some(self.focus);
+    }
+}
@@ -0,0 +1,261 @@
+use std::rc::Rc;
+
+use tiny_skia::{Pixmap, PixmapMut};
+use winit::window::Window;
+
+use crate::error::{Error, Result};
+use crate::{math, Damage};
+use crate::present::Presenter;
+
+
+// draws the frame texture over the whole surface with a single triangle which covers it
+const SHADER: &str = r"
+@group(0) @binding(0) var frame: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let color = textureLoad(frame, vec2<i32>(position.xy), 0);
+    return vec4<f32>(color.rgb, 1.0);
+}
+";
+
+struct Gpu {
+    // declared before `window` so that the surface is dropped while the window still exists
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    // the texture the frame is uploaded to, with its bind group, once the size is known
+    frame_texture: Option<(wgpu::Texture, wgpu::BindGroup)>,
+    window: Rc<Window>
+}
+
+/// Shows frames through the GPU with wgpu.
+///
+/// Frames are still drawn on the CPU, then uploaded to a texture and drawn onto the window's
+/// surface, which avoids the copy into a CPU-side window buffer that `SoftbufferPresenter` makes.
+/// Only the rects a frame drew again are uploaded, since the texture keeps the frame before it.
+#[derive(Default)]
+pub struct WgpuPresenter {
+    gpu: Option<Gpu>,
+    frame: Option<Pixmap>
+}
+
+impl WgpuPresenter {
+    pub fn new() -> WgpuPresenter {
+        WgpuPresenter::default()
+    }
+}
+
+impl Gpu {
+    fn new(window: Rc<Window>) -> Result<Gpu> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        // SAFETY: the window is kept alive in `Gpu` for as long as the surface, which is dropped first
+        let surface = unsafe {
+            let target = wgpu::SurfaceTargetUnsafe::from_window(&*window).map_err(|error| Error::Gpu(error.to_string()))?;
+            instance.create_surface_unsafe(target)?
+        };
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            compatible_surface: Some(&surface),
+            ..Default::default()
+        })).ok_or_else(|| Error::Gpu("no graphics adapter can draw to the window".to_string()))?;
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))?;
+
+        let capabilities = surface.get_capabilities(&adapter);
+        // the frame already holds sRGB-encoded values, which an sRGB surface would encode again
+        let format = capabilities.formats.iter().copied().find(|format| !format.is_srgb())
+            .or_else(|| capabilities.formats.first().copied())
+            .ok_or_else(|| Error::Gpu("the window's surface supports no formats".to_string()))?;
+        let size = window.inner_size();
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: wgpu::PresentMode::AutoVsync,
+            desired_maximum_frame_latency: 2,
+            alpha_mode: capabilities.alpha_modes.first().copied().unwrap_or(wgpu::CompositeAlphaMode::Auto),
+            view_formats: Vec::new()
+        };
+        surface.configure(&device, &config);
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("yoru frame"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false
+                },
+                count: None
+            }]
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("yoru frame"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[]
+        });
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("yoru frame"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into())
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("yoru frame"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[]
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(format.into())]
+            }),
+            multiview: None,
+            cache: None
+        });
+
+        Ok(Gpu { surface, device, queue, config, pipeline, bind_group_layout, frame_texture: None, window })
+    }
+
+    // returns whether the frame texture was created anew, so that the next frame has to be
+    // uploaded whole
+    fn resize(&mut self, width: u32, height: u32) -> bool {
+        if self.frame_texture.is_some() && (self.config.width, self.config.height) == (width, height) {
+            return false;
+        }
+        self.config.width = width;
+        self.config.height = height;
+        self.surface.configure(&self.device, &self.config);
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("yoru frame"),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            // `Color` swaps red and blue when converting for tiny-skia, so frames are laid out as BGRA
+            format: wgpu::TextureFormat::Bgra8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[]
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("yoru frame"),
+            layout: &self.bind_group_layout,
+            entries: &[wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&view) }]
+        });
+        self.frame_texture = Some((texture, bind_group));
+        true
+    }
+
+    // copies `rect`, which has to lie on whole pixels within `frame`, to the frame texture
+    fn upload(&self, texture: &wgpu::Texture, frame: &Pixmap, rect: math::Rect) {
+        let (left, top) = (rect.left() as u32, rect.top() as u32);
+        let (width, height) = (rect.right() as u32 - left, rect.bottom() as u32 - top);
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x: left, y: top, z: 0 },
+                aspect: wgpu::TextureAspect::All
+            },
+            frame.data(),
+            wgpu::TexelCopyBufferLayout {
+                offset: 4 * (top as u64 * frame.width() as u64 + left as u64),
+                bytes_per_row: Some(4 * frame.width()),
+                rows_per_image: Some(height)
+            },
+            wgpu::Extent3d { width, height, depth_or_array_layers: 1 }
+        );
+    }
+
+    fn present(&mut self, frame: &Pixmap, damage: &Damage) -> Result<()> {
+        let Some((texture, bind_group)) = &self.frame_texture else { return Ok(()); };
+        match damage {
+            Damage::Full => self.upload(texture, frame, math::Rect::from_xywh(0.0, 0.0, frame.width() as f32, frame.height() as f32)),
+            Damage::Rects(rects) => for rect in rects {
+                self.upload(texture, frame, *rect);
+            }
+        }
+
+        let output = match self.surface.get_current_texture() {
+            Ok(output) => output,
+            // the surface has to be configured again, e.g. after the window moved to another monitor
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                self.surface.configure(&self.device, &self.config);
+                return Ok(());
+            }
+            Err(error) => return Err(error.into())
+        };
+        let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("yoru frame") });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("yoru frame"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations { load: wgpu::LoadOp::Clear(wgpu::Color::BLACK), store: wgpu::StoreOp::Store }
+                })],
+                ..Default::default()
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+        self.queue.submit([encoder.finish()]);
+
+        self.window.pre_present_notify();
+        output.present();
+        Ok(())
+    }
+}
+
+impl Presenter for WgpuPresenter {
+    fn attach(&mut self, window: Rc<Window>) -> Result<()> {
+        self.gpu = Some(Gpu::new(window)?);
+        Ok(())
+    }
+
+    fn present(&mut self, window: &Window, draw: &mut dyn FnMut(PixmapMut) -> Damage) -> Result<()> {
+        let Some(gpu) = &mut self.gpu else { return Ok(()); };
+
+        let size = window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return Ok(());
+        }
+        if self.frame.as_ref().map(|frame| (frame.width(), frame.height())) != Some((size.width, size.height)) {
+            self.frame = Pixmap::new(size.width, size.height);
+        }
+        let Some(frame) = &mut self.frame else { return Ok(()); };
+        let resized = gpu.resize(size.width, size.height);
+
+        let damage = match draw(frame.as_mut()) {
+            // a new texture holds nothing of the frames before
+            _ if resized => Damage::Full,
+            damage if damage.is_empty() => return Ok(()),
+            damage => damage
+        };
+        gpu.present(frame, &damage)
+    }
+}
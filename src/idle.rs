@@ -0,0 +1,81 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::tracking::{ReadableSignal, RwSignal};
+
+
+struct IdleInner {
+    last_input: Cell<Instant>,
+    thresholds: RefCell<Vec<(Duration, RwSignal<bool>)>>
+}
+
+/// Tracks how long it has been since the user last gave any input.
+///
+/// `idle_for` is reactive: widgets reading it are updated once the threshold passes and again when
+/// input resumes. The application records input and polls the monitor from its event loop; an
+/// embedding host calls `record_input` and `poll` itself. Clones share the same state.
+#[derive(Clone)]
+pub struct IdleMonitor {
+    inner: Rc<IdleInner>
+}
+
+impl IdleMonitor {
+    pub fn new() -> IdleMonitor {
+        IdleMonitor {
+            inner: Rc::new(IdleInner {
+                last_input: Cell::new(Instant::now()),
+                thresholds: RefCell::new(Vec::new())
+            })
+        }
+    }
+
+    pub fn last_input(&self) -> Instant {
+        self.inner.last_input.get()
+    }
+
+    /// Whether there has been no input for at least `duration`.
+    pub fn idle_for(&self, duration: Duration) -> bool {
+        let mut thresholds = self.inner.thresholds.borrow_mut();
+        if let Some((_, idle)) = thresholds.iter().find(|(threshold, _)| *threshold == duration) {
+            return idle.get();
+        }
+        let idle = RwSignal::new(self.last_input().elapsed() >= duration);
+        let value = idle.get();
+        thresholds.push((duration, idle));
+        value
+    }
+
+    pub fn record_input(&self) {
+        self.inner.last_input.set(Instant::now());
+        for (_, idle) in self.inner.thresholds.borrow().iter() {
+            if idle.get_untracked() {
+                idle.update(|idle| *idle = false);
+            }
+        }
+    }
+
+    /// Marks the thresholds which have passed as idle, and returns when the next one will pass.
+    pub fn poll(&self) -> Option<Instant> {
+        let last_input = self.last_input();
+        let now = Instant::now();
+        let mut next = None;
+        for (threshold, idle) in self.inner.thresholds.borrow().iter() {
+            let deadline = last_input + *threshold;
+            if deadline <= now {
+                if !idle.get_untracked() {
+                    idle.update(|idle| *idle = true);
+                }
+            } else {
+                next = Some(next.map_or(deadline, |next: Instant| next.min(deadline)));
+            }
+        }
+        next
+    }
+}
+
+impl Default for IdleMonitor {
+    fn default() -> Self {
+        IdleMonitor::new()
+    }
+}
@@ -1,20 +1,71 @@
+#[cfg(feature = "window")]
 use bytemuck::Zeroable;
-use winit::event::{ElementState, MouseButton, WindowEvent};
+#[cfg(feature = "window")]
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
+#[cfg(feature = "window")]
+use winit::keyboard::{self as winit_keyboard, KeyCode, PhysicalKey};
 
+use crate::focus::FocusId;
 use crate::math;
+use crate::shortcut::{KeyInput, Modifiers};
+#[cfg(feature = "window")]
+use crate::shortcut::{Key, NamedKey};
 
-#[derive(Copy, Clone, Debug)]
-pub struct InteractState {
-    pub mouse_position: (f32, f32),
-    pub focused_item: ()
+/// The shape of the mouse cursor a widget asks for while it is over the widget, see
+/// `Widget::cursor`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Cursor {
+    #[default]
+    Default,
+    /// Something which can be clicked, such as a button.
+    Pointer,
+    /// Text which can be selected or edited.
+    Text,
+    Move,
+    Grab,
+    Grabbing,
+    Crosshair,
+    NotAllowed,
+    Wait,
+    /// Something which can be dragged left and right, such as a vertical split handle.
+    ResizeHorizontal,
+    /// Something which can be dragged up and down.
+    ResizeVertical
 }
 
+/// A class of input a widget can ask for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Capability {
+    Click,
+    Hover,
+    Scroll,
+    Keyboard,
+    Drag,
+    Drop,
+    Ime
+}
 
-#[derive(Copy, Clone, Debug)]
-pub struct InteractSet {
-    pub click: bool,
+impl Capability {
+    pub const ALL: [Capability; 7] = [
+        Capability::Click, Capability::Hover, Capability::Scroll, Capability::Keyboard,
+        Capability::Drag, Capability::Drop, Capability::Ime
+    ];
 
-    pub click_area: math::Rect
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The input a widget and its descendants want, and where.
+///
+/// Each capability has its own region; pointer events are only routed into the region of their
+/// capability. Keyboard input is routed by input scope priority instead of position, its region
+/// just records where the scope is.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InteractSet {
+    regions: [Option<math::Rect>; Capability::ALL.len()],
+    /// The priority of the highest active input scope in the subtree, or 0 if there is none.
+    pub key_scope: u32
 }
 
 impl InteractSet {
@@ -22,15 +73,51 @@ impl InteractSet {
 
     pub const fn empty() -> InteractSet {
         InteractSet {
-            click: false,
-            click_area: bytemuck::zeroed()
+            regions: [None; Capability::ALL.len()],
+            key_scope: 0
         }
     }
 
+    /// Adds `region` to the region in which `capability` is wanted.
+    pub fn with(mut self, capability: Capability, region: math::Rect) -> InteractSet {
+        let slot = &mut self.regions[capability.index()];
+        *slot = Some(match *slot {
+            Some(existing) => math::Rect::bounding_box([existing, region]).unwrap(),
+            None => region
+        });
+        self
+    }
+
+    pub fn wants(&self, capability: Capability) -> bool {
+        self.regions[capability.index()].is_some()
+    }
+
+    pub fn region(&self, capability: Capability) -> Option<math::Rect> {
+        self.regions[capability.index()]
+    }
+
     pub fn accepts(&self, interaction: &Interaction) -> bool {
         match interaction {
-            Interaction::Click(point) => {
-                self.click && self.click_area.contains(*point)
+            Interaction::Click(point) | Interaction::SecondaryClick(point) => {
+                self.region(Capability::Click).is_some_and(|region| region.contains(*point))
+            }
+            Interaction::Scroll { position, .. } => {
+                self.region(Capability::Scroll).is_some_and(|region| region.contains(*position))
+            }
+            Interaction::Press(point) => {
+                self.region(Capability::Drag).is_some_and(|region| region.contains(*point))
+            }
+            // a drag continues wherever the cursor goes, so the widget which took the press has
+            // to be reachable even after the cursor leaves it
+            Interaction::Drag(_) | Interaction::Release(_) => self.wants(Capability::Drag),
+            // every widget which follows the cursor has to see it move away from it
+            Interaction::Hover(_) => self.wants(Capability::Hover),
+            &Interaction::Key(_, scope) => {
+                scope > 0 && self.key_scope == scope
+            }
+            // only the focused widget acts on these, so every subtree which can hold focus sees them
+            Interaction::KeyDown(..) | Interaction::KeyUp(..) | Interaction::Text { .. } | Interaction::Focus(_) => {
+                self.wants(Capability::Keyboard)
             }
         }
     }
@@ -46,46 +133,285 @@ impl std::ops::BitOr for InteractSet {
     type Output = InteractSet;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        InteractSet {
-            click: self.click | rhs.click,
-            click_area: math::Rect::bounding_box([self.click_area, rhs.click_area]).unwrap()
+        let mut set = self;
+        for capability in Capability::ALL {
+            if let Some(region) = rhs.region(capability) {
+                set = set.with(capability, region);
+            }
+        }
+        set.key_scope = self.key_scope.max(rhs.key_scope);
+        set
+    }
+}
+
+
+/// The union of a list of interaction sets, kept up to date one entry at a time.
+///
+/// Entries are the leaves of a complete binary tree whose inner nodes hold the union of their
+/// children, so changing an entry only recomputes the unions along its path to the root.
+pub(crate) struct UnionTree {
+    len: usize,
+    nodes: Vec<InteractSet>
+}
+
+impl UnionTree {
+    pub fn new(len: usize) -> UnionTree {
+        UnionTree {
+            len,
+            nodes: vec![InteractSet::EMPTY; 2 * len.next_power_of_two()]
         }
     }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn set(&mut self, index: usize, set: InteractSet) {
+        let mut node = self.nodes.len() / 2 + index;
+        self.nodes[node] = set;
+        while node > 1 {
+            node /= 2;
+            self.nodes[node] = self.nodes[2 * node] | self.nodes[2 * node + 1];
+        }
+    }
+
+    pub fn union(&self) -> InteractSet {
+        // with a single entry the root is that entry's leaf, and with none it stays empty
+        self.nodes[1]
+    }
 }
 
 
 #[derive(Debug)]
 pub enum Interaction {
-    Click(math::Point)
+    Click(math::Point),
+    /// The secondary, usually right, button was clicked. Routed like `Click`, to the widgets
+    /// whose click region contains the point.
+    SecondaryClick(math::Point),
+    /// A key press, sent to the active input scopes with the given priority.
+    Key(KeyInput, u32),
+    /// The mouse wheel or touchpad scrolled by `delta` physical pixels while the cursor was at
+    /// `position`. Positive deltas scroll towards the end of the content, i.e. down and right.
+    Scroll { delta: math::Vector, position: math::Point },
+    /// The primary button went down. Widgets which take it keep receiving `Drag` until the
+    /// matching `Release`.
+    Press(math::Point),
+    /// The cursor moved while the primary button was held.
+    Drag(math::Point),
+    /// The cursor moved to a point, or left the window if `None`. Sent to every widget which
+    /// wants `Capability::Hover`, wherever its region is.
+    Hover(Option<math::Point>),
+    /// The primary button went up, just before the `Click` at the same point.
+    Release(math::Point),
+    /// A key press, sent to the focused widget.
+    KeyDown(KeyInput, FocusId),
+    /// A key release, sent to the focused widget.
+    KeyUp(KeyInput, FocusId),
+    /// Text typed while a widget is focused, with the modifiers held at the time.
+    Text { text: String, modifiers: Modifiers, target: FocusId },
+    /// The keyboard focus moved to another widget, or to none.
+    Focus(Option<FocusId>)
 }
 
 
+// how far one notch of a wheel which scrolls by lines moves, in physical pixels
+#[cfg(feature = "window")]
+const LINE_HEIGHT: f32 = 40.0;
+
+#[cfg(feature = "window")]
 pub(crate) struct InteractionState {
-    cursor_position: math::Point
+    cursor_position: math::Point,
+    pressed: bool,
+    modifiers: Modifiers
 }
 
+#[cfg(feature = "window")]
 impl InteractionState {
     pub fn new() -> InteractionState {
         InteractionState {
-            cursor_position: math::Point::zeroed()
+            cursor_position: math::Point::zeroed(),
+            pressed: false,
+            modifiers: Modifiers::NONE
         }
     }
 
-    pub fn handle_window_event(&mut self, event: WindowEvent, send_interaction: impl FnOnce(Interaction)) -> bool {
+    pub fn handle_window_event(&mut self, event: WindowEvent, mut send_interaction: impl FnMut(Interaction)) -> bool {
         match event {
             WindowEvent::CursorMoved { position, .. } => {
                 self.cursor_position = math::Point::new(position.x as f32, position.y as f32);
+                send_interaction(Interaction::Hover(Some(self.cursor_position)));
+                if self.pressed {
+                    send_interaction(Interaction::Drag(self.cursor_position));
+                }
+                true
+            }
+            WindowEvent::CursorLeft { .. } => {
+                send_interaction(Interaction::Hover(None));
+                true
+            }
+            WindowEvent::MouseInput { button: MouseButton::Left, state: ElementState::Pressed, .. } => {
+                self.pressed = true;
+                send_interaction(Interaction::Press(self.cursor_position));
+                true
+            }
+            WindowEvent::MouseInput { button: MouseButton::Left, state: ElementState::Released, .. } => {
+                self.pressed = false;
+                send_interaction(Interaction::Release(self.cursor_position));
+                send_interaction(Interaction::Click(self.cursor_position));
+                true
+            }
+            WindowEvent::MouseInput { button: MouseButton::Right, state: ElementState::Released, .. } => {
+                send_interaction(Interaction::SecondaryClick(self.cursor_position));
                 true
             }
-            WindowEvent::MouseInput { button, state, .. } => {
-                if button == MouseButton::Left && state == ElementState::Released {
-                    send_interaction(Interaction::Click(self.cursor_position));
-                    true
-                } else {
-                    false
+            WindowEvent::MouseWheel { delta, .. } => {
+                let mut delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => math::Vector::new(-x * LINE_HEIGHT, -y * LINE_HEIGHT),
+                    MouseScrollDelta::PixelDelta(delta) => math::Vector::new(-delta.x as f32, -delta.y as f32)
+                };
+                // most platforms turn Shift+wheel into sideways scrolling only for some mice, so
+                // do it here for wheels which only scroll vertically
+                if self.modifiers.shift && delta.x == 0.0 {
+                    delta = math::Vector::new(delta.y, 0.0);
                 }
+                send_interaction(Interaction::Scroll { delta, position: self.cursor_position });
+                true
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                let state = modifiers.state();
+                self.modifiers = Modifiers {
+                    ctrl: state.control_key(),
+                    shift: state.shift_key(),
+                    alt: state.alt_key(),
+                    meta: state.super_key()
+                };
+                false
             }
             _ => false
         }
     }
+
+    /// Translates a key press, ignoring releases and keys yoru has no name for.
+    pub fn key_input(&self, event: &KeyEvent) -> Option<KeyInput> {
+        if event.state != ElementState::Pressed {
+            return None;
+        }
+        self.translate_key(event)
+    }
+
+    /// Translates a key release, ignoring presses and keys yoru has no name for.
+    pub fn key_release(&self, event: &KeyEvent) -> Option<KeyInput> {
+        if event.state != ElementState::Released {
+            return None;
+        }
+        self.translate_key(event)
+    }
+
+    /// The text a key press typed, if any, with the modifiers held.
+    pub fn text_input(&self, event: &KeyEvent) -> Option<(String, Modifiers)> {
+        if event.state != ElementState::Pressed {
+            return None;
+        }
+        let text = event.text.as_ref()?;
+        // control characters come from keys like Backspace, which widgets see as key presses
+        if text.chars().all(char::is_control) {
+            return None;
+        }
+        Some((text.to_string(), self.modifiers))
+    }
+
+    fn translate_key(&self, event: &KeyEvent) -> Option<KeyInput> {
+        let logical = match &event.logical_key {
+            winit_keyboard::Key::Character(text) => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Key::Character(c),
+                    _ => return None
+                }
+            }
+            winit_keyboard::Key::Named(named) => named_key(*named)?,
+            _ => return None
+        };
+        let physical = match event.physical_key {
+            PhysicalKey::Code(code) => physical_key(code),
+            PhysicalKey::Unidentified(_) => None
+        };
+        Some(KeyInput { logical, physical, modifiers: self.modifiers, repeat: event.repeat })
+    }
+}
+
+#[cfg(feature = "window")]
+fn named_key(named: winit_keyboard::NamedKey) -> Option<Key> {
+    use winit_keyboard::NamedKey as N;
+
+    let named = match named {
+        N::Space => return Some(Key::Character(' ')),
+        N::Enter => NamedKey::Enter,
+        N::Escape => NamedKey::Escape,
+        N::Tab => NamedKey::Tab,
+        N::Backspace => NamedKey::Backspace,
+        N::Delete => NamedKey::Delete,
+        N::Insert => NamedKey::Insert,
+        N::ArrowLeft => NamedKey::ArrowLeft,
+        N::ArrowRight => NamedKey::ArrowRight,
+        N::ArrowUp => NamedKey::ArrowUp,
+        N::ArrowDown => NamedKey::ArrowDown,
+        N::Home => NamedKey::Home,
+        N::End => NamedKey::End,
+        N::PageUp => NamedKey::PageUp,
+        N::PageDown => NamedKey::PageDown,
+        N::F1 => NamedKey::F(1),
+        N::F2 => NamedKey::F(2),
+        N::F3 => NamedKey::F(3),
+        N::F4 => NamedKey::F(4),
+        N::F5 => NamedKey::F(5),
+        N::F6 => NamedKey::F(6),
+        N::F7 => NamedKey::F(7),
+        N::F8 => NamedKey::F(8),
+        N::F9 => NamedKey::F(9),
+        N::F10 => NamedKey::F(10),
+        N::F11 => NamedKey::F(11),
+        N::F12 => NamedKey::F(12),
+        _ => return None
+    };
+    Some(Key::Named(named))
+}
+
+// the key at this position on a US QWERTY layout
+#[cfg(feature = "window")]
+fn physical_key(code: KeyCode) -> Option<Key> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE, KeyCode::KeyF,
+        KeyCode::KeyG, KeyCode::KeyH, KeyCode::KeyI, KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL,
+        KeyCode::KeyM, KeyCode::KeyN, KeyCode::KeyO, KeyCode::KeyP, KeyCode::KeyQ, KeyCode::KeyR,
+        KeyCode::KeyS, KeyCode::KeyT, KeyCode::KeyU, KeyCode::KeyV, KeyCode::KeyW, KeyCode::KeyX,
+        KeyCode::KeyY, KeyCode::KeyZ
+    ];
+    const DIGITS: [KeyCode; 10] = [
+        KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+        KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9
+    ];
+
+    if let Some(index) = LETTERS.iter().position(|&letter| letter == code) {
+        return Some(Key::Character((b'a' + index as u8) as char));
+    }
+    if let Some(index) = DIGITS.iter().position(|&digit| digit == code) {
+        return Some(Key::Character((b'0' + index as u8) as char));
+    }
+    let c = match code {
+        KeyCode::Space => ' ',
+        KeyCode::Minus => '-',
+        KeyCode::Equal => '=',
+        KeyCode::BracketLeft => '[',
+        KeyCode::BracketRight => ']',
+        KeyCode::Backslash => '\\',
+        KeyCode::Semicolon => ';',
+        KeyCode::Quote => '\'',
+        KeyCode::Backquote => '`',
+        KeyCode::Comma => ',',
+        KeyCode::Period => '.',
+        KeyCode::Slash => '/',
+        _ => return None
+    };
+    Some(Key::Character(c))
 }
@@ -1,22 +1,90 @@
 use tiny_skia::PixmapMut;
 
+#[cfg(feature = "window")]
 mod app;
+mod channel;
+pub mod clipboard;
+mod damage;
+mod data;
 mod element;
-mod style;
-pub mod math;
+mod embed;
+mod error;
+mod focus;
+#[cfg(feature = "gpu")]
+mod gpu;
+pub use yoru_core::math;
+use yoru_core::style;
 mod layout;
+mod modal;
 pub mod widgets;
 pub mod tracking;
+mod idle;
 mod interact;
+#[cfg(feature = "notify")]
+mod notify;
+mod overflow;
+mod overlay;
+mod pool;
+#[cfg(feature = "window")]
+mod present;
+mod print;
+mod safe_area;
+mod selection;
+mod shortcut;
+#[cfg(feature = "sound")]
+pub mod sound;
+mod text;
+mod theme;
+mod timer;
+#[cfg(feature = "text")]
+mod toast;
+#[cfg(feature = "tray")]
+mod tray;
 mod utils;
 
-pub use crate::element::{Element, Root};
+pub use crate::element::{Element, ElementInfo, Handled, Root, Visitor};
+pub use crate::embed::YoruEmbed;
+#[cfg(feature = "window")]
 pub use crate::app::Application;
-pub use crate::layout::{PrelayoutInput, LayoutCharacteristics, Layout};
-pub use crate::style::{LayoutStyle, Sizing, Justify, Direction, Color};
-pub use crate::widgets::{Widget, Div, Label};
+pub use crate::channel::{channel, pump_channels, ReceiverSignal, WakingSender};
+pub use crate::damage::Damage;
+pub use crate::data::{DataProvider, CsvProvider};
+pub use crate::error::{Error, Result};
+pub use crate::focus::FocusId;
+pub use crate::idle::IdleMonitor;
+pub use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+pub use crate::modal::Modals;
+pub use crate::layout::{PrelayoutInput, LayoutCharacteristics, LayoutContext, Layout, TextMetrics};
+pub use crate::text::{Hinting, TextContext, TextRendering};
+pub use crate::theme::Theme;
+pub use crate::timer::{TimerId, Timers};
+#[cfg(feature = "text")]
+pub use crate::toast::Toasts;
+#[cfg(feature = "tray")]
+pub use crate::tray::Tray;
+#[cfg(feature = "notify")]
+pub use crate::notify::Notifier;
+pub use crate::overflow::{Overflow, OverflowCheck};
+pub use crate::overlay::Overlays;
+pub use crate::pool::PixmapPool;
+#[cfg(feature = "window")]
+pub use crate::present::{HeadlessPresenter, Presenter, SoftbufferPresenter};
+#[cfg(feature = "gpu")]
+pub use crate::gpu::WgpuPresenter;
+pub use crate::print::{BreakHint, Paginator};
+pub use crate::safe_area::SafeArea;
+pub use crate::selection::{SelectionModel, SelectionMode, SelectModifiers};
+pub use crate::shortcut::{Key, KeyInput, Modifiers, NamedKey, Shortcut, Shortcuts};
+pub use crate::style::{LayoutStyle, Sizing, Justify, Direction, Color, PageBreak, PointerEvents, Position, Shadow, StyleSheet, StylePatch, WidgetState, Wrap};
+pub use crate::widgets::{Widget, Div};
+#[cfg(feature = "text")]
+pub use crate::widgets::{Label, Span, TextOverflow};
 
 pub struct RenderContext<'a> {
     pub canvas: PixmapMut<'a>,
+    pub text: &'a TextContext,
+    pub pool: &'a PixmapPool,
+    pub theme: &'a Theme,
+    pub overlays: &'a Overlays,
 }
 
@@ -0,0 +1,92 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::Element;
+use crate::tracking::{ReadableSignal, RwSignal};
+
+
+struct ModalsInner<A> {
+    // shared, so that a modal can close itself while it handles an interaction
+    stack: RefCell<Vec<Rc<RefCell<Element<A>>>>>,
+    // bumped whenever the stack changes
+    revision: RwSignal<u64>
+}
+
+/// The stack of modal elements shown over a `Root`, see `Root::with_modals`.
+///
+/// Each modal is laid out over the whole viewport, above a dimmed copy of everything beneath it,
+/// and only the topmost one gets input. Keep a clone in the model to open and close modals from
+/// event handlers; clones share the same stack.
+pub struct Modals<A> {
+    inner: Rc<ModalsInner<A>>
+}
+
+impl<A> Modals<A> {
+    pub fn new() -> Modals<A> {
+        Modals {
+            inner: Rc::new(ModalsInner {
+                stack: RefCell::new(Vec::new()),
+                revision: RwSignal::new(0)
+            })
+        }
+    }
+
+    fn changed(&self) {
+        self.inner.revision.update(|revision| *revision += 1);
+    }
+
+    /// Shows `modal` over the tree and any modals already open.
+    pub fn push(&self, modal: impl Into<Element<A>>) {
+        self.inner.stack.borrow_mut().push(Rc::new(RefCell::new(modal.into())));
+        self.changed();
+    }
+
+    /// Closes the topmost modal, returning false if none was open.
+    pub fn pop(&self) -> bool {
+        let closed = self.inner.stack.borrow_mut().pop().is_some();
+        if closed {
+            self.changed();
+        }
+        closed
+    }
+
+    /// Closes every modal.
+    pub fn clear(&self) {
+        if !self.inner.stack.borrow().is_empty() {
+            self.inner.stack.borrow_mut().clear();
+            self.changed();
+        }
+    }
+
+    /// How many modals are open, read tracked.
+    pub fn len(&self) -> usize {
+        self.inner.revision.track();
+        self.inner.stack.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // the open modals, bottom first, read tracked
+    pub(crate) fn elements(&self) -> Vec<Rc<RefCell<Element<A>>>> {
+        self.inner.revision.track();
+        self.inner.stack.borrow().clone()
+    }
+
+    pub(crate) fn top(&self) -> Option<Rc<RefCell<Element<A>>>> {
+        self.inner.stack.borrow().last().cloned()
+    }
+}
+
+impl<A> Clone for Modals<A> {
+    fn clone(&self) -> Self {
+        Modals { inner: Rc::clone(&self.inner) }
+    }
+}
+
+impl<A> Default for Modals<A> {
+    fn default() -> Self {
+        Modals::new()
+    }
+}
@@ -0,0 +1,117 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use winit::event_loop::EventLoopProxy;
+
+use crate::error::Result;
+
+
+type NotificationAction<A> = Box<dyn FnOnce(&mut A)>;
+
+struct NotifierInner<A> {
+    next_id: u64,
+    on_click: HashMap<u64, NotificationAction<A>>,
+    // carries the id of each notification that was acted on and whether it was clicked or closed
+    responses_sender: Sender<(u64, bool)>,
+    responses: Receiver<(u64, bool)>,
+    proxy: Option<EventLoopProxy<()>>
+}
+
+/// Posts system notifications.
+///
+/// A `Notifier` is a cheap handle which can be kept in the model and cloned freely. Pass a clone
+/// to `Application::with_notifier` so that clicks on notifications are delivered back into the
+/// event loop; without it notifications are still shown but their callbacks never run.
+pub struct Notifier<A> {
+    inner: Rc<RefCell<NotifierInner<A>>>
+}
+
+impl<A> Notifier<A> {
+    pub fn new() -> Notifier<A> {
+        let (responses_sender, responses) = mpsc::channel();
+        Notifier {
+            inner: Rc::new(RefCell::new(NotifierInner {
+                next_id: 0,
+                on_click: HashMap::new(),
+                responses_sender,
+                responses,
+                proxy: None
+            }))
+        }
+    }
+
+    pub fn notify(&self, title: &str, body: &str) -> Result<()> {
+        notify_rust::Notification::new().summary(title).body(body).show()?;
+        Ok(())
+    }
+
+    /// Shows a notification, calling `on_click` with the model if the user clicks it.
+    ///
+    /// Click callbacks are only supported on Linux and the BSDs; elsewhere the notification is
+    /// shown but `on_click` is never called.
+    pub fn notify_with_callback(&self, title: &str, body: &str, on_click: impl FnOnce(&mut A) + 'static) -> Result<()> {
+        let mut notification = notify_rust::Notification::new();
+        notification.summary(title).body(body).action("default", "default");
+        let _handle = notification.show()?;
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            let mut inner = self.inner.borrow_mut();
+            let id = inner.next_id;
+            inner.next_id += 1;
+            inner.on_click.insert(id, Box::new(on_click));
+
+            let sender = inner.responses_sender.clone();
+            let proxy = inner.proxy.clone();
+            std::thread::spawn(move || {
+                _handle.wait_for_action(|action| {
+                    if sender.send((id, action == "default")).is_ok() {
+                        if let Some(proxy) = proxy {
+                            let _ = proxy.send_event(());
+                        }
+                    }
+                });
+            });
+        }
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        drop(on_click);
+
+        Ok(())
+    }
+
+    pub(crate) fn attach(&self, proxy: EventLoopProxy<()>) {
+        self.inner.borrow_mut().proxy = Some(proxy);
+    }
+
+    /// Runs the callbacks of every notification clicked since the last poll, returning whether any
+    /// callback ran.
+    pub(crate) fn poll(&self, model: &mut A) -> bool {
+        let mut handled = false;
+        loop {
+            let action = {
+                let mut inner = self.inner.borrow_mut();
+                let Ok((id, clicked)) = inner.responses.try_recv() else { break; };
+                inner.on_click.remove(&id).filter(|_| clicked)
+            };
+            if let Some(action) = action {
+                action(model);
+                handled = true;
+            }
+        }
+        handled
+    }
+}
+
+impl<A> Clone for Notifier<A> {
+    fn clone(&self) -> Self {
+        Notifier { inner: Rc::clone(&self.inner) }
+    }
+}
+
+impl<A> Default for Notifier<A> {
+    fn default() -> Self {
+        Notifier::new()
+    }
+}
@@ -0,0 +1,80 @@
+use crate::element::{ElementInfo, Visitor};
+use crate::math;
+
+
+// differences smaller than this come from rounding, not from a layout bug
+const TOLERANCE: f32 = 0.5;
+
+/// What the root does when a child's margin box sticks out of its parent's content box, see
+/// `Root::with_overflow_check`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum OverflowCheck {
+    #[default]
+    Off,
+    /// Collects the overflowing elements, see `Root::overflows`, and tells the listener set with
+    /// `Root::on_overflow` about each one once each time it starts overflowing.
+    Report,
+    /// Outlines overflowing elements in red.
+    Highlight
+}
+
+/// An element whose margin box exceeds the content box of the element enclosing it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overflow {
+    /// The type of each element from the root down, with its index among its siblings.
+    pub path: String,
+    pub margin_box: math::Rect,
+    pub bounds: math::Rect
+}
+
+struct Frame {
+    name: String,
+    content_box: Option<math::Rect>,
+    clips: bool,
+    children: usize
+}
+
+#[derive(Default)]
+pub(crate) struct OverflowFinder {
+    ancestors: Vec<Frame>,
+    pub found: Vec<Overflow>
+}
+
+fn short_name(type_name: &str) -> &str {
+    let without_generics = type_name.split('<').next().unwrap_or(type_name);
+    without_generics.rsplit("::").next().unwrap_or(without_generics)
+}
+
+fn exceeds(inner: math::Rect, outer: math::Rect) -> bool {
+    inner.left() < outer.left() - TOLERANCE || inner.top() < outer.top() - TOLERANCE ||
+        inner.right() > outer.right() + TOLERANCE || inner.bottom() > outer.bottom() + TOLERANCE
+}
+
+impl Visitor for OverflowFinder {
+    fn enter(&mut self, element: &ElementInfo) {
+        let index = self.ancestors.last_mut().map_or(0, |parent| {
+            parent.children += 1;
+            parent.children - 1
+        });
+        let name = format!("{}[{}]", short_name(element.type_name), index);
+
+        // wrappers without a layout of their own don't constrain their child, and children cut
+        // off by their parent don't overflow it
+        let bounds = self.ancestors.iter().rev()
+            .find(|frame| frame.content_box.is_some())
+            .filter(|frame| !frame.clips)
+            .and_then(|frame| frame.content_box);
+        if let (Some(layout), Some(bounds)) = (element.layout, bounds) {
+            if exceeds(layout.margin_box, bounds) {
+                let path = self.ancestors.iter().map(|frame| frame.name.as_str()).chain([name.as_str()]).collect::<Vec<_>>().join(" > ");
+                self.found.push(Overflow { path, margin_box: layout.margin_box, bounds });
+            }
+        }
+
+        self.ancestors.push(Frame { name, content_box: element.layout.map(|layout| layout.content_box), clips: element.clips_children, children: 0 });
+    }
+
+    fn leave(&mut self, _element: &ElementInfo) {
+        self.ancestors.pop();
+    }
+}
@@ -0,0 +1,43 @@
+use std::cell::RefCell;
+
+use tiny_skia::{Pixmap, PixmapMut, PixmapPaint, Transform};
+
+use crate::PixmapPool;
+
+
+/// Layers drawn over the whole tree once it has been drawn, e.g. tooltips, which must neither be
+/// covered by the widgets drawn after them nor cut off by a scroll view around them.
+///
+/// Every `RenderContext` carries one; a `Root` composites the layers pushed while drawing it.
+#[derive(Default)]
+pub struct Overlays {
+    layers: RefCell<Vec<Pixmap>>
+}
+
+impl Overlays {
+    pub fn new() -> Overlays {
+        Overlays::default()
+    }
+
+    /// Draws `layer`, which has the size of the canvas, over the tree once the tree is drawn.
+    /// Layers are drawn in the order they were pushed.
+    pub fn push(&self, layer: Pixmap) {
+        self.layers.borrow_mut().push(layer);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.layers.borrow().is_empty()
+    }
+
+    // draws the layers pushed so far onto `canvas`, handing them back to `pool`. Returns whether
+    // there were any.
+    pub(crate) fn composite(&self, canvas: &mut PixmapMut, pool: &PixmapPool) -> bool {
+        let layers = self.layers.take();
+        let composited = !layers.is_empty();
+        for layer in layers {
+            canvas.draw_pixmap(0, 0, layer.as_ref(), &PixmapPaint::default(), Transform::identity(), None);
+            pool.give_pixmap(layer);
+        }
+        composited
+    }
+}
@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+
+use tiny_skia::{Mask, Pixmap};
+
+
+// enough for a few nested layers without holding on to memory after a burst of them
+const MAX_POOLED: usize = 8;
+
+/// Scratch pixmaps and masks reused between frames.
+///
+/// Widgets which draw through an intermediate layer take a buffer from the pool and hand it back
+/// once they are done, so steady-state frames don't allocate. Buffers come back cleared.
+#[derive(Default)]
+pub struct PixmapPool {
+    pixmaps: RefCell<Vec<Pixmap>>,
+    masks: RefCell<Vec<Mask>>
+}
+
+impl PixmapPool {
+    pub fn new() -> PixmapPool {
+        PixmapPool::default()
+    }
+
+    /// Returns a transparent pixmap of the given size, or `None` if either dimension is zero.
+    pub fn take_pixmap(&self, width: u32, height: u32) -> Option<Pixmap> {
+        let mut pixmaps = self.pixmaps.borrow_mut();
+        match pixmaps.iter().position(|pixmap| pixmap.width() == width && pixmap.height() == height) {
+            Some(index) => {
+                let mut pixmap = pixmaps.swap_remove(index);
+                pixmap.fill(tiny_skia::Color::TRANSPARENT);
+                Some(pixmap)
+            }
+            None => Pixmap::new(width, height)
+        }
+    }
+
+    pub fn give_pixmap(&self, pixmap: Pixmap) {
+        let mut pixmaps = self.pixmaps.borrow_mut();
+        if pixmaps.len() == MAX_POOLED {
+            pixmaps.remove(0);
+        }
+        pixmaps.push(pixmap);
+    }
+
+    /// Returns an empty mask of the given size, or `None` if either dimension is zero.
+    pub fn take_mask(&self, width: u32, height: u32) -> Option<Mask> {
+        let mut masks = self.masks.borrow_mut();
+        match masks.iter().position(|mask| mask.width() == width && mask.height() == height) {
+            Some(index) => {
+                let mut mask = masks.swap_remove(index);
+                mask.clear();
+                Some(mask)
+            }
+            None => Mask::new(width, height)
+        }
+    }
+
+    pub fn give_mask(&self, mask: Mask) {
+        let mut masks = self.masks.borrow_mut();
+        if masks.len() == MAX_POOLED {
+            masks.remove(0);
+        }
+        masks.push(mask);
+    }
+}
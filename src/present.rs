@@ -0,0 +1,142 @@
+use std::num::NonZeroU32;
+use std::rc::Rc;
+
+use softbuffer::Surface;
+use tiny_skia::{Pixmap, PixmapMut};
+use winit::window::Window;
+
+use crate::{damage, math, Damage};
+use crate::error::Result;
+
+
+/// Puts the frames an `Application` draws onto the screen.
+///
+/// The application creates its window and then calls `attach` with it; every frame after that goes
+/// through `present`, which hands `draw` a canvas the size of the window and shows it once `draw`
+/// returns. The canvas holds the frame drawn before it until the window is resized, since `draw`
+/// only draws again what changed and returns where that is, see `Root::draw_damage`.
+pub trait Presenter {
+    fn attach(&mut self, window: Rc<Window>) -> Result<()>;
+
+    fn present(&mut self, window: &Window, draw: &mut dyn FnMut(PixmapMut) -> Damage) -> Result<()>;
+}
+
+type WindowSurface = (softbuffer::Context<Rc<Window>>, Surface<Rc<Window>, Rc<Window>>);
+
+/// Copies frames to the window through the CPU. This is the default presenter.
+///
+/// Only the rects a frame drew again are copied and handed to the compositor, as long as the
+/// window's buffer still holds the frame before it. Frames which didn't change at all aren't
+/// presented.
+#[derive(Default)]
+pub struct SoftbufferPresenter {
+    surface: Option<WindowSurface>,
+    // the frame being drawn, which is kept to draw the next one over
+    frame: Option<Pixmap>
+}
+
+impl SoftbufferPresenter {
+    pub fn new() -> SoftbufferPresenter {
+        SoftbufferPresenter::default()
+    }
+}
+
+impl Presenter for SoftbufferPresenter {
+    fn attach(&mut self, window: Rc<Window>) -> Result<()> {
+        let context = softbuffer::Context::new(Rc::clone(&window))?;
+        let surface = Surface::new(&context, window)?;
+        self.surface = Some((context, surface));
+        Ok(())
+    }
+
+    fn present(&mut self, window: &Window, draw: &mut dyn FnMut(PixmapMut) -> Damage) -> Result<()> {
+        let Some((_, surface)) = &mut self.surface else { return Ok(()); };
+
+        let size = window.inner_size();
+        let (Some(width), Some(height)) = (NonZeroU32::new(size.width), NonZeroU32::new(size.height)) else { return Ok(()); };
+        if self.frame.as_ref().map(|frame| (frame.width(), frame.height())) != Some((size.width, size.height)) {
+            self.frame = Pixmap::new(size.width, size.height);
+        }
+        let Some(frame) = &mut self.frame else { return Ok(()); };
+        let damage = draw(frame.as_mut());
+        if damage.is_empty() {
+            return Ok(());
+        }
+
+        surface.resize(width, height)?;
+        let mut buffer = surface.buffer_mut()?;
+        // a buffer which didn't show the last frame needs all of this one
+        let rects = match damage {
+            Damage::Rects(rects) if buffer.age() == 1 => Some(rects),
+            _ => None
+        };
+        // the buffer may not match the window size yet while a resize is in flight; skip the frame
+        let Some(mut pixmap) = PixmapMut::from_bytes(bytemuck::must_cast_slice_mut(buffer.as_mut()), size.width, size.height) else { return Ok(()); };
+        match &rects {
+            Some(rects) => for rect in rects {
+                damage::copy_rect(frame, &mut pixmap, *rect);
+            },
+            None => pixmap.data_mut().copy_from_slice(frame.data())
+        }
+
+        window.pre_present_notify();
+        match rects {
+            Some(rects) => buffer.present_with_damage(&rects.into_iter().filter_map(to_softbuffer_rect).collect::<Vec<_>>())?,
+            None => buffer.present()?
+        }
+        Ok(())
+    }
+}
+
+fn to_softbuffer_rect(rect: math::Rect) -> Option<softbuffer::Rect> {
+    Some(softbuffer::Rect {
+        x: rect.x as u32,
+        y: rect.y as u32,
+        width: NonZeroU32::new(rect.w as u32)?,
+        height: NonZeroU32::new(rect.h as u32)?
+    })
+}
+
+type FrameCallback = Box<dyn FnMut(&Pixmap)>;
+
+/// Draws frames into memory without ever showing them, e.g. to test or record an application.
+///
+/// The window is still created, since it drives the event loop and receives input, but nothing
+/// is drawn into it.
+#[derive(Default)]
+pub struct HeadlessPresenter {
+    frame: Option<Pixmap>,
+    on_frame: Option<FrameCallback>
+}
+
+impl HeadlessPresenter {
+    pub fn new() -> HeadlessPresenter {
+        HeadlessPresenter::default()
+    }
+
+    /// Calls `on_frame` with every frame once it has been drawn.
+    pub fn with_frame_callback(mut self, on_frame: impl FnMut(&Pixmap) + 'static) -> HeadlessPresenter {
+        self.on_frame = Some(Box::new(on_frame));
+        self
+    }
+}
+
+impl Presenter for HeadlessPresenter {
+    fn attach(&mut self, _window: Rc<Window>) -> Result<()> {
+        Ok(())
+    }
+
+    fn present(&mut self, window: &Window, draw: &mut dyn FnMut(PixmapMut) -> Damage) -> Result<()> {
+        let size = window.inner_size();
+        if self.frame.as_ref().map(|frame| (frame.width(), frame.height())) != Some((size.width, size.height)) {
+            self.frame = Pixmap::new(size.width, size.height);
+        }
+        let Some(frame) = &mut self.frame else { return Ok(()); };
+
+        draw(frame.as_mut());
+        if let Some(on_frame) = &mut self.on_frame {
+            on_frame(frame);
+        }
+        Ok(())
+    }
+}
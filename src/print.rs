@@ -0,0 +1,121 @@
+use tiny_skia::{IntRect, Pixmap, PixmapPaint, Transform};
+
+use crate::{Color, math, Overlays, PageBreak, PixmapPool, RenderContext, Root};
+use crate::error::{Error, Result};
+use crate::text::TextContext;
+
+
+/// The vertical extent of a widget which asked for special treatment at page boundaries.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BreakHint {
+    pub top: f32,
+    pub bottom: f32,
+    pub page_break: PageBreak
+}
+
+/// Lays out an element tree as a document and splits it into pages.
+///
+/// The tree is given the page width and as much height as it asks for, then cut into page-sized
+/// slices, moving cuts up to honour `PageBreak` hints. Pages are rasterized; vector output is not
+/// supported by the renderer.
+pub struct Paginator {
+    page_size: math::Size,
+    scale_factor: f32,
+    background: Color
+}
+
+impl Paginator {
+    /// Creates a paginator producing pages of `page_size` pixels.
+    pub fn new(page_size: math::Size) -> Paginator {
+        Paginator {
+            page_size,
+            scale_factor: 1.0,
+            background: Color::WHITE
+        }
+    }
+
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Paginator {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    pub fn with_background(mut self, background: Color) -> Paginator {
+        self.background = background;
+        self
+    }
+
+    /// Lays out `root` and returns the document-space rect covered by each page.
+    pub fn paginate<A>(&self, root: &mut Root<A>, model: &mut A, text: &TextContext) -> Vec<math::Rect> {
+        root.update(model);
+        let width = self.page_size.width();
+        let measured = root.measure(text, math::Size::new(width, f32::INFINITY), self.scale_factor);
+        // elements which expand vertically fill exactly one page
+        let document_height = if measured.height().is_finite() { measured.height() } else { self.page_size.height() };
+        root.layout(text, math::Size::new(width, document_height.max(self.page_size.height())), self.scale_factor);
+
+        let hints = root.page_breaks();
+        split_pages(document_height, self.page_size.height(), &hints).into_iter()
+            .map(|(top, bottom)| math::Rect::from_lrtb(0.0, width, top, bottom))
+            .collect()
+    }
+
+    /// Lays out `root` and rasterizes each page.
+    pub fn render<A>(&self, root: &mut Root<A>, model: &mut A, text: &TextContext) -> Result<Vec<Pixmap>> {
+        let pages = self.paginate(root, model, text);
+        let document_height = pages.last().map_or(0.0, |page| page.bottom());
+
+        let width = self.page_size.width().ceil() as u32;
+        let page_height = self.page_size.height().ceil() as u32;
+        let mut document = Pixmap::new(width, (document_height.ceil() as u32).max(1)).ok_or(Error::InvalidGeometry)?;
+        document.fill(self.background.into());
+        let theme = root.theme();
+        root.draw(&mut RenderContext { canvas: document.as_mut(), text, pool: &PixmapPool::new(), theme: &theme, overlays: &Overlays::new() });
+
+        let mut rendered = Vec::with_capacity(pages.len());
+        for page in pages {
+            let mut pixmap = Pixmap::new(width, page_height).ok_or(Error::InvalidGeometry)?;
+            pixmap.fill(self.background.into());
+            let top = page.top().round() as i32;
+            let height = (page.bottom().round() as i32 - top).max(1) as u32;
+            if let Some(slice) = IntRect::from_xywh(0, top, width, height).and_then(|rect| document.clone_rect(rect)) {
+                pixmap.draw_pixmap(0, 0, slice.as_ref(), &PixmapPaint::default(), Transform::identity(), None);
+            }
+            rendered.push(pixmap);
+        }
+        Ok(rendered)
+    }
+}
+
+fn split_pages(document_height: f32, page_height: f32, hints: &[BreakHint]) -> Vec<(f32, f32)> {
+    if page_height <= 0.0 || page_height.is_nan() {
+        return vec![(0.0, document_height)];
+    }
+
+    let mut pages = Vec::new();
+    let mut start = 0.0;
+    loop {
+        let mut end = (start + page_height).min(document_height);
+
+        let forced = hints.iter()
+            .filter(|hint| hint.page_break == PageBreak::Before && hint.top > start && hint.top < end)
+            .map(|hint| hint.top)
+            .reduce(f32::min);
+        if let Some(forced) = forced {
+            end = forced;
+        } else if end < document_height {
+            // each step moves the cut strictly upwards, so this terminates
+            while let Some(avoided) = hints.iter().find(|hint| {
+                hint.page_break == PageBreak::Avoid && hint.top > start && hint.top < end && hint.bottom > end
+            }) {
+                end = avoided.top;
+            }
+        }
+
+        pages.push((start, end));
+        if end >= document_height {
+            break;
+        }
+        start = end;
+    }
+    pages
+}
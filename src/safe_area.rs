@@ -0,0 +1,42 @@
+use std::rc::Rc;
+
+use crate::math;
+use crate::tracking::{ReadableSignal, RwSignal};
+
+
+/// Insets from the edges of the window that content should stay clear of, such as a custom
+/// titlebar overlay, a notch, or an on-screen keyboard.
+///
+/// A `SafeArea` is a shared, reactive handle: clones refer to the same insets, and widgets which
+/// read them are updated when they change. A `Root` given a safe area lays its tree out inside it.
+/// winit does not report insets, so they are set by the application or the embedding host.
+#[derive(Clone)]
+pub struct SafeArea {
+    insets: Rc<RwSignal<math::SizeRect>>
+}
+
+impl SafeArea {
+    pub fn new() -> SafeArea {
+        SafeArea { insets: Rc::new(RwSignal::new(0.0.into())) }
+    }
+
+    /// The current insets in physical pixels.
+    pub fn insets(&self) -> math::SizeRect {
+        self.insets.get()
+    }
+
+    pub fn set_insets(&self, insets: impl Into<math::SizeRect>) {
+        let insets = insets.into();
+        self.insets.update(|current| *current = insets);
+    }
+
+    pub fn track(&self) {
+        self.insets.track();
+    }
+}
+
+impl Default for SafeArea {
+    fn default() -> Self {
+        SafeArea::new()
+    }
+}
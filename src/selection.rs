@@ -0,0 +1,126 @@
+use crate::tracking::{ReadableSignal, RwSignal};
+
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SelectionMode {
+    #[default]
+    Single,
+    Multi
+}
+
+/// The modifier keys held while clicking an item, usually ctrl (or cmd) and shift.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct SelectModifiers {
+    pub toggle: bool,
+    pub range: bool
+}
+
+/// The selected items of a list, table or tree, keyed by `K`.
+///
+/// The selection and the anchor (the item range selections extend from) are signals, so widgets
+/// and the rest of the model can observe them and change them programmatically.
+pub struct SelectionModel<K> {
+    mode: SelectionMode,
+    selected: RwSignal<Vec<K>>,
+    anchor: RwSignal<Option<K>>
+}
+
+impl<K: Clone + PartialEq> SelectionModel<K> {
+    pub fn new(mode: SelectionMode) -> SelectionModel<K> {
+        SelectionModel {
+            mode,
+            selected: RwSignal::new(Vec::new()),
+            anchor: RwSignal::new(None)
+        }
+    }
+
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    pub fn selected(&self) -> &RwSignal<Vec<K>> {
+        &self.selected
+    }
+
+    pub fn anchor(&self) -> &RwSignal<Option<K>> {
+        &self.anchor
+    }
+
+    pub fn is_selected(&self, key: &K) -> bool {
+        self.selected.with(|selected| selected.contains(key))
+    }
+
+    pub fn select(&self, key: K) {
+        self.selected.update(|selected| {
+            selected.clear();
+            selected.push(key.clone());
+        });
+        self.anchor.update(|anchor| *anchor = Some(key));
+    }
+
+    /// Adds or removes `key`, keeping the rest of the selection. In single mode this selects `key`.
+    pub fn toggle(&self, key: K) {
+        if self.mode == SelectionMode::Single {
+            return self.select(key);
+        }
+        self.selected.update(|selected| {
+            match selected.iter().position(|selected| *selected == key) {
+                Some(index) => { selected.remove(index); }
+                None => selected.push(key.clone())
+            }
+        });
+        self.anchor.update(|anchor| *anchor = Some(key));
+    }
+
+    /// Selects every item between the anchor and `key` in `order`, the keys in display order.
+    ///
+    /// With `extend` the range is added to the selection rather than replacing it. The anchor is
+    /// left unchanged so the range can be adjusted by selecting again.
+    pub fn select_range(&self, key: K, order: &[K], extend: bool) {
+        let anchor = self.anchor.get_untracked();
+        let (Some(anchor), SelectionMode::Multi) = (anchor, self.mode) else { return self.select(key); };
+        let (Some(from), Some(to)) = (order.iter().position(|k| *k == anchor), order.iter().position(|k| *k == key)) else {
+            return self.select(key);
+        };
+
+        let range = &order[from.min(to)..=from.max(to)];
+        self.selected.update(|selected| {
+            if !extend {
+                selected.clear();
+            }
+            for key in range {
+                if !selected.contains(key) {
+                    selected.push(key.clone());
+                }
+            }
+        });
+    }
+
+    /// Applies the usual click semantics: a plain click selects only `key`, toggle adds or
+    /// removes it, and range selects from the anchor to `key`.
+    pub fn click(&self, key: K, modifiers: SelectModifiers, order: &[K]) {
+        match (modifiers.range, modifiers.toggle) {
+            (true, extend) => self.select_range(key, order, extend),
+            (false, true) => self.toggle(key),
+            (false, false) => self.select(key)
+        }
+    }
+
+    pub fn select_all(&self, order: &[K]) {
+        if self.mode == SelectionMode::Single {
+            return;
+        }
+        self.selected.update(|selected| *selected = order.to_vec());
+    }
+
+    pub fn clear(&self) {
+        self.selected.update(|selected| selected.clear());
+        self.anchor.update(|anchor| *anchor = None);
+    }
+}
+
+impl<K: Clone + PartialEq> Default for SelectionModel<K> {
+    fn default() -> Self {
+        SelectionModel::new(SelectionMode::default())
+    }
+}
@@ -0,0 +1,159 @@
+/// A key, either a character produced by the keyboard layout or a named key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Key {
+    Character(char),
+    Named(NamedKey)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NamedKey {
+    Enter,
+    Escape,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F(u8)
+}
+
+impl Key {
+    fn eq_ignore_case(&self, other: &Key) -> bool {
+        match (self, other) {
+            (Key::Character(a), Key::Character(b)) => a.to_lowercase().eq(b.to_lowercase()),
+            (a, b) => a == b
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    /// Command on macOS, the Windows key elsewhere.
+    pub meta: bool
+}
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers { ctrl: false, shift: false, alt: false, meta: false };
+    pub const CTRL: Modifiers = Modifiers { ctrl: true, ..Modifiers::NONE };
+    pub const SHIFT: Modifiers = Modifiers { shift: true, ..Modifiers::NONE };
+    pub const ALT: Modifiers = Modifiers { alt: true, ..Modifiers::NONE };
+    pub const META: Modifiers = Modifiers { meta: true, ..Modifiers::NONE };
+
+    /// The modifier used for most shortcuts on the current platform: Command on macOS, Ctrl elsewhere.
+    #[cfg(target_os = "macos")]
+    pub const PRIMARY: Modifiers = Modifiers::META;
+    #[cfg(not(target_os = "macos"))]
+    pub const PRIMARY: Modifiers = Modifiers::CTRL;
+}
+
+impl std::ops::BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Modifiers {
+            ctrl: self.ctrl | rhs.ctrl,
+            shift: self.shift | rhs.shift,
+            alt: self.alt | rhs.alt,
+            meta: self.meta | rhs.meta
+        }
+    }
+}
+
+/// A key press.
+///
+/// `logical` is the key as produced by the active keyboard layout, `physical` is the key at the
+/// same position on a US QWERTY layout, if it has a name there. On AZERTY the key labelled Z gives
+/// a logical `Character('z')` and a physical `Character('w')`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KeyInput {
+    pub logical: Key,
+    pub physical: Option<Key>,
+    pub modifiers: Modifiers,
+    pub repeat: bool
+}
+
+/// A key combination that triggers an action.
+///
+/// Shortcuts match the logical key, so Ctrl+Z is where the layout puts Z. Layouts without Latin
+/// letters (e.g. Cyrillic) fall back to the physical key, so Ctrl+Z still works there. Letters
+/// match regardless of case; modifiers must match exactly.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Shortcut {
+    pub modifiers: Modifiers,
+    pub key: Key
+}
+
+impl Shortcut {
+    pub fn new(modifiers: Modifiers, key: Key) -> Shortcut {
+        Shortcut { modifiers, key }
+    }
+
+    /// The platform's primary modifier with `key`, e.g. `Shortcut::primary('z')` for undo.
+    pub fn primary(key: char) -> Shortcut {
+        Shortcut::new(Modifiers::PRIMARY, Key::Character(key))
+    }
+
+    /// Picks `macos` when running on macOS and `other` everywhere else.
+    pub fn platform(other: Shortcut, macos: Shortcut) -> Shortcut {
+        if cfg!(target_os = "macos") { macos } else { other }
+    }
+
+    pub fn matches(&self, input: &KeyInput) -> bool {
+        if input.modifiers != self.modifiers {
+            return false;
+        }
+        if input.logical.eq_ignore_case(&self.key) {
+            return true;
+        }
+        let latin_layout = match input.logical {
+            Key::Character(c) => c.is_ascii(),
+            Key::Named(_) => true
+        };
+        !latin_layout && input.physical.is_some_and(|physical| physical.eq_ignore_case(&self.key))
+    }
+}
+
+
+type ShortcutAction<A> = Box<dyn Fn(&mut A)>;
+
+/// A set of shortcuts and the actions they run on the model.
+pub struct Shortcuts<A> {
+    bindings: Vec<(Shortcut, ShortcutAction<A>)>
+}
+
+impl<A> Shortcuts<A> {
+    pub fn new() -> Shortcuts<A> {
+        Shortcuts { bindings: Vec::new() }
+    }
+
+    pub fn add(&mut self, shortcut: Shortcut, action: impl Fn(&mut A) + 'static) {
+        self.bindings.push((shortcut, Box::new(action)));
+    }
+
+    /// Runs the action of the first shortcut matching `input`, returning whether there was one.
+    pub fn handle(&self, input: &KeyInput, model: &mut A) -> bool {
+        match self.bindings.iter().find(|(shortcut, _)| shortcut.matches(input)) {
+            Some((_, action)) => {
+                action(model);
+                true
+            }
+            None => false
+        }
+    }
+}
+
+impl<A> Default for Shortcuts<A> {
+    fn default() -> Self {
+        Shortcuts::new()
+    }
+}
@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rodio::Source;
+
+use crate::error::{Error, Result};
+use crate::tracking::{ReadableSignal, RwSignal};
+
+
+/// The kinds of short feedback sounds widgets can play, e.g. see `Button::with_cue`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Cue {
+    Click,
+    Error,
+    Notify
+}
+
+impl Cue {
+    fn default_tone(&self) -> (f32, Duration) {
+        match self {
+            Cue::Click => (1200.0, Duration::from_millis(15)),
+            Cue::Error => (220.0, Duration::from_millis(150)),
+            Cue::Notify => (880.0, Duration::from_millis(80)),
+        }
+    }
+}
+
+enum Output {
+    Uninitialized,
+    // why the device could not be opened
+    Unavailable(String),
+    // the stream must be kept alive for as long as sounds are played through the handle
+    Available { _stream: rodio::OutputStream, handle: rodio::OutputStreamHandle }
+}
+
+struct Sounds {
+    output: Output,
+    cues: HashMap<Cue, Arc<[u8]>>,
+    muted: RwSignal<bool>
+}
+
+thread_local! {
+    static SOUNDS: RefCell<Sounds> = RefCell::new(Sounds {
+        output: Output::Uninitialized,
+        cues: HashMap::new(),
+        muted: RwSignal::new(false)
+    });
+}
+
+/// Plays `cue` on the default audio device unless sounds are muted.
+///
+/// The audio device is opened on first use; if that fails, every later call returns the same
+/// error without trying again.
+pub fn play(cue: Cue) -> Result<()> {
+    SOUNDS.with_borrow_mut(|sounds| {
+        if sounds.muted.get_untracked() {
+            return Ok(());
+        }
+
+        if let Output::Uninitialized = sounds.output {
+            sounds.output = match rodio::OutputStream::try_default() {
+                Ok((stream, handle)) => Output::Available { _stream: stream, handle },
+                Err(error) => Output::Unavailable(format!("no audio device: {}", error))
+            };
+        }
+        if let Output::Unavailable(reason) = &sounds.output {
+            return Err(Error::Sound(reason.clone()));
+        }
+        let Output::Available { handle, .. } = &sounds.output else { return Ok(()); };
+
+        let result = match sounds.cues.get(&cue) {
+            Some(data) => {
+                let decoder = rodio::Decoder::new(Cursor::new(Arc::clone(data)))
+                    .map_err(|error| Error::Sound(format!("could not decode the sound for {:?}: {}", cue, error)))?;
+                handle.play_raw(decoder.convert_samples())
+            }
+            None => {
+                let (frequency, duration) = cue.default_tone();
+                handle.play_raw(rodio::source::SineWave::new(frequency).take_duration(duration).amplify(0.2))
+            }
+        };
+        result.map_err(|error| Error::Sound(format!("could not play the sound for {:?}: {}", cue, error)))
+    })
+}
+
+/// Replaces the built-in tone for `cue` with an encoded (e.g. WAV) sound.
+pub fn set_cue_sound(cue: Cue, data: impl Into<Arc<[u8]>>) {
+    SOUNDS.with_borrow_mut(|sounds| {
+        sounds.cues.insert(cue, data.into());
+    });
+}
+
+pub fn set_muted(muted: bool) {
+    SOUNDS.with_borrow(|sounds| sounds.muted.update(|value| *value = muted));
+}
+
+/// Whether sounds are muted. This is tracked, so widgets reading it update when it changes.
+pub fn is_muted() -> bool {
+    SOUNDS.with_borrow(|sounds| sounds.muted.get())
+}
@@ -0,0 +1,295 @@
+#[cfg(feature = "text")]
+use std::collections::HashMap;
+#[cfg(feature = "text")]
+use std::sync::{Arc, Mutex};
+use std::sync::OnceLock;
+
+#[cfg(feature = "text")]
+use swash::scale::{Render, ScaleContext, Source, StrikeWith};
+#[cfg(feature = "text")]
+use swash::zeno::{Angle, Format, Transform, Vector};
+
+#[cfg(feature = "text")]
+use crate::Color;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Hinting {
+    /// Hint glyphs at scale factors below 2, where snapping outlines to the pixel grid makes small
+    /// text noticeably sharper, and leave them unhinted on high DPI displays.
+    Auto,
+    On,
+    Off
+}
+
+/// How glyphs are rasterized.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TextRendering {
+    pub hinting: Hinting,
+    /// Whether glyph edges are antialiased. Without antialiasing glyphs are drawn as solid pixels.
+    pub antialias: bool,
+    /// Whether glyphs are placed at fractional pixel offsets. Disabling this trades slightly
+    /// uneven spacing for crisper stems on low DPI displays.
+    pub subpixel_positioning: bool
+}
+
+impl Default for TextRendering {
+    fn default() -> Self {
+        TextRendering {
+            hinting: Hinting::Auto,
+            antialias: true,
+            subpixel_positioning: true
+        }
+    }
+}
+
+#[cfg(feature = "text")]
+impl TextRendering {
+    fn hint_at(&self, scale_factor: f32) -> bool {
+        match self.hinting {
+            Hinting::Auto => scale_factor < 2.0,
+            Hinting::On => true,
+            Hinting::Off => false
+        }
+    }
+}
+
+
+/// Set in the metadata of text attributes to underline the glyphs shaped with them.
+#[cfg(feature = "text")]
+pub(crate) const UNDERLINE: usize = 1;
+
+#[cfg(feature = "text")]
+struct CachedGlyph {
+    offset: (i32, i32),
+    image: Option<tiny_skia::Pixmap>
+}
+
+#[cfg(feature = "text")]
+struct GlyphCache {
+    scale_context: ScaleContext,
+    rendering: TextRendering,
+    // glyphs are keyed on whether they were hinted, since that depends on the scale factor, and
+    // on the color they were filled with
+    cached_glyphs: HashMap<(cosmic_text::CacheKey, bool, Color), CachedGlyph>
+}
+
+#[cfg(feature = "text")]
+impl GlyphCache {
+    fn new() -> GlyphCache {
+        GlyphCache {
+            scale_context: ScaleContext::new(),
+            rendering: TextRendering::default(),
+            cached_glyphs: HashMap::new()
+        }
+    }
+
+    fn set_rendering(&mut self, rendering: TextRendering) {
+        if rendering != self.rendering {
+            self.rendering = rendering;
+            self.cached_glyphs.clear();
+        }
+    }
+
+    fn get_glyph(&mut self, fonts: &mut cosmic_text::FontSystem, key: cosmic_text::CacheKey, scale_factor: f32, color: Color) -> &CachedGlyph {
+        let hint = self.rendering.hint_at(scale_factor);
+        self.cached_glyphs.entry((key, hint, color))
+            .or_insert_with(|| Self::render(fonts, &mut self.scale_context, self.rendering, key, hint, color))
+    }
+
+    fn rasterize(fonts: &mut cosmic_text::FontSystem, scale_context: &mut ScaleContext, rendering: TextRendering, key: cosmic_text::CacheKey, hint: bool) -> Option<swash::scale::image::Image> {
+        let font = fonts.get_font(key.font_id)?;
+        let mut scaler = scale_context.builder(font.as_swash())
+            .size(f32::from_bits(key.font_size_bits))
+            .hint(hint)
+            .build();
+        let offset = match rendering.subpixel_positioning {
+            true => Vector::new(key.x_bin.as_float(), key.y_bin.as_float()),
+            false => Vector::new(0.0, 0.0)
+        };
+        let skew = key.flags.contains(cosmic_text::CacheKeyFlags::FAKE_ITALIC)
+            .then(|| Transform::skew(Angle::from_degrees(14.0), Angle::from_degrees(0.0)));
+
+        let mut image = Render::new(&[Source::ColorOutline(0), Source::ColorBitmap(StrikeWith::BestFit), Source::Outline])
+            .format(Format::Alpha)
+            .offset(offset)
+            .transform(skew)
+            .render(&mut scaler, key.glyph_id)?;
+        if !rendering.antialias {
+            for coverage in &mut image.data {
+                *coverage = if *coverage >= 128 { 255 } else { 0 };
+            }
+        }
+        Some(image)
+    }
+
+    fn render(fonts: &mut cosmic_text::FontSystem, scale_context: &mut ScaleContext, rendering: TextRendering, key: cosmic_text::CacheKey, hint: bool, color: Color) -> CachedGlyph {
+        if let Some(swash_image) = Self::rasterize(fonts, scale_context, rendering, key, hint) {
+            let size = tiny_skia::IntSize::from_wh(swash_image.placement.width, swash_image.placement.height);
+            // the mask is rejected when the glyph isn't a plain coverage mask, e.g. a color emoji
+            let mask = size.and_then(|size| tiny_skia::Mask::from_vec(swash_image.data, size));
+            let image = mask.and_then(|mask| {
+                let mut image = tiny_skia::Pixmap::new(mask.width(), mask.height())?;
+                let mut paint = tiny_skia::Paint::default();
+                paint.set_color(color.into());
+
+                let rect = tiny_skia::Rect::from_xywh(0.0, 0.0, mask.width() as f32, mask.height() as f32)?;
+                image.fill_rect(rect, &paint, tiny_skia::Transform::identity(), Some(&mask));
+                Some(image)
+            });
+
+            CachedGlyph {
+                offset: (swash_image.placement.left, swash_image.placement.top),
+                image
+            }
+        } else {
+            CachedGlyph {
+                offset: (0, 0),
+                image: None
+            }
+        }
+    }
+}
+
+
+#[cfg(feature = "text")]
+struct TextContextInner {
+    fonts: Mutex<cosmic_text::FontSystem>,
+    glyph_cache: Mutex<GlyphCache>,
+}
+
+/// Owns the font database and the rendered glyph cache used by text widgets.
+///
+/// A `TextContext` is a cheap handle; clones refer to the same fonts. Every `Application` owns
+/// one, but the same context can be handed to several applications (or windows) with
+/// `Application::with_text_context` so that font data and glyphs are only loaded once.
+///
+/// Without the `text` feature this is an empty placeholder, so layout and drawing keep the same
+/// signatures.
+#[derive(Clone)]
+pub struct TextContext {
+    #[cfg(feature = "text")]
+    inner: Arc<TextContextInner>
+}
+
+impl TextContext {
+    #[cfg(feature = "text")]
+    pub fn new() -> TextContext {
+        TextContext::from_font_system(cosmic_text::FontSystem::new())
+    }
+
+    #[cfg(not(feature = "text"))]
+    pub fn new() -> TextContext {
+        TextContext { }
+    }
+
+    #[cfg(feature = "text")]
+    pub fn from_font_system(fonts: cosmic_text::FontSystem) -> TextContext {
+        TextContext {
+            inner: Arc::new(TextContextInner {
+                fonts: Mutex::new(fonts),
+                glyph_cache: Mutex::new(GlyphCache::new())
+            })
+        }
+    }
+
+    /// Returns a handle to the process-wide context, creating it on first use.
+    pub fn shared() -> TextContext {
+        static SHARED: OnceLock<TextContext> = OnceLock::new();
+        SHARED.get_or_init(TextContext::new).clone()
+    }
+
+    #[cfg(feature = "text")]
+    pub fn with_fonts<O>(&self, f: impl FnOnce(&mut cosmic_text::FontSystem) -> O) -> O {
+        let mut fonts = self.inner.fonts.lock().unwrap();
+        f(&mut fonts)
+    }
+
+    /// Changes how glyphs are rasterized for everything drawn with this context.
+    #[cfg(feature = "text")]
+    pub fn set_rendering(&self, rendering: TextRendering) {
+        self.inner.glyph_cache.lock().unwrap().set_rendering(rendering);
+    }
+
+    /// Draws the glyphs of `buffer` in `color`, except for glyphs whose attributes set their own,
+    /// cut off as `truncation` says if given.
+    #[cfg(feature = "text")]
+    pub(crate) fn draw_buffer(&self, canvas: &mut tiny_skia::PixmapMut, buffer: &cosmic_text::Buffer, top_left: (f32, f32), scale_factor: f32, color: Color, truncation: Option<Truncation>) {
+        let mut fonts = self.inner.fonts.lock().unwrap();
+        let mut glyph_cache = self.inner.glyph_cache.lock().unwrap();
+
+        let mut draw_glyphs = |glyphs: &[cosmic_text::LayoutGlyph], x_shift: f32, line_y: f32| {
+            let top_left = (top_left.0 + x_shift, top_left.1);
+            for glyph in glyphs {
+                let physical_glyph = glyph.physical(top_left, 1.0);
+                let color = glyph.color_opt.map_or(color, |color| Color::from_rgba8(color.r(), color.g(), color.b(), color.a()));
+
+                let rendered_glyph = glyph_cache.get_glyph(&mut fonts, physical_glyph.cache_key, scale_factor, color);
+                if let Some(glyph_image) = &rendered_glyph.image {
+                    let x_off = top_left.0 + glyph.x + glyph.x_offset;
+                    let y_off = top_left.1 + glyph.y_offset + line_y;
+
+                    canvas.draw_pixmap(
+                        rendered_glyph.offset.0 + x_off as i32,
+                        -rendered_glyph.offset.1 + y_off as i32,
+                        glyph_image.as_ref(),
+                        &tiny_skia::PixmapPaint::default(), tiny_skia::Transform::identity(), None
+                    );
+                }
+                if glyph.metadata & UNDERLINE != 0 {
+                    // a little below the baseline, each glyph underlining its whole advance
+                    let thickness = (glyph.font_size / 15.0).max(1.0);
+                    let y = top_left.1 + line_y + 0.1 * glyph.font_size;
+                    if let Some(rect) = tiny_skia::Rect::from_xywh(top_left.0 + glyph.x, y, glyph.w, thickness) {
+                        let mut paint = tiny_skia::Paint::default();
+                        paint.set_color(color.into());
+                        canvas.fill_rect(rect, &paint, tiny_skia::Transform::identity(), None);
+                    }
+                }
+            }
+        };
+
+        let Some(truncation) = truncation else {
+            for run in buffer.layout_runs() {
+                draw_glyphs(run.glyphs, 0.0, run.line_y);
+            }
+            return;
+        };
+        let line_count = buffer.layout_runs().count();
+        let shown_lines = line_count.min(truncation.max_lines);
+        let ellipsis = truncation.ellipsis.and_then(|ellipsis| ellipsis.layout_runs().next());
+        for (index, run) in buffer.layout_runs().take(shown_lines).enumerate() {
+            let hides_text = index + 1 == shown_lines && line_count > shown_lines;
+            if !hides_text && run.line_w <= truncation.width {
+                draw_glyphs(run.glyphs, 0.0, run.line_y);
+                continue;
+            }
+            // the line is cut off, keeping room for the ellipsis at its end
+            let limit = truncation.width - ellipsis.as_ref().map_or(0.0, |ellipsis| ellipsis.line_w);
+            let kept = run.glyphs.iter().take_while(|glyph| glyph.x + glyph.w <= limit).count();
+            draw_glyphs(&run.glyphs[..kept], 0.0, run.line_y);
+            if let Some(ellipsis) = &ellipsis {
+                let end = run.glyphs[..kept].last().map_or(0.0, |glyph| glyph.x + glyph.w);
+                draw_glyphs(ellipsis.glyphs, end, run.line_y);
+            }
+        }
+    }
+}
+
+/// Where `TextContext::draw_buffer` cuts text off.
+#[cfg(feature = "text")]
+pub(crate) struct Truncation<'a> {
+    /// Lines after this many are not drawn.
+    pub max_lines: usize,
+    /// Glyphs which would end past this, relative to the left of the buffer, are not drawn.
+    pub width: f32,
+    /// Drawn at the end of lines which were cut off or are followed by hidden lines. It should be
+    /// shaped with the same metrics as the buffer.
+    pub ellipsis: Option<&'a cosmic_text::Buffer>
+}
+
+impl Default for TextContext {
+    fn default() -> Self {
+        TextContext::new()
+    }
+}
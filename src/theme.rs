@@ -0,0 +1,61 @@
+use crate::style::Color;
+
+
+/// The colors and sizes widgets fall back to when they aren't given their own.
+///
+/// A `Root` holds the theme its tree is drawn with, see `Root::with_theme` and
+/// `Root::bind_theme`. Widgets read it from the `LayoutContext` and `RenderContext` they are given.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Theme {
+    /// Behind everything, e.g. the window.
+    pub background: Color,
+    /// Raised areas such as buttons.
+    pub surface: Color,
+    /// Text, borders, outlines and marks such as a checkbox's check.
+    pub foreground: Color,
+    /// The filled part of sliders and gauges.
+    pub accent: Color,
+    /// The unfilled part of sliders and gauges.
+    pub track: Color,
+    /// The size of text which doesn't set its own, in logical pixels.
+    pub font_size: f32,
+    /// The unit of the spacing scale, in logical pixels, see `space`.
+    pub spacing: f32
+}
+
+impl Theme {
+    pub fn light() -> Theme {
+        Theme {
+            background: Color::WHITE,
+            surface: Color::LIGHT_GRAY,
+            foreground: Color::BLACK,
+            accent: Color::BLUE,
+            track: Color::LIGHT_GRAY,
+            font_size: 15.0,
+            spacing: 4.0
+        }
+    }
+
+    pub fn dark() -> Theme {
+        Theme {
+            background: Color::from_rgb8(30, 30, 30),
+            surface: Color::from_rgb8(62, 62, 62),
+            foreground: Color::from_rgb8(220, 220, 220),
+            accent: Color::from_rgb8(90, 150, 255),
+            track: Color::from_rgb8(80, 80, 80),
+            font_size: 15.0,
+            spacing: 4.0
+        }
+    }
+
+    /// `steps` units of the spacing scale, for margins and padding which follow the theme.
+    pub fn space(&self, steps: f32) -> f32 {
+        steps * self.spacing
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
@@ -0,0 +1,108 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+
+/// Identifies a timer started by `Timers`, so that it can be cancelled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+enum Callback<A> {
+    Once(Box<dyn FnOnce(&mut A)>),
+    // shared so that it can be called while the timers are borrowed to reschedule it
+    Every(Duration, Rc<dyn Fn(&mut A)>)
+}
+
+struct Timer<A> {
+    id: TimerId,
+    deadline: Instant,
+    callback: Callback<A>
+}
+
+struct TimersInner<A> {
+    next_id: Cell<u64>,
+    timers: RefCell<Vec<Timer<A>>>
+}
+
+/// Callbacks which run on the model after a delay, e.g. to debounce input, blink a caret or poll
+/// periodically, without a thread of their own.
+///
+/// Keep a clone in the model to start timers from event handlers, and hand one to
+/// `Application::with_timers` so that its event loop sleeps until the next timer is due and runs
+/// it. An embedding host calls `run_due` itself. Clones share the same timers.
+pub struct Timers<A> {
+    inner: Rc<TimersInner<A>>
+}
+
+impl<A> Timers<A> {
+    pub fn new() -> Timers<A> {
+        Timers {
+            inner: Rc::new(TimersInner {
+                next_id: Cell::new(0),
+                timers: RefCell::new(Vec::new())
+            })
+        }
+    }
+
+    fn add(&self, deadline: Instant, callback: Callback<A>) -> TimerId {
+        let id = TimerId(self.inner.next_id.get());
+        self.inner.next_id.set(id.0 + 1);
+        self.inner.timers.borrow_mut().push(Timer { id, deadline, callback });
+        id
+    }
+
+    /// Runs `callback` once, after `delay`.
+    pub fn set_timeout(&self, delay: Duration, callback: impl FnOnce(&mut A) + 'static) -> TimerId {
+        self.add(Instant::now() + delay, Callback::Once(Box::new(callback)))
+    }
+
+    /// Runs `callback` every `period`, at least a millisecond, until the timer is cancelled. If
+    /// the event loop falls behind, missed runs are skipped rather than run back to back.
+    pub fn set_interval(&self, period: Duration, callback: impl Fn(&mut A) + 'static) -> TimerId {
+        let period = period.max(Duration::from_millis(1));
+        self.add(Instant::now() + period, Callback::Every(period, Rc::new(callback)))
+    }
+
+    /// Stops a timer from running again. Cancelling a timeout which already ran does nothing.
+    pub fn cancel(&self, id: TimerId) {
+        self.inner.timers.borrow_mut().retain(|timer| timer.id != id);
+    }
+
+    /// Runs the timers which are due on `model`, and returns when the next one will be.
+    pub fn run_due(&self, model: &mut A) -> Option<Instant> {
+        let now = Instant::now();
+        // taken out first, since callbacks may start or cancel timers
+        let due: Vec<Timer<A>> = {
+            let mut timers = self.inner.timers.borrow_mut();
+            let (due, pending) = timers.drain(..).partition(|timer| timer.deadline <= now);
+            *timers = pending;
+            due
+        };
+        for timer in due {
+            match timer.callback {
+                Callback::Once(callback) => callback(model),
+                Callback::Every(period, callback) => {
+                    let mut deadline = timer.deadline + period;
+                    while deadline <= now {
+                        deadline += period;
+                    }
+                    self.inner.timers.borrow_mut().push(Timer { id: timer.id, deadline, callback: Callback::Every(period, Rc::clone(&callback)) });
+                    callback(model);
+                }
+            }
+        }
+        self.inner.timers.borrow().iter().map(|timer| timer.deadline).min()
+    }
+}
+
+impl<A> Clone for Timers<A> {
+    fn clone(&self) -> Self {
+        Timers { inner: Rc::clone(&self.inner) }
+    }
+}
+
+impl<A> Default for Timers<A> {
+    fn default() -> Self {
+        Timers::new()
+    }
+}
@@ -0,0 +1,107 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::tracking::{ReadableSignal, RwSignal};
+
+
+struct Toast {
+    id: u64,
+    message: String,
+    expires: Instant
+}
+
+struct ToastsInner {
+    next_id: Cell<u64>,
+    toasts: RefCell<Vec<Toast>>,
+    // bumped whenever a toast is shown or dismissed
+    revision: RwSignal<u64>
+}
+
+/// Short messages such as "Saved" which pop up in a corner of the window and go away on their own
+/// after a while, or when clicked.
+///
+/// Keep a clone in the model to show toasts from event handlers, and hand one to a `ToastOverlay`
+/// around the content to draw them. Clones share the same toasts.
+pub struct Toasts {
+    inner: Rc<ToastsInner>
+}
+
+impl Toasts {
+    pub fn new() -> Toasts {
+        Toasts {
+            inner: Rc::new(ToastsInner {
+                next_id: Cell::new(0),
+                toasts: RefCell::new(Vec::new()),
+                revision: RwSignal::new(0)
+            })
+        }
+    }
+
+    fn changed(&self) {
+        self.inner.revision.update(|revision| *revision += 1);
+    }
+
+    /// Shows `message` for `duration`, below the toasts already shown.
+    pub fn show(&self, message: impl Into<String>, duration: Duration) {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+        self.inner.toasts.borrow_mut().push(Toast { id, message: message.into(), expires: Instant::now() + duration });
+        self.changed();
+    }
+
+    /// Dismisses every toast.
+    pub fn clear(&self) {
+        if !self.inner.toasts.borrow().is_empty() {
+            self.inner.toasts.borrow_mut().clear();
+            self.changed();
+        }
+    }
+
+    /// How many toasts are shown, read tracked.
+    pub fn len(&self) -> usize {
+        self.inner.revision.track();
+        self.inner.toasts.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn dismiss(&self, id: u64) {
+        let len = self.inner.toasts.borrow().len();
+        self.inner.toasts.borrow_mut().retain(|toast| toast.id != id);
+        if self.inner.toasts.borrow().len() != len {
+            self.changed();
+        }
+    }
+
+    // dismisses the toasts whose time is up, and returns when the next one will be
+    pub(crate) fn expire(&self) -> Option<Instant> {
+        let now = Instant::now();
+        let len = self.inner.toasts.borrow().len();
+        self.inner.toasts.borrow_mut().retain(|toast| toast.expires > now);
+        if self.inner.toasts.borrow().len() != len {
+            self.changed();
+        }
+        self.inner.toasts.borrow().iter().map(|toast| toast.expires).min()
+    }
+
+    // the id and message of each toast, oldest first, read tracked
+    pub(crate) fn messages(&self) -> Vec<(u64, String)> {
+        self.inner.revision.track();
+        self.inner.toasts.borrow().iter().map(|toast| (toast.id, toast.message.clone())).collect()
+    }
+}
+
+impl Clone for Toasts {
+    fn clone(&self) -> Self {
+        Toasts { inner: Rc::clone(&self.inner) }
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Toasts::new()
+    }
+}
@@ -1,6 +1,10 @@
 use std::cell::{Cell, RefCell};
 use std::marker::PhantomData;
 use std::rc::{Rc, Weak};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use crate::channel::ReceiverSignal;
 
 
 struct Scope {
@@ -9,6 +13,90 @@ struct Scope {
 
 thread_local! {
     static SCOPE: Cell<Option<Scope>> = const { Cell::new(None) };
+    static WAKEUPS: RefCell<Vec<(Instant, Weak<ObserverInner>)>> = const { RefCell::new(Vec::new()) };
+    static BATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
+    // the observers of signals written during a batch, made dirty once the batch ends
+    static DEFERRED: RefCell<Vec<Rc<ObserverInner>>> = const { RefCell::new(Vec::new()) };
+    // memos whose inputs changed, recomputed once the dirtiness has finished spreading
+    static PENDING_MEMOS: RefCell<Vec<Rc<dyn MemoNode>>> = const { RefCell::new(Vec::new()) };
+    static FLUSHING_MEMOS: Cell<bool> = const { Cell::new(false) };
+}
+
+// ends the batch even if its body panics
+struct BatchGuard;
+
+impl BatchGuard {
+    fn begin() -> BatchGuard {
+        BATCH_DEPTH.set(BATCH_DEPTH.get() + 1);
+        BatchGuard
+    }
+}
+
+impl Drop for BatchGuard {
+    fn drop(&mut self) {
+        let depth = BATCH_DEPTH.get() - 1;
+        BATCH_DEPTH.set(depth);
+        if depth == 0 {
+            // marking may run nothing but dirtiness propagation, so no new writes are deferred here
+            for observer in DEFERRED.take() {
+                observer.mark_dirty();
+            }
+        }
+    }
+}
+
+/// Runs `f`, holding back the effects of signal writes until it returns.
+///
+/// Values written inside `f` change immediately, but nothing which read them before becomes dirty
+/// until the outermost batch ends, so caches computed earlier in the same pass keep agreeing with
+/// each other. The root runs layout and drawing in a batch: anything written while drawing shows up
+/// in the next frame instead of half of the current one.
+///
+/// Every recomputation of a `Computed`, `Computed2` or `Derived` is a batch too. A computation
+/// which writes a signal it read is therefore left dirty, rather than having the write lost when
+/// it is marked clean, and is recomputed the next time it is used.
+pub fn batch<O>(f: impl FnOnce() -> O) -> O {
+    let _guard = BatchGuard::begin();
+    f()
+}
+
+// makes the dependents of `observer` dirty once `deadline` passes, see `poll_wakeups`
+fn wake_at(deadline: Instant, observer: &Rc<ObserverInner>) {
+    WAKEUPS.with_borrow_mut(|wakeups| wakeups.push((deadline, Rc::downgrade(observer))));
+}
+
+/// Makes the dependents of rate-limited computations whose delay has passed dirty, and returns
+/// when the next delay passes. The application calls this while waiting for events; code that
+/// drives a tree itself should call it too and redraw if the tree became dirty.
+pub fn poll_wakeups() -> Option<Instant> {
+    let now = Instant::now();
+    let mut due = Vec::new();
+    let next = WAKEUPS.with_borrow_mut(|wakeups| {
+        wakeups.retain(|(deadline, observer)| {
+            if *deadline > now {
+                return observer.strong_count() > 0;
+            }
+            due.extend(observer.upgrade());
+            false
+        });
+        wakeups.iter().map(|&(deadline, _)| deadline).min()
+    });
+    // triggered outside the borrow, since the dependents may schedule new wakeups
+    for observer in due {
+        observer.as_observable.trigger();
+    }
+    next
+}
+
+// restores the enclosing scope when dropped, so a panicking computation doesn't leave its scope installed
+struct ScopeGuard {
+    old_scope: Option<Scope>
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPE.set(self.old_scope.take());
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -48,69 +136,166 @@ impl ObservableInner {
         });
     }
 
+    // like `trigger`, but only queues the dependents while a batch is running
+    fn trigger_or_defer(&self) {
+        if BATCH_DEPTH.get() == 0 {
+            self.trigger();
+        } else {
+            let dependents = self.dependents.borrow();
+            DEFERRED.with_borrow_mut(|deferred| deferred.extend(dependents.iter().filter_map(Weak::upgrade)));
+        }
+    }
+
     pub fn trigger(&self) {
+        self.trigger_from(true);
+    }
+
+    // `direct` is false when the change reaches the dependents through a boundary, see `Watch`
+    fn trigger_from(&self, direct: bool) {
         let mut to_visit = Vec::new();
 
-        fn mark_and_push_children(to_visit: &mut Vec<Rc<ObserverInner>>, observable: &ObservableInner) {
+        fn mark_and_push_children(to_visit: &mut Vec<(Rc<ObserverInner>, bool)>, observable: &ObservableInner, direct: bool) {
             for dependent in observable.dependents.borrow().iter() {
                 if let Some(observer) = dependent.upgrade() {
-                    if observer.is_dirty.get().is_clean() {
+                    let newly_dirty = observer.is_dirty.get().is_clean();
+                    let newly_direct = direct && !observer.direct.replace(observer.direct.get() || direct);
+                    if newly_dirty {
                         observer.is_dirty.set(Dirtiness::Dirty);
-                        to_visit.push(observer);
+                        // a memo's dependents only become dirty if its value turns out different
+                        if observer.queue_memo() {
+                            continue;
+                        }
+                    } else if !newly_direct || observer.boundary || observer.memo.borrow().is_some() {
+                        // already dirty, and its dependents already know as much as this change tells them
+                        continue;
                     }
+                    let direct = direct && !observer.boundary;
+                    to_visit.push((observer, direct));
                 }
             }
         }
 
-        mark_and_push_children(&mut to_visit, self);
+        mark_and_push_children(&mut to_visit, self, direct);
 
-        while let Some(next) = to_visit.pop() {
-            mark_and_push_children(&mut to_visit, &next.as_observable);
+        while let Some((next, direct)) = to_visit.pop() {
+            mark_and_push_children(&mut to_visit, &next.as_observable, direct);
         }
+        flush_memos();
+    }
+}
+
+// recomputes the memos whose inputs changed, which may in turn queue more of them
+fn flush_memos() {
+    if FLUSHING_MEMOS.replace(true) {
+        return;
+    }
+    while let Some(memo) = PENDING_MEMOS.with_borrow_mut(Vec::pop) {
+        memo.recompute();
     }
+    FLUSHING_MEMOS.set(false);
 }
 
 struct ObserverInner {
     as_observable: ObservableInner,
     is_dirty: Cell<Dirtiness>,
+    // whether this observer stands for one element of the tree, see `Watch`
+    boundary: bool,
+    // whether it became dirty through a path which doesn't pass another boundary
+    direct: Cell<bool>,
+    // the memo this observer tracks the inputs of, if any
+    memo: RefCell<Option<Weak<dyn MemoNode>>>
 }
 
 impl ObserverInner {
     fn new(starting: Dirtiness) -> Rc<ObserverInner> {
         Rc::new(ObserverInner {
             as_observable: ObservableInner::new(),
-            is_dirty: Cell::new(starting)
+            is_dirty: Cell::new(starting),
+            boundary: false,
+            direct: Cell::new(starting.is_dirty()),
+            memo: RefCell::new(None)
         })
     }
 
     fn run_and_track<T>(f: impl FnOnce() -> T) -> (Rc<ObserverInner>, T) {
+        ObserverInner::run_and_track_as(false, f)
+    }
+
+    fn run_and_track_as<T>(boundary: bool, f: impl FnOnce() -> T) -> (Rc<ObserverInner>, T) {
         let observer = Rc::new(ObserverInner {
             as_observable: ObservableInner::new(),
-            is_dirty: Cell::new(Dirtiness::Clean)   // todo make sure the dependents are all clean
+            is_dirty: Cell::new(Dirtiness::Clean),   // todo make sure the dependents are all clean
+            boundary,
+            direct: Cell::new(false),
+            memo: RefCell::new(None)
         });
-        let old_scope = SCOPE.replace(Some(Scope { observers: Rc::clone(&observer) }));
+        let batch = BatchGuard::begin();
+        let guard = ScopeGuard { old_scope: SCOPE.replace(Some(Scope { observers: Rc::clone(&observer) })) };
         let value = f();
-        SCOPE.set(old_scope);
+        drop(guard);
         observer.is_dirty.set(Dirtiness::Clean);
+        observer.direct.set(false);
+        // writes made by `f` are only applied now, so that they aren't cleared along with the rest
+        drop(batch);
         (observer, value)
     }
 
+    // like `run_and_track`, but adds what `f` reads to the dependencies `observer` already has
+    fn track_into<T>(observer: &Rc<ObserverInner>, f: impl FnOnce() -> T) -> T {
+        let batch = BatchGuard::begin();
+        let guard = ScopeGuard { old_scope: SCOPE.replace(Some(Scope { observers: Rc::clone(observer) })) };
+        let value = f();
+        drop(guard);
+        drop(batch);
+        value
+    }
+
     fn is_dirty(&self) -> bool {
         self.is_dirty.get().is_dirty()
     }
 
     fn mark_dirty(&self) {
         let old_value = self.is_dirty.replace(Dirtiness::Dirty);
+        let was_direct = self.direct.replace(true);
         if old_value.is_clean() {
-            self.as_observable.trigger();
+            self.queue_memo();
+            self.as_observable.trigger_from(!self.boundary);
+        } else if !was_direct && !self.boundary {
+            self.as_observable.trigger_from(true);
         }
     }
+
+    // queues the memo this observer belongs to for recomputation, returning false if there is none
+    fn queue_memo(&self) -> bool {
+        let Some(memo) = self.memo.borrow().as_ref().and_then(Weak::upgrade) else { return false; };
+        PENDING_MEMOS.with_borrow_mut(|pending| pending.push(memo));
+        true
+    }
 }
 
 pub trait ReadableSignal<T> {
     fn get(&self) -> T;
     fn get_untracked(&self) -> T;
     fn track(&self);
+
+    /// Freezes this signal while `enabled` is false, see `Gate`.
+    fn gated_by<E: ReadableSignal<bool>>(self, enabled: E) -> Gate<Self, E, T> where Self: Sized, T: Clone {
+        Gate::new(self, enabled)
+    }
+}
+
+impl<T, S: ReadableSignal<T> + ?Sized> ReadableSignal<T> for Rc<S> {
+    fn get(&self) -> T {
+        (**self).get()
+    }
+
+    fn get_untracked(&self) -> T {
+        (**self).get_untracked()
+    }
+
+    fn track(&self) {
+        (**self).track()
+    }
 }
 
 pub trait WritableSignal<T> {
@@ -170,26 +355,59 @@ impl<T> ReadableSignal<T> for SignalInner<T> where T: Clone {
     }
 }
 
+type Subscriber<T> = Box<dyn Fn(&T) -> bool>;
+
 pub struct RwSignal<T> {
-    inner: SignalInner<T>
+    inner: SignalInner<T>,
+    // each returns false once its receiver is gone
+    subscribers: RefCell<Vec<Subscriber<T>>>
 }
 
 impl<T> RwSignal<T> {
     pub fn new(value: T) -> RwSignal<T> {
         RwSignal {
-            inner: SignalInner::new(value)
+            inner: SignalInner::new(value),
+            subscribers: RefCell::new(Vec::new())
         }
     }
 
+    /// A signal which takes the latest value sent through `receiver` whenever channels are pumped,
+    /// see `channel` and `pump_channels`.
+    pub fn from_receiver(initial: T, receiver: Receiver<T>) -> ReceiverSignal<T> where T: 'static {
+        ReceiverSignal::new(initial, receiver)
+    }
+
+    /// Returns a channel which receives a copy of the value after every change, e.g. to hand model
+    /// state to a worker thread.
+    pub fn subscribe(&self) -> Receiver<T> where T: Clone + Send + 'static {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.borrow_mut().push(Box::new(move |value: &T| sender.send(value.clone()).is_ok()));
+        receiver
+    }
+
     pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
         let value = f(&*self.inner.value.borrow());
         self.inner.as_observable.register();
         value
     }
 
+    /// Changes the value and makes everything that read it dirty. Inside a computation or a
+    /// `batch` the value changes right away, but the dirtiness is held back until it ends.
+    ///
+    /// # Panics
+    /// If called from inside `with` on the same signal.
     pub fn update(&self, f: impl FnOnce(&mut T)) {
-        f(&mut *self.inner.value.borrow_mut());
-        self.inner.as_observable.trigger();
+        let Ok(mut value) = self.inner.value.try_borrow_mut() else {
+            panic!("a signal was written from inside `with` on the same signal; read the value out first");
+        };
+        f(&mut value);
+        drop(value);
+        self.inner.as_observable.trigger_or_defer();
+        let mut subscribers = self.subscribers.borrow_mut();
+        if !subscribers.is_empty() {
+            let value = self.inner.value.borrow();
+            subscribers.retain(|send| send(&value));
+        }
     }
 
     // pub fn get_mut(&mut self) -> &mut T {
@@ -223,6 +441,83 @@ impl<T> ReadableSignal<T> for RwSignal<T> where T: Clone {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for RwSignal<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.value.borrow().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for RwSignal<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(RwSignal::new)
+    }
+}
+
+type Lens<A, T> = dyn for<'a> Fn(&'a A) -> &'a RwSignal<T>;
+
+/// A two-way binding between a widget and an `RwSignal` inside the model.
+///
+/// A `Bind` stands in for the getter/setter closure pairs widgets otherwise take, so a widget
+/// can both display and change a value from a single lens. See also the `bind!` macro.
+pub struct Bind<A, T> {
+    lens: Rc<Lens<A, T>>
+}
+
+impl<A: 'static, T: 'static> Bind<A, T> {
+    pub fn new(lens: impl for<'a> Fn(&'a A) -> &'a RwSignal<T> + 'static) -> Bind<A, T> {
+        Bind { lens: Rc::new(lens) }
+    }
+
+    pub fn signal<'a>(&self, model: &'a A) -> &'a RwSignal<T> {
+        (self.lens)(model)
+    }
+
+    pub fn set(&self, model: &A, value: T) {
+        self.signal(model).update(|current| *current = value);
+    }
+
+    pub fn update(&self, model: &A, f: impl FnOnce(&mut T)) {
+        self.signal(model).update(f);
+    }
+
+    /// A getter suitable for widgets which compute their contents from the model.
+    pub fn getter(&self) -> impl Fn(&mut A) -> T + 'static where T: Clone {
+        let bind = self.clone();
+        move |model| bind.signal(model).get()
+    }
+
+    /// A setter suitable for widgets which report changes to the model.
+    pub fn setter(&self) -> impl Fn(&mut A, T) + 'static {
+        let bind = self.clone();
+        move |model, value| bind.set(model, value)
+    }
+}
+
+impl<A, T> Clone for Bind<A, T> {
+    fn clone(&self) -> Self {
+        Bind { lens: Rc::clone(&self.lens) }
+    }
+}
+
+/// Creates a `Bind` to a signal field of the model, e.g. `bind!(Model => settings.volume)`.
+#[macro_export]
+macro_rules! bind {
+    ($model:ty => $($field:ident).+) => {
+        $crate::tracking::Bind::new(|model: &$model| &model $(.$field)+)
+    };
+}
+
+/// A plain struct with a counterpart whose fields are all signals, see `#[derive(Trackable)]`.
+pub trait Trackable {
+    type Tracked;
+
+    fn into_tracked(self) -> Self::Tracked;
+}
+
+pub use yoru_macros::Trackable;
+
 pub struct Computed<V> {
     as_observer: RefCell<Rc<ObserverInner>>,
     value: RefCell<V>,
@@ -234,6 +529,12 @@ impl<V: Default> Computed<V> {
     }
 }
 
+impl<V: Default> Default for Computed<V> {
+    fn default() -> Self {
+        Computed::new()
+    }
+}
+
 impl<V> Computed<V> {
     pub fn new_with_initial(initial: V) -> Computed<V> {
         Computed {
@@ -280,10 +581,21 @@ impl<T> ReadableSignal<T> for Computed<T> where T: Clone {
     }
 }
 
+#[derive(Copy, Clone)]
+enum RateLimit {
+    Throttle(Duration),
+    Debounce(Duration)
+}
+
 pub struct Derived<A, V> {
     as_observer: RefCell<Rc<ObserverInner>>,
     value: RefCell<V>,
-    compute: Box<dyn Fn(&mut A) -> V>
+    compute: Box<dyn Fn(&mut A) -> V>,
+
+    rate_limit: Option<RateLimit>,
+    last_run: Cell<Option<Instant>>,
+    // when an input last changed, if that change hasn't been recomputed yet
+    pending_since: Cell<Option<Instant>>
 }
 
 impl<A, V> Derived<A, V> where V: Default {
@@ -297,21 +609,92 @@ impl<A, V> Derived<A, V> {
         Derived {
             as_observer: RefCell::new(ObserverInner::new(Dirtiness::Dirty)),
             value: RefCell::new(initial),
-            compute: Box::new(compute)
+            compute: Box::new(compute),
+
+            rate_limit: None,
+            last_run: Cell::new(None),
+            pending_since: Cell::new(None)
         }
     }
 
+    /// Recomputes at most once per `interval`. Changes in between are picked up when the interval
+    /// is over, so the value lags its inputs by at most `interval`.
+    pub fn throttled(mut self, interval: Duration) -> Derived<A, V> {
+        self.rate_limit = Some(RateLimit::Throttle(interval));
+        self
+    }
+
+    /// Only recomputes once the inputs have stopped changing for `delay`.
+    pub fn debounced(mut self, delay: Duration) -> Derived<A, V> {
+        self.rate_limit = Some(RateLimit::Debounce(delay));
+        self
+    }
+
+    // whether to recompute now; a rate-limited computation which has to wait stops looking dirty
+    // until its wakeup, but stays registered with its inputs to notice further changes
+    fn should_update(&self) -> bool {
+        let observer = self.as_observer.borrow();
+        let (Some(rate_limit), Some(last_run)) = (self.rate_limit, self.last_run.get()) else {
+            return observer.is_dirty();
+        };
+
+        let now = Instant::now();
+        if observer.is_dirty() {
+            match rate_limit {
+                RateLimit::Throttle(_) => { self.pending_since.set(Some(self.pending_since.get().unwrap_or(now))); }
+                RateLimit::Debounce(_) => { self.pending_since.set(Some(now)); }
+            }
+        }
+        let Some(pending_since) = self.pending_since.get() else { return false; };
+        let ready_at = match rate_limit {
+            RateLimit::Throttle(interval) => last_run + interval,
+            RateLimit::Debounce(delay) => pending_since + delay
+        };
+        if ready_at <= now {
+            return true;
+        }
+        observer.is_dirty.set(Dirtiness::Clean);
+        observer.direct.set(false);
+        wake_at(ready_at, &observer);
+        false
+    }
+
+    fn run(&self, model: &mut A) -> V {
+        self.as_observer.borrow().mark_dirty();
+        let (observer, value) = ObserverInner::run_and_track(|| (self.compute)(model));
+        *self.as_observer.borrow_mut() = observer;
+        self.last_run.set(Some(Instant::now()));
+        self.pending_since.set(None);
+        value
+    }
+
     pub fn maybe_update(&self, model: &mut A) -> bool {
-        if self.as_observer.borrow().is_dirty() {
-            self.as_observer.borrow().mark_dirty();
-            let (observer, value) = ObserverInner::run_and_track(|| (self.compute)(model));
+        if self.should_update() {
+            let value = self.run(model);
             *self.value.borrow_mut() = value;
-            *self.as_observer.borrow_mut() = observer;
             true
         } else {
             false
         }
     }
+
+    /// Like `maybe_update`, but only returns true if the recomputed value differs from the old one.
+    pub fn maybe_update_if_changed(&self, model: &mut A) -> bool where V: PartialEq {
+        if self.should_update() {
+            let value = self.run(model);
+            let changed = *self.value.borrow() != value;
+            if changed {
+                *self.value.borrow_mut() = value;
+            }
+            changed
+        } else {
+            false
+        }
+    }
+
+    pub fn with_untracked<O>(&self, f: impl FnOnce(&V) -> O) -> O {
+        f(&*self.value.borrow())
+    }
 }
 
 impl<A, T> ReadableSignal<T> for Derived<A, T> where T: Clone {
@@ -330,6 +713,428 @@ impl<A, T> ReadableSignal<T> for Derived<A, T> where T: Clone {
 }
 
 
+/// A signal which follows `source` while `enabled` is true and keeps its last value otherwise.
+///
+/// While the gate is closed it doesn't depend on `source` at all, so changes to it don't make
+/// anything downstream dirty; e.g. a disabled form or a paused live view stops being relaid out
+/// by background updates. Opening the gate catches up with the current value of `source`.
+pub struct Gate<S, E, T> {
+    source: S,
+    enabled: E,
+    as_observer: RefCell<Rc<ObserverInner>>,
+    value: RefCell<T>
+}
+
+impl<S, E, T> Gate<S, E, T> where S: ReadableSignal<T>, E: ReadableSignal<bool> {
+    pub fn new(source: S, enabled: E) -> Gate<S, E, T> {
+        Gate {
+            value: RefCell::new(source.get_untracked()),
+            source,
+            enabled,
+            as_observer: RefCell::new(ObserverInner::new(Dirtiness::Dirty))
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.enabled.get_untracked()
+    }
+
+    fn maybe_update(&self) {
+        if self.as_observer.borrow().is_dirty() {
+            self.as_observer.borrow().mark_dirty();
+            // only read the source while open, so that a closed gate isn't registered with it
+            let (observer, value) = ObserverInner::run_and_track(|| self.enabled.get().then(|| self.source.get()));
+            if let Some(value) = value {
+                *self.value.borrow_mut() = value;
+            }
+            *self.as_observer.borrow_mut() = observer;
+        }
+    }
+}
+
+impl<S, E, T> ReadableSignal<T> for Gate<S, E, T> where S: ReadableSignal<T>, E: ReadableSignal<bool>, T: Clone {
+    fn get(&self) -> T {
+        self.maybe_update();
+        self.as_observer.borrow().as_observable.register();
+        self.value.borrow().clone()
+    }
+
+    fn get_untracked(&self) -> T {
+        self.maybe_update();
+        self.value.borrow().clone()
+    }
+
+    fn track(&self) {
+        self.as_observer.borrow().as_observable.register();
+    }
+}
+
+
+trait MemoNode {
+    fn recompute(&self);
+}
+
+struct MemoInner<T> {
+    as_observer: RefCell<Rc<ObserverInner>>,
+    // what dependents register with; only triggered when the value actually changes
+    as_observable: ObservableInner,
+    value: RefCell<T>,
+    compute: Box<dyn Fn() -> T>,
+    this: Weak<MemoInner<T>>
+}
+
+impl<T: PartialEq + 'static> MemoInner<T> {
+    fn run(&self) -> T {
+        let (observer, value) = ObserverInner::run_and_track(|| (self.compute)());
+        *observer.memo.borrow_mut() = Some(self.this.clone() as Weak<dyn MemoNode>);
+        let written_while_computing = observer.is_dirty();
+        *self.as_observer.borrow_mut() = observer;
+        if written_while_computing {
+            self.as_observer.borrow().queue_memo();
+        }
+        value
+    }
+}
+
+impl<T: PartialEq + 'static> MemoNode for MemoInner<T> {
+    fn recompute(&self) {
+        if !self.as_observer.borrow().is_dirty() {
+            return;
+        }
+        let value = self.run();
+        if *self.value.borrow() != value {
+            *self.value.borrow_mut() = value;
+            self.as_observable.trigger_or_defer();
+        }
+    }
+}
+
+/// A value computed from other signals which only makes its dependents dirty when it changes.
+///
+/// A `Computed` makes everything downstream dirty as soon as one of its inputs changes, even if
+/// recomputing it gives the same value. A memo instead recomputes right after its inputs change
+/// and compares the result with the old value, so e.g. a memo of `count > 0` doesn't relayout
+/// anything while `count` goes from 3 to 4. Clones share the same value.
+pub struct Memo<T> {
+    inner: Rc<MemoInner<T>>
+}
+
+impl<T: PartialEq + 'static> Memo<T> {
+    pub fn new(compute: impl Fn() -> T + 'static) -> Memo<T> {
+        let inner = Rc::new_cyclic(|this| {
+            let (observer, value) = ObserverInner::run_and_track(&compute);
+            *observer.memo.borrow_mut() = Some(this.clone() as Weak<dyn MemoNode>);
+            MemoInner {
+                as_observer: RefCell::new(observer),
+                as_observable: ObservableInner::new(),
+                value: RefCell::new(value),
+                compute: Box::new(compute),
+                this: this.clone()
+            }
+        });
+        Memo { inner }
+    }
+}
+
+impl<T> Clone for Memo<T> {
+    fn clone(&self) -> Self {
+        Memo { inner: Rc::clone(&self.inner) }
+    }
+}
+
+impl<T> ReadableSignal<T> for Memo<T> where T: Clone {
+    fn get(&self) -> T {
+        self.inner.as_observable.register();
+        self.inner.value.borrow().clone()
+    }
+
+    fn get_untracked(&self) -> T {
+        self.inner.value.borrow().clone()
+    }
+
+    fn track(&self) {
+        self.inner.as_observable.register();
+    }
+}
+
+
+/// A value which `AnimatedSignal` can move between smoothly.
+pub trait Animatable: Clone {
+    /// The value `t` of the way from `self` to `other`, where `t` is usually, but not always,
+    /// between 0 and 1.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn interpolate(&self, other: &f32, t: f32) -> f32 {
+        self + (other - self) * t
+    }
+}
+
+impl Animatable for f64 {
+    fn interpolate(&self, other: &f64, t: f32) -> f64 {
+        self + (other - self) * t as f64
+    }
+}
+
+impl Animatable for crate::Color {
+    fn interpolate(&self, other: &crate::Color, t: f32) -> crate::Color {
+        self.lerp(*other, t)
+    }
+}
+
+impl Animatable for crate::math::Point {
+    fn interpolate(&self, other: &crate::math::Point, t: f32) -> crate::math::Point {
+        crate::math::Point::new(self.x.interpolate(&other.x, t), self.y.interpolate(&other.y, t))
+    }
+}
+
+impl Animatable for crate::math::Size {
+    fn interpolate(&self, other: &crate::math::Size, t: f32) -> crate::math::Size {
+        crate::math::Size::new(self.width().interpolate(&other.width(), t), self.height().interpolate(&other.height(), t))
+    }
+}
+
+/// How an animation's progress is spread over its duration.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum Easing {
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down towards the end.
+    EaseOut,
+    /// Starts and ends slowly.
+    #[default]
+    EaseInOut,
+    /// Maps the elapsed fraction of the duration to the fraction of the way travelled.
+    Custom(fn(f32) -> f32)
+}
+
+impl Easing {
+    /// The fraction of the way travelled once `t` of the duration has elapsed.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+            Easing::Custom(curve) => curve(t)
+        }
+    }
+}
+
+// how often an animation which is being read makes its dependents dirty
+const ANIMATION_FRAME: Duration = Duration::from_millis(16);
+
+struct Transition<T> {
+    from: T,
+    to: T,
+    start: Instant
+}
+
+/// A signal which moves to new values gradually, over a duration and along an easing curve.
+///
+/// Reading it while an animation runs gives the value for the current time, and schedules a
+/// wakeup so that whatever read it is recomputed, and redrawn, a frame later; once the animation
+/// reaches its target, nothing is scheduled any more. Bind a widget's color, size or offset to
+/// `get` to animate it, e.g. `div.bind_background_color(|model| Some(model.color.get()))`.
+///
+/// The application polls the wakeups by itself; code that drives a tree itself should call
+/// `poll_wakeups`.
+pub struct AnimatedSignal<T> {
+    as_observer: Rc<ObserverInner>,
+    transition: RefCell<Transition<T>>,
+    duration: Cell<Duration>,
+    easing: Cell<Easing>,
+    // the wakeup already scheduled for the next frame, if any
+    next_frame: Cell<Option<Instant>>
+}
+
+impl<T: Animatable> AnimatedSignal<T> {
+    /// Starts at `value`, animating changes over 200 ms with `Easing::EaseInOut`.
+    pub fn new(value: T) -> AnimatedSignal<T> {
+        AnimatedSignal {
+            as_observer: ObserverInner::new(Dirtiness::Clean),
+            transition: RefCell::new(Transition { from: value.clone(), to: value, start: Instant::now() }),
+            duration: Cell::new(Duration::from_millis(200)),
+            easing: Cell::new(Easing::default()),
+            next_frame: Cell::new(None)
+        }
+    }
+
+    pub fn with_duration(self, duration: Duration) -> AnimatedSignal<T> {
+        self.duration.set(duration);
+        self
+    }
+
+    pub fn with_easing(self, easing: Easing) -> AnimatedSignal<T> {
+        self.easing.set(easing);
+        self
+    }
+
+    /// Starts moving towards `target` from wherever the signal is now, interrupting any
+    /// animation which is still running.
+    pub fn animate_to(&self, target: T) {
+        let now = Instant::now();
+        let from = self.value_at(now);
+        *self.transition.borrow_mut() = Transition { from, to: target, start: now };
+        self.as_observer.as_observable.trigger_or_defer();
+    }
+
+    /// Jumps to `value` without animating.
+    pub fn set(&self, value: T) {
+        *self.transition.borrow_mut() = Transition { from: value.clone(), to: value, start: Instant::now() };
+        self.as_observer.as_observable.trigger_or_defer();
+    }
+
+    /// The value the signal is moving towards, or is at once it settled.
+    pub fn target(&self) -> T {
+        self.transition.borrow().to.clone()
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.progress(Instant::now()) < 1.0
+    }
+
+    // the elapsed fraction of the current animation
+    fn progress(&self, now: Instant) -> f32 {
+        let duration = self.duration.get();
+        let elapsed = now.saturating_duration_since(self.transition.borrow().start);
+        if elapsed >= duration {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / duration.as_secs_f32()
+        }
+    }
+
+    fn value_at(&self, now: Instant) -> T {
+        let progress = self.progress(now);
+        let transition = self.transition.borrow();
+        if progress >= 1.0 {
+            transition.to.clone()
+        } else {
+            transition.from.interpolate(&transition.to, self.easing.get().apply(progress))
+        }
+    }
+
+    // registers the reader, and makes sure it is woken for the next frame while animating
+    fn register(&self, now: Instant) {
+        self.as_observer.as_observable.register();
+        if self.progress(now) >= 1.0 || self.next_frame.get().is_some_and(|frame| frame > now) {
+            return;
+        }
+        let end = self.transition.borrow().start + self.duration.get();
+        let frame = (now + ANIMATION_FRAME).min(end);
+        self.next_frame.set(Some(frame));
+        wake_at(frame, &self.as_observer);
+    }
+}
+
+impl<T: Animatable> ReadableSignal<T> for AnimatedSignal<T> {
+    fn get(&self) -> T {
+        let now = Instant::now();
+        self.register(now);
+        self.value_at(now)
+    }
+
+    fn get_untracked(&self) -> T {
+        self.value_at(Instant::now())
+    }
+
+    fn track(&self) {
+        self.register(Instant::now());
+    }
+}
+
+/// How a `Watch` changed since it last ran.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum WatchState {
+    Clean,
+    /// Only through the watches run inside it.
+    Nested,
+    /// Through something it read itself, and maybe through nested watches too.
+    Own
+}
+
+// tracks one pass over an element of the tree, such as its update or layout, and tells the
+// changes to what the element read itself apart from those which only reached it through the
+// watches of the elements inside it
+pub(crate) struct Watch {
+    observer: RefCell<Rc<ObserverInner>>
+}
+
+impl Watch {
+    pub fn new() -> Watch {
+        Watch { observer: RefCell::new(ObserverInner::new(Dirtiness::Dirty)) }
+    }
+
+    // runs `f`, tracking what it reads, and makes whatever is being computed around it depend on
+    // the watch
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let (observer, value) = ObserverInner::run_and_track_as(true, f);
+        observer.as_observable.register();
+        *self.observer.borrow_mut() = observer;
+        value
+    }
+
+    // like `run`, but keeps tracking into the same observer until something it read changes, so
+    // that everything which runs `f` in between depends on the same watch
+    pub fn run_shared<T>(&self, f: impl FnOnce() -> T) -> T {
+        let observer = Rc::clone(&self.observer.borrow());
+        if observer.is_dirty() {
+            return self.run(f);
+        }
+        let value = ObserverInner::track_into(&observer, f);
+        observer.as_observable.register();
+        value
+    }
+
+    pub fn state(&self) -> WatchState {
+        let observer = self.observer.borrow();
+        match (observer.is_dirty(), observer.direct.get()) {
+            (false, _) => WatchState::Clean,
+            (true, false) => WatchState::Nested,
+            (true, true) => WatchState::Own
+        }
+    }
+}
+
+// a flag which turns on once a deadline passes, making whatever read it dirty at that point
+pub(crate) struct Alarm {
+    as_observer: Rc<ObserverInner>,
+    deadline: Cell<Option<Instant>>
+}
+
+impl Alarm {
+    pub fn new() -> Alarm {
+        Alarm { as_observer: ObserverInner::new(Dirtiness::Clean), deadline: Cell::new(None) }
+    }
+
+    /// Goes off at `deadline`, or never if it is `None`.
+    pub fn set(&self, deadline: Option<Instant>) {
+        let was_due = self.is_due_untracked();
+        self.deadline.set(deadline);
+        if let Some(deadline) = deadline {
+            wake_at(deadline, &self.as_observer);
+        }
+        if was_due != self.is_due_untracked() {
+            self.as_observer.as_observable.trigger_or_defer();
+        }
+    }
+
+    /// Whether the alarm went off, read tracked.
+    pub fn is_due(&self) -> bool {
+        self.as_observer.as_observable.register();
+        self.is_due_untracked()
+    }
+
+    fn is_due_untracked(&self) -> bool {
+        self.deadline.get().is_some_and(|deadline| deadline <= Instant::now())
+    }
+}
+
 pub struct Computed2<I, V> {
     as_observer: RefCell<Rc<ObserverInner>>,
     input: RefCell<I>,
@@ -365,6 +1170,18 @@ impl<I, V> Computed2<I, V> where for<'a> &'a I: PartialEq {
             *self.value.borrow_mut() = value;
         }
     }
+
+    /// Whether a signal read by the last computation has changed since. A different input also
+    /// causes a recomputation, but isn't reflected here.
+    pub fn is_dirty(&self) -> bool {
+        self.as_observer.borrow().is_dirty()
+    }
+}
+
+impl<I, V> Default for Computed2<I, V> where for<'a> &'a I: PartialEq, I: Default, V: Default {
+    fn default() -> Self {
+        Computed2::new()
+    }
 }
 
 impl<I, V> ReadableSignal<V> for Computed2<I, V> where V: Clone {
@@ -408,6 +1225,10 @@ impl<T> TrackedVec<T> {
         self.inner.with(|items| f(items))
     }
 
+    pub fn with_untracked<O>(&self, f: impl FnOnce(&[T]) -> O) -> O {
+        f(&self.inner.inner.value.borrow())
+    }
+
     pub fn with_mut_untracked<O>(&mut self, f: impl FnOnce(&mut [T]) -> O) -> O {
         f(self.inner.inner.value.borrow_mut().as_mut_slice())
     }
@@ -415,4 +1236,38 @@ impl<T> TrackedVec<T> {
     pub fn push(&mut self, item: T) {
         self.inner.update(|items| items.push(item));
     }
+
+    pub fn insert(&mut self, index: usize, item: T) {
+        self.inner.update(|items| items.insert(index, item));
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        let mut removed = None;
+        self.inner.update(|items| removed = Some(items.remove(index)));
+        removed.unwrap()
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&T) -> bool) {
+        self.inner.update(|items| items.retain(f));
+    }
+}
+
+impl<T> Default for TrackedVec<T> {
+    fn default() -> Self {
+        TrackedVec::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for TrackedVec<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.inner.value.borrow().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for TrackedVec<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(|items| TrackedVec { inner: RwSignal::new(items) })
+    }
 }
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use tray_icon::menu::{Menu, MenuEvent, MenuId, MenuItem};
+use tray_icon::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use winit::event_loop::EventLoopProxy;
+
+use crate::error::Result;
+
+
+type TrayAction<A> = Box<dyn Fn(&mut A)>;
+
+enum TrayEvent {
+    Icon(TrayIconEvent),
+    Menu(MenuEvent)
+}
+
+struct ActiveTray<A> {
+    _icon: tray_icon::TrayIcon,
+    actions: HashMap<MenuId, TrayAction<A>>,
+    events: Receiver<TrayEvent>
+}
+
+/// A system tray icon with an optional tooltip and context menu.
+///
+/// Clicks on the icon and activated menu items are routed to the model like any other interaction.
+/// The icon is created once the application resumes, see `Application::with_tray`.
+pub struct Tray<A> {
+    icon: Option<tray_icon::Icon>,
+    tooltip: Option<String>,
+    items: Vec<(String, TrayAction<A>)>,
+    on_click: Option<TrayAction<A>>,
+
+    active: Option<ActiveTray<A>>
+}
+
+impl<A> Tray<A> {
+    pub fn new() -> Tray<A> {
+        Tray {
+            icon: None,
+            tooltip: None,
+            items: Vec::new(),
+            on_click: None,
+            active: None
+        }
+    }
+
+    pub fn with_icon_rgba(mut self, rgba: Vec<u8>, width: u32, height: u32) -> Result<Tray<A>> {
+        self.icon = Some(tray_icon::Icon::from_rgba(rgba, width, height)?);
+        Ok(self)
+    }
+
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Tray<A> {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    pub fn with_item(mut self, label: impl Into<String>, action: impl Fn(&mut A) + 'static) -> Tray<A> {
+        self.items.push((label.into(), Box::new(action)));
+        self
+    }
+
+    pub fn on_click(mut self, action: impl Fn(&mut A) + 'static) -> Tray<A> {
+        self.on_click = Some(Box::new(action));
+        self
+    }
+
+    pub(crate) fn activate(&mut self, proxy: &EventLoopProxy<()>) -> Result<()> {
+        if self.active.is_some() {
+            return Ok(());
+        }
+
+        let menu = Menu::new();
+        let mut actions = HashMap::new();
+        for (label, action) in self.items.drain(..) {
+            let item = MenuItem::new(label, true, None);
+            menu.append(&item)?;
+            actions.insert(item.id().clone(), action);
+        }
+
+        let mut builder = TrayIconBuilder::new().with_menu(Box::new(menu));
+        if let Some(icon) = self.icon.take() {
+            builder = builder.with_icon(icon);
+        }
+        if let Some(tooltip) = &self.tooltip {
+            builder = builder.with_tooltip(tooltip);
+        }
+        let icon = builder.build()?;
+
+        let (sender, events) = mpsc::channel();
+        TrayIconEvent::set_event_handler(Some(forward(sender.clone(), proxy.clone(), TrayEvent::Icon)));
+        MenuEvent::set_event_handler(Some(forward(sender, proxy.clone(), TrayEvent::Menu)));
+
+        self.active = Some(ActiveTray { _icon: icon, actions, events });
+        Ok(())
+    }
+
+    /// Runs the actions for every tray event received since the last poll, returning whether any
+    /// action ran.
+    pub(crate) fn poll(&self, model: &mut A) -> bool {
+        let Some(active) = &self.active else { return false; };
+
+        let mut handled = false;
+        while let Ok(event) = active.events.try_recv() {
+            let action = match event {
+                TrayEvent::Icon(TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. }) => {
+                    self.on_click.as_ref()
+                }
+                TrayEvent::Icon(_) => None,
+                TrayEvent::Menu(event) => active.actions.get(&event.id)
+            };
+            if let Some(action) = action {
+                action(model);
+                handled = true;
+            }
+        }
+        handled
+    }
+}
+
+impl<A> Default for Tray<A> {
+    fn default() -> Self {
+        Tray::new()
+    }
+}
+
+// the handlers are called from whichever thread the platform delivers tray events on, so forward
+// them over a channel and wake the event loop to process them
+fn forward<E: 'static>(sender: Sender<TrayEvent>, proxy: EventLoopProxy<()>, wrap: fn(E) -> TrayEvent) -> impl Fn(E) + Send + Sync + 'static {
+    let forward = Mutex::new((sender, proxy));
+    move |event| {
+        let forward = forward.lock().unwrap();
+        if forward.0.send(wrap(event)).is_ok() {
+            let _ = forward.1.send_event(());
+        }
+    }
+}
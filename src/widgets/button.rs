@@ -1,26 +1,52 @@
-use crate::style::{Color, Direction, LayoutStyle, Justify, Sizing, ContainerLayoutStyle};
-use crate::layout::{LayoutCharacteristics, Layout, PrelayoutInput, LayoutInput};
-use crate::{Element, Label, layout, math, RenderContext};
-use crate::interact::{Interaction, InteractSet};
+use crate::style::{Color, Direction, LayoutStyle, Justify, Sizing, ContainerLayoutStyle, PointerEvents, Position, Shadow, StyleSheet, WidgetState, Wrap};
+use crate::layout::{LayoutCharacteristics, Layout, LayoutContext, PrelayoutInput, LayoutInput, TextMetrics};
+use crate::{Element, layout, math, RenderContext};
+#[cfg(feature = "text")]
+use crate::Label;
+use crate::focus::{self, FocusId};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
 use crate::math::{Axis};
-use crate::tracking::{Computed, Computed2, ReadableSignal};
-use crate::widgets::div::to_tiny_skia_path;
+use crate::print::BreakHint;
+use crate::shortcut::{Key, NamedKey};
+use crate::tracking::{Bind, Computed, Computed2, Derived, ReadableSignal};
+use crate::widgets::div::{draw_shadow, fill_rounded_rect, rounded_contains, shadow_outset, stroke_border};
+use crate::widgets::modifier::draw_layer;
+use crate::widgets::state::StateTracker;
 use crate::widgets::Widget;
 
 
+type Callback<A> = Box<dyn Fn(&mut A)>;
+type HoverCallback<A> = Box<dyn Fn(&mut A, bool)>;
+
 pub struct Button<A> {
     style: ContainerLayoutStyle,
 
-    prelayout_cache: Computed2<PrelayoutInput, math::Size>,
+    prelayout_cache: Computed2<PrelayoutInput, (math::Size, Option<TextMetrics>)>,
     layout_cache: Computed2<LayoutInput, Layout>,
     interactions: Computed<InteractSet>,
 
     inner: Element<A>,
-    on_click: Box<dyn Fn(&mut A)>
+    on_click: Callback<A>,
+    on_press: Option<Callback<A>>,
+    on_release: Option<Callback<A>>,
+    on_hover_change: Option<HoverCallback<A>>,
+    pressed: bool,
+    hovered: bool,
+    // of the outer edge of the border, in logical pixels
+    border_radius: f32,
+    background: Option<Derived<A, Option<Color>>>,
+    border_color: Option<Derived<A, Option<Color>>>,
+    shadow: Option<Shadow>,
+    style_sheet: StyleSheet,
+    state: StateTracker<A>,
+    focus_id: Option<FocusId>,
+    #[cfg(feature = "sound")]
+    cue: Option<crate::sound::Cue>
 }
 
 impl<A: 'static> Button<A> {
-    pub fn new(inner: Label<A>, on_click: impl Fn(&mut A) + 'static) -> Button<A> {
+    /// A button showing `inner`, e.g. an icon or a row of an icon and a label.
+    pub fn new(inner: impl Into<Element<A>>, on_click: impl Fn(&mut A) + 'static) -> Button<A> {
         let layout_style = ContainerLayoutStyle {
             layout_style: LayoutStyle {
                 border_size: 2.0,
@@ -28,11 +54,15 @@ impl<A: 'static> Button<A> {
                 margin: 1.0.into(),
                 width: Sizing::Fit,
                 height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
             },
             main_axis: Axis::Vertical,
             main_direction: Direction::Positive,
             main_justify: Justify::Center,
-            cross_justify: Justify::Center
+            cross_justify: Justify::Center,
+            wrap: Wrap::NoWrap,
+            gap: 0.0
         };
 
         Button {
@@ -43,41 +73,177 @@ impl<A: 'static> Button<A> {
             interactions: Computed::new(),
 
             inner: inner.into(),
-            on_click: Box::new(on_click)
+            on_click: Box::new(on_click),
+            on_press: None,
+            on_release: None,
+            on_hover_change: None,
+            pressed: false,
+            hovered: false,
+            border_radius: 0.0,
+            background: None,
+            border_color: None,
+            shadow: None,
+            style_sheet: StyleSheet::new(),
+            state: StateTracker::new(),
+            focus_id: None,
+            #[cfg(feature = "sound")]
+            cue: None
         }
     }
+
+    /// A button showing a fixed text.
+    #[cfg(feature = "text")]
+    pub fn text(text: impl Into<String>, on_click: impl Fn(&mut A) + 'static) -> Button<A> {
+        let text = text.into();
+        Button::new(Label::new(move |_| text.clone()), on_click)
+    }
+
+    /// A button which changes a bound signal with `on_click` when clicked.
+    pub fn bind<T: 'static>(inner: impl Into<Element<A>>, bind: Bind<A, T>, on_click: impl Fn(&mut T) + 'static) -> Button<A> {
+        Button::new(inner, move |model| bind.update(model, &on_click))
+    }
+}
+
+impl<A> Button<A> {
+    /// Rounds the corners of the button by `radius` logical pixels. Clicks in the cut-off corners
+    /// miss the button.
+    pub fn with_border_radius(mut self, radius: f32) -> Button<A> {
+        self.border_radius = radius.max(0.0);
+        self
+    }
+
+    /// Draws a shadow beneath the button.
+    pub fn with_shadow(mut self, shadow: Shadow) -> Button<A> {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    /// Overrides how the button is drawn in each state. Without one, the button darkens a little
+    /// while hovered and more while pressed, and is drawn half transparent while disabled.
+    pub fn with_style_sheet(mut self, style_sheet: StyleSheet) -> Button<A> {
+        self.style_sheet = style_sheet;
+        self
+    }
+
+    /// Ignores clicks and draws the button as disabled while `disabled` returns true.
+    pub fn bind_disabled(mut self, disabled: impl Fn(&mut A) -> bool + 'static) -> Button<A> {
+        self.state.bind_disabled(disabled);
+        self
+    }
+
+    /// Computes the background from the model instead of using the theme's surface color. It is
+    /// still darkened while hovered or pressed, and `None` falls back to the theme.
+    pub fn bind_background_color(&mut self, color: impl Fn(&mut A) -> Option<Color> + 'static) {
+        self.background = Some(Derived::new(color));
+    }
+
+    /// Computes the border color from the model instead of using the theme's foreground color.
+    /// `None` hides the border, but it still takes up space.
+    pub fn bind_border_color(&mut self, color: impl Fn(&mut A) -> Option<Color> + 'static) {
+        self.border_color = Some(Derived::new(color));
+    }
+
+    /// Lets the button hold the keyboard focus under `id`. While focused, Enter and Space click it.
+    pub fn with_focus_id(mut self, id: FocusId) -> Button<A> {
+        self.focus_id = Some(id);
+        self
+    }
+
+    /// Plays `cue` whenever the button is clicked.
+    #[cfg(feature = "sound")]
+    pub fn with_cue(mut self, cue: crate::sound::Cue) -> Button<A> {
+        self.cue = Some(cue);
+        self
+    }
+
+    /// Calls `handler` when the primary button goes down on the button, e.g. to start repeating
+    /// an action while it is held.
+    pub fn on_press(mut self, handler: impl Fn(&mut A) + 'static) -> Button<A> {
+        self.on_press = Some(Box::new(handler));
+        self
+    }
+
+    /// Calls `handler` when the primary button goes up after a press on the button, wherever the
+    /// pointer is by then. `on_click` only follows if it is still over the button.
+    pub fn on_release(mut self, handler: impl Fn(&mut A) + 'static) -> Button<A> {
+        self.on_release = Some(Box::new(handler));
+        self
+    }
+
+    /// Calls `handler` with `true` when the pointer moves onto the button and `false` when it
+    /// leaves.
+    pub fn on_hover_change(mut self, handler: impl Fn(&mut A, bool) + 'static) -> Button<A> {
+        self.on_hover_change = Some(Box::new(handler));
+        self
+    }
+
+    pub fn set_pointer_events(&mut self, pointer_events: PointerEvents) {
+        self.style.layout_style.pointer_events = pointer_events;
+        self.interactions.invalidate();
+    }
 }
 
 impl<A> Widget<A> for Button<A> {
     fn update(&self, model: &mut A) {
+        // the state and bound colors only change how the button is drawn, but they need a redraw
+        self.state.update(model);
+        for color in self.background.iter().chain(&self.border_color) {
+            color.maybe_update_if_changed(model);
+            color.track();
+        }
         self.inner.update(model)
     }
 
-    fn prelayout(&self, input: PrelayoutInput) -> LayoutCharacteristics {
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
         self.prelayout_cache.maybe_update(input, |&input| {
-            let characteristics = layout::container::do_prelayout(&self.style, input, std::slice::from_ref(&self.inner));
-            characteristics.min_size
+            let characteristics = layout::container::do_prelayout(&self.style, ctx, input, std::slice::from_ref(&self.inner));
+            (characteristics.min_size, characteristics.text_metrics)
         });
-        LayoutCharacteristics { layout_style: &self.style.layout_style, min_size: self.prelayout_cache.get() }
+        let (min_size, text_metrics) = self.prelayout_cache.get();
+        LayoutCharacteristics { layout_style: self.style.layout_style, min_size, text_metrics }
     }
 
-    fn layout(&self, input: LayoutInput) {
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
         self.layout_cache.maybe_update(input, |&input| {
             self.prelayout_cache.track();
-            let children_layout = layout::container::do_layout(&self.style, input, std::slice::from_ref(&self.inner));
-            self.inner.layout(children_layout[0]);
+            let children_layout = layout::container::do_layout(&self.style, ctx, input, std::slice::from_ref(&self.inner));
+            self.inner.layout(ctx, children_layout[0]);
             Layout::from_layout_input(&self.style.layout_style, input)
         });
         self.layout_cache.track();
     }
 
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn paint_outset(&self) -> math::SizeRect {
+        let scale_factor = self.layout_cache.get_untracked().scale_factor;
+        self.shadow.map_or(0.0.into(), |shadow| shadow_outset(shadow, scale_factor))
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner)
+    }
+
+    fn focus_id(&self) -> Option<FocusId> {
+        self.focus_id
+    }
+
     fn interactions(&self) -> InteractSet {
         self.interactions.maybe_update(|| {
+            if self.style.layout_style.pointer_events == PointerEvents::None {
+                return InteractSet::empty();
+            }
             let set = self.inner.interactions();
-            let this_set = InteractSet {
-                click: true,
-                click_area: self.layout_cache.get().border_box
-            };
+            let border_box = self.layout_cache.get().border_box;
+            let mut this_set = InteractSet::empty()
+                .with(Capability::Click, border_box)
+                .with(Capability::Hover, border_box)
+                .with(Capability::Drag, border_box);
+            if self.focus_id.is_some() {
+                this_set = this_set.with(Capability::Keyboard, border_box);
+            }
             this_set | set
         });
         self.interactions.get()
@@ -85,13 +251,54 @@ impl<A> Widget<A> for Button<A> {
 
     fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
         if self.interactions.get_untracked().accepts(interaction) {
-            match interaction {
-                Interaction::Click(point) => {
-                    let layout = self.layout_cache.get_untracked();
-                    if layout.border_box.contains(*point) {
-                        (self.on_click)(model);
+            let layout = self.layout_cache.get_untracked();
+            let radius = self.border_radius * layout.scale_factor;
+            let contains = |point| rounded_contains(layout.border_box, radius, point);
+            self.state.handle_interaction(interaction, contains, |focus| self.focus_id == Some(focus));
+
+            match *interaction {
+                Interaction::Hover(point) => {
+                    let hovered = point.is_some_and(contains);
+                    if hovered != self.hovered {
+                        self.hovered = hovered;
+                        if let Some(on_hover_change) = &self.on_hover_change {
+                            on_hover_change(model, hovered);
+                        }
+                    }
+                }
+                Interaction::Press(point) if contains(point) && !self.state.is_disabled() => {
+                    self.pressed = true;
+                    if let Some(on_press) = &self.on_press {
+                        on_press(model);
+                    }
+                }
+                Interaction::Release(_) if self.pressed => {
+                    self.pressed = false;
+                    if let Some(on_release) = &self.on_release {
+                        on_release(model);
                     }
                 }
+                _ => {}
+            }
+
+            let clicked = match interaction {
+                &Interaction::Click(point) => contains(point),
+                Interaction::KeyDown(input, target) => {
+                    Some(*target) == self.focus_id
+                        && matches!(input.logical, Key::Named(NamedKey::Enter) | Key::Character(' '))
+                }
+                _ => false
+            };
+            if clicked && !self.state.is_disabled() {
+                if let Interaction::KeyDown(..) = interaction {
+                    focus::mark_key_used();
+                }
+                #[cfg(feature = "sound")]
+                if let Some(cue) = self.cue {
+                    // a sound which can't be played isn't worth failing the click over
+                    let _ = crate::sound::play(cue);
+                }
+                (self.on_click)(model);
             }
 
             self.inner.handle_interaction(interaction, model);
@@ -99,27 +306,63 @@ impl<A> Widget<A> for Button<A> {
     }
 
     fn draw(&mut self, context: &mut RenderContext) {
-        let layout = self.layout_cache.get_untracked();
-        let border_size = self.style.layout_style.border_size * layout.scale_factor;
-        if let Some(border_color) = Some(Color::BLACK) {
-            if border_size > 0.0 {
-                let border_box = layout.half_border_box;
-                let path = to_tiny_skia_path(kurbo::Rect::from(border_box));
-                let mut stroke = tiny_skia::Stroke::default();
-                stroke.width = border_size;
-                let mut paint = tiny_skia::Paint::default();
-                paint.set_color(border_color.into());
-                context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
-            }
+        let state = self.state.get();
+        let default_opacity = (state == WidgetState::Disabled).then_some(0.5);
+        match self.style_sheet.resolve(state).opacity.or(default_opacity) {
+            Some(opacity) => draw_layer(context, opacity, None, |context| self.draw_contents(context)),
+            None => self.draw_contents(context)
         }
+    }
 
-        if let Some(background) = Some(Color::LIGHT_GRAY) {
-            let padding_box = layout.padding_box;
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints);
+    }
 
-            let mut paint = tiny_skia::Paint::default();
-            paint.set_color(background.into());
-            context.canvas.fill_rect(padding_box.into(), &paint, tiny_skia::Transform::identity(), None);
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        let layout = self.layout_cache.get_untracked();
+        let over = rounded_contains(layout.border_box, self.border_radius * layout.scale_factor, point);
+        if !over || self.style.layout_style.pointer_events == PointerEvents::None {
+            return None;
         }
+        Some(if self.state.is_disabled() { Cursor::NotAllowed } else { Cursor::Pointer })
+    }
+}
+
+impl<A> Button<A> {
+    fn draw_contents(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let border_size = self.style.layout_style.border_size * layout.scale_factor;
+        let radius = self.border_radius * layout.scale_factor;
+        let theme = *context.theme;
+        let state = self.state.get();
+        let patch = self.style_sheet.resolve(state);
+        if let Some(shadow) = self.shadow {
+            draw_shadow(context, layout.border_box, radius, shadow, layout.scale_factor);
+        }
+        match patch.border {
+            // the border box stays where layout put it, so a patched border is drawn inside it
+            Some((color, width)) if width > 0.0 => {
+                let width = width * layout.scale_factor;
+                stroke_border(context, layout.border_box.shrink_by((0.5 * width).into()), radius, width, color);
+            }
+            Some(_) => {}
+            None => {
+                let border_color = match &self.border_color {
+                    Some(color) => color.get_untracked(),
+                    None => Some(theme.foreground)
+                };
+                if let Some(border_color) = border_color.filter(|_| border_size > 0.0) {
+                    stroke_border(context, layout.half_border_box, radius, border_size, border_color);
+                }
+            }
+        }
+        let surface = self.background.as_ref().and_then(|color| color.get_untracked()).unwrap_or(theme.surface);
+        let background = patch.background.unwrap_or_else(|| match state {
+            WidgetState::Hovered => surface.lerp(theme.foreground, 0.1),
+            WidgetState::Pressed => surface.lerp(theme.foreground, 0.2),
+            _ => surface
+        });
+        fill_rounded_rect(context, layout.padding_box, radius - border_size, background);
 
         self.inner.draw(context);
     }
@@ -0,0 +1,137 @@
+use std::rc::Rc;
+
+use crate::{Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Capability, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Computed, Computed2, Derived, ReadableSignal};
+use crate::widgets::Widget;
+
+
+type Painter = Rc<dyn Fn(&Layout, &mut RenderContext)>;
+type InteractionHandler<A> = Box<dyn Fn(&mut A, &Interaction, &Layout)>;
+
+/// Draws whatever a closure draws, e.g. a chart, without implementing `Widget`.
+///
+/// The closure given to `new` reads what it needs from the model and returns a painter, which is
+/// called with the canvas' layout and the render context whenever the canvas is drawn. The
+/// closure reruns, and the canvas is redrawn, whenever a signal it read changes; the painter
+/// itself runs outside of tracking, so it should only use what it captured.
+pub struct Canvas<A> {
+    style: LayoutStyle,
+    // in logical pixels
+    size: math::Size,
+
+    painter: Derived<A, Option<Painter>>,
+    on_interaction: Option<InteractionHandler<A>>,
+
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Canvas<A> {
+    pub fn new<P>(paint: impl Fn(&mut A) -> P + 'static) -> Canvas<A> where P: Fn(&Layout, &mut RenderContext) + 'static {
+        Canvas {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            size: math::Size::new(100.0, 100.0),
+
+            painter: Derived::new(move |model| Some(Rc::new(paint(model)) as Painter)),
+            on_interaction: None,
+
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Sets the size the canvas asks for when it fits its content, before scaling.
+    pub fn with_size(mut self, width: f32, height: f32) -> Canvas<A> {
+        self.size = math::Size::new(width, height);
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> Canvas<A> {
+        self.style.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: Sizing) -> Canvas<A> {
+        self.style.height = height;
+        self
+    }
+
+    /// Calls `handler` with the clicks, presses, drags, hovers and scrolls over the canvas, and
+    /// with the canvas' layout to tell where they are.
+    pub fn on_interaction(mut self, handler: impl Fn(&mut A, &Interaction, &Layout) + 'static) -> Canvas<A> {
+        self.on_interaction = Some(Box::new(handler));
+        self
+    }
+}
+
+impl<A: 'static> From<Canvas<A>> for Element<A> {
+    fn from(value: Canvas<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A> Widget<A> for Canvas<A> {
+    fn update(&self, model: &mut A) {
+        self.painter.maybe_update(model);
+        self.painter.track()
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| {
+            math::Size::new(scale_factor * self.size.width(), scale_factor * self.size.height())
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            if self.on_interaction.is_none() || self.style.pointer_events == PointerEvents::None {
+                return InteractSet::empty();
+            }
+            let border_box = self.layout_cache.get().border_box;
+            [Capability::Click, Capability::Drag, Capability::Hover, Capability::Scroll].into_iter()
+                .fold(InteractSet::empty(), |set, capability| set.with(capability, border_box))
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        // a parent passes on every interaction, also those outside the canvas
+        if !self.interactions.get_untracked().accepts(interaction) {
+            return;
+        }
+        if let Some(on_interaction) = &self.on_interaction {
+            on_interaction(model, interaction, &self.layout_cache.get_untracked());
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        self.painter.with_untracked(|painter| {
+            if let Some(painter) = painter {
+                painter(&layout, context);
+            }
+        });
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Bind, Computed, Computed2, Derived, ReadableSignal};
+use crate::widgets::div::to_tiny_skia_path;
+use crate::widgets::Widget;
+
+
+/// A box which shows a checkmark while `checked` is true and calls `on_toggle` when clicked.
+///
+/// The checkbox doesn't keep any state of its own, so `on_toggle` has to change whatever
+/// `checked` reads for the checkmark to change.
+pub struct Checkbox<A> {
+    style: LayoutStyle,
+    size: f32,
+    // the theme's foreground color if not set
+    check_color: Option<Color>,
+
+    checked: Derived<A, bool>,
+    on_toggle: Box<dyn Fn(&mut A)>,
+    #[cfg(feature = "sound")]
+    cue: Option<crate::sound::Cue>,
+
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Checkbox<A> {
+    pub fn new(checked: impl (Fn(&mut A) -> bool) + 'static, on_toggle: impl Fn(&mut A) + 'static) -> Checkbox<A> {
+        Checkbox {
+            style: LayoutStyle {
+                border_size: 2.0,
+                padding: 0.0.into(),
+                margin: 1.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            size: 16.0,
+            check_color: None,
+
+            checked: Derived::new(checked),
+            on_toggle: Box::new(on_toggle),
+            #[cfg(feature = "sound")]
+            cue: None,
+
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// A checkbox showing a bound signal, which flips it when clicked.
+    pub fn bind(bind: Bind<A, bool>) -> Checkbox<A> where A: 'static {
+        let getter = bind.getter();
+        Checkbox::new(getter, move |model| bind.update(model, |checked| *checked = !*checked))
+    }
+
+    /// Sets the width and height of the box, before scaling and not counting its border.
+    pub fn with_size(mut self, size: f32) -> Checkbox<A> {
+        self.size = size;
+        self
+    }
+
+    pub fn with_check_color(mut self, color: Color) -> Checkbox<A> {
+        self.check_color = Some(color);
+        self
+    }
+
+    /// Plays `cue` whenever the checkbox is toggled.
+    #[cfg(feature = "sound")]
+    pub fn with_cue(mut self, cue: crate::sound::Cue) -> Checkbox<A> {
+        self.cue = Some(cue);
+        self
+    }
+
+    pub fn set_pointer_events(&mut self, pointer_events: PointerEvents) {
+        self.style.pointer_events = pointer_events;
+        self.interactions.invalidate();
+    }
+}
+
+impl<A> Widget<A> for Checkbox<A> {
+    fn update(&self, model: &mut A) {
+        self.checked.maybe_update(model);
+        self.checked.track()
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| {
+            math::Size::new(scale_factor * self.size, scale_factor * self.size)
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            if self.style.pointer_events == PointerEvents::None {
+                return InteractSet::empty();
+            }
+            InteractSet::empty().with(Capability::Click, self.layout_cache.get().border_box)
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        if let Interaction::Click(point) = interaction {
+            if self.layout_cache.get_untracked().border_box.contains(*point) {
+                #[cfg(feature = "sound")]
+                if let Some(cue) = self.cue {
+                    // a sound which can't be played isn't worth failing the click over
+                    let _ = crate::sound::play(cue);
+                }
+                (self.on_toggle)(model);
+            }
+        }
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        let over = self.layout_cache.get_untracked().border_box.contains(point);
+        (over && self.style.pointer_events != PointerEvents::None).then_some(Cursor::Pointer)
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+
+        if let Ok(padding_box) = tiny_skia::Rect::try_from(layout.padding_box) {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(context.theme.background.into());
+            context.canvas.fill_rect(padding_box, &paint, tiny_skia::Transform::identity(), None);
+        }
+
+        let border_size = self.style.border_size * layout.scale_factor;
+        if border_size > 0.0 {
+            if let Some(path) = to_tiny_skia_path(kurbo::Rect::from(layout.half_border_box)) {
+                let stroke = tiny_skia::Stroke { width: border_size, ..Default::default() };
+                let mut paint = tiny_skia::Paint::default();
+                paint.set_color(context.theme.foreground.into());
+                context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+            }
+        }
+
+        if self.checked.get_untracked() {
+            let area = layout.content_box;
+            let at = |x: f32, y: f32| kurbo::Point::new((area.left() + x * area.width()) as f64, (area.top() + y * area.height()) as f64);
+            let mut check = kurbo::BezPath::new();
+            check.move_to(at(0.2, 0.5));
+            check.line_to(at(0.42, 0.72));
+            check.line_to(at(0.8, 0.28));
+            if let Some(path) = to_tiny_skia_path(check) {
+                let width = 0.15 * area.width().min(area.height());
+                let stroke = tiny_skia::Stroke { width, line_cap: tiny_skia::LineCap::Round, line_join: tiny_skia::LineJoin::Round, ..Default::default() };
+                let mut paint = tiny_skia::Paint::default();
+                paint.set_color(self.check_color.unwrap_or(context.theme.foreground).into());
+                paint.anti_alias = true;
+                context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+            }
+        }
+    }
+}
+
+impl<A: 'static> From<Checkbox<A>> for Element<A> {
+    fn from(value: Checkbox<A>) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,269 @@
+use std::rc::Rc;
+
+use crate::{Color, Element, Label, layout, LayoutStyle, math, Modals, RenderContext};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::shortcut::{Key, KeyInput, NamedKey};
+use crate::tracking::{Computed, Computed2, ReadableSignal, RwSignal};
+use crate::widgets::div::{fill_rounded_rect, stroke_border};
+use crate::widgets::Widget;
+
+
+// in logical pixels
+const MENU_PADDING: f32 = 4.0;
+const ROW_PADDING: f32 = 8.0;
+const MENU_RADIUS: f32 = 3.0;
+const MENU_BORDER: f32 = 1.0;
+// the open menu takes key presses over every input scope
+const MENU_KEY_SCOPE: u32 = u32::MAX;
+
+fn is_escape(input: &KeyInput) -> bool {
+    input.logical == Key::Named(NamedKey::Escape)
+}
+
+type Action<A> = Rc<dyn Fn(&mut A)>;
+
+/// Pops up a menu of actions at the cursor when its inner element is right-clicked.
+///
+/// The menu is pushed to `modals`, which should be the stack of the `Root` showing the tree, see
+/// `Root::with_modals`, so nothing beneath the menu gets input while it is open. It is kept inside
+/// the viewport. Clicking an item runs its action and closes the menu; clicking anywhere else or
+/// pressing Escape closes it without running anything.
+pub struct ContextMenu<A> {
+    inner: Element<A>,
+    modals: Modals<A>,
+    items: Vec<(String, Action<A>)>,
+
+    layout_cache: Computed2<LayoutInput, LayoutInput>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A: 'static> ContextMenu<A> {
+    pub fn new(modals: Modals<A>, inner: impl Into<Element<A>>) -> ContextMenu<A> {
+        ContextMenu {
+            inner: inner.into(),
+            modals,
+            items: Vec::new(),
+
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Adds an item showing `label` which runs `action` when clicked.
+    pub fn with_item(mut self, label: impl Into<String>, action: impl Fn(&mut A) + 'static) -> ContextMenu<A> {
+        self.items.push((label.into(), Rc::new(action)));
+        self
+    }
+
+    fn open(&self, origin: math::Point) {
+        let items = self.items.iter().map(|(label, action)| {
+            let label = label.clone();
+            MenuItem { label: Label::new(move |_| label.clone()).into(), action: Rc::clone(action) }
+        }).collect();
+        self.modals.push(Element::new(Menu {
+            items,
+            origin,
+            modals: self.modals.clone(),
+            hovered: RwSignal::new(None),
+
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }));
+    }
+}
+
+impl<A: 'static> From<ContextMenu<A>> for Element<A> {
+    fn from(value: ContextMenu<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A: 'static> Widget<A> for ContextMenu<A> {
+    fn update(&self, model: &mut A) {
+        self.inner.update(model);
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.inner.prelayout(ctx, input)
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.inner.layout(ctx, input);
+            input
+        });
+        self.layout_cache.track();
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner);
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            let area = self.layout_cache.get().allocated;
+            self.inner.interactions().with(Capability::Click, area)
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match interaction {
+            &Interaction::SecondaryClick(point) if self.layout_cache.get_untracked().allocated.contains(point) => {
+                self.open(point);
+            }
+            _ => self.inner.handle_interaction(interaction, model)
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.inner.draw(context);
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        self.inner.cursor(point)
+    }
+}
+
+struct MenuItem<A> {
+    label: Element<A>,
+    action: Action<A>
+}
+
+// the open menu, laid out over the whole viewport as a modal
+struct Menu<A> {
+    items: Vec<MenuItem<A>>,
+    // where the menu was opened, in physical pixels
+    origin: math::Point,
+    modals: Modals<A>,
+    hovered: RwSignal<Option<usize>>,
+
+    // the viewport, the menu's frame and each item's row
+    layout_cache: Computed2<LayoutInput, (LayoutInput, math::Rect, Vec<math::Rect>)>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Menu<A> {
+    fn set_hovered(&self, hovered: Option<usize>) {
+        if self.hovered.get_untracked() != hovered {
+            self.hovered.update(|current| *current = hovered);
+        }
+    }
+
+    fn row_at(&self, point: math::Point) -> Option<usize> {
+        let (_, _, rows) = self.layout_cache.get_untracked();
+        rows.iter().position(|row| row.contains(point))
+    }
+
+    fn close(&self) {
+        self.modals.pop();
+    }
+}
+
+impl<A> Widget<A> for Menu<A> {
+    fn update(&self, model: &mut A) {
+        for item in &self.items {
+            item.label.update(model);
+        }
+    }
+
+    // a modal always covers the viewport
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&LayoutStyle::default(), input, |available, _| available)
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            // the highlighted row is only drawn, but tracked here so that moving it redraws
+            self.hovered.track();
+            let scale_factor = input.scale_factor;
+            let viewport = input.allocated;
+            let unbounded = math::Size::new(f32::INFINITY, f32::INFINITY);
+            let sizes: Vec<_> = self.items.iter()
+                .map(|item| item.label.prelayout(ctx, PrelayoutInput { available: unbounded, scale_factor }).min_size)
+                .collect();
+            let padding = MENU_PADDING * scale_factor;
+            let row_padding = ROW_PADDING * scale_factor;
+            let row_width = sizes.iter().map(math::Size::width).fold(0.0, f32::max) + 2.0 * row_padding;
+            let size = math::Size::new(
+                row_width + 2.0 * padding,
+                sizes.iter().map(math::Size::height).sum::<f32>() + 2.0 * padding
+            );
+            // opens towards the cursor's other side where it would stick out of the viewport
+            let left = self.origin.x.min(viewport.right() - size.width()).max(viewport.left());
+            let top = self.origin.y.min(viewport.bottom() - size.height()).max(viewport.top());
+            let frame = math::Rect::from_topleft_size(math::Point::new(left, top), size);
+
+            let mut top = frame.top() + padding;
+            let rows = self.items.iter().zip(&sizes).map(|(item, size)| {
+                let row = math::Rect::from_topleft_size(math::Point::new(frame.left() + padding, top), math::Size::new(row_width, size.height()));
+                let label = math::Rect::from_topleft_size(math::Point::new(row.left() + row_padding, top), *size);
+                item.label.layout(ctx, LayoutInput { allocated: label, scale_factor });
+                top += size.height();
+                row
+            }).collect();
+            (input, frame, rows)
+        });
+        self.layout_cache.track();
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        self.items.iter().for_each(|item| f(&item.label));
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            // a click anywhere closes the menu, so it asks for all of them
+            let (input, _, _) = self.layout_cache.get();
+            let mut set = InteractSet::empty()
+                .with(Capability::Click, input.allocated)
+                .with(Capability::Hover, input.allocated);
+            set.key_scope = MENU_KEY_SCOPE;
+            set
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match interaction {
+            &Interaction::Hover(point) => self.set_hovered(point.and_then(|point| self.row_at(point))),
+            &Interaction::Click(point) => {
+                if let Some(index) = self.row_at(point) {
+                    (self.items[index].action)(model);
+                }
+                self.close();
+            }
+            Interaction::SecondaryClick(_) => self.close(),
+            Interaction::Key(input, _) if is_escape(input) => self.close(),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let (input, frame, rows) = self.layout_cache.get_untracked();
+        let scale_factor = input.scale_factor;
+        let hovered = self.hovered.get_untracked().and_then(|index| rows.get(index).copied());
+        let theme = *context.theme;
+        let radius = MENU_RADIUS * scale_factor;
+        let border = MENU_BORDER * scale_factor;
+        fill_rounded_rect(context, frame, radius, theme.surface);
+        if let Some(row) = hovered {
+            let accent = theme.accent;
+            fill_rounded_rect(context, row, radius, Color::from_rgba8(accent.r, accent.g, accent.b, 96));
+        }
+        stroke_border(context, frame.shrink_by((0.5 * border).into()), radius, border, theme.foreground);
+        for item in &mut self.items {
+            item.label.draw(context);
+        }
+    }
+
+    fn cursor(&self, _point: math::Point) -> Option<Cursor> {
+        Some(Cursor::Default)
+    }
+}
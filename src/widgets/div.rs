@@ -1,14 +1,19 @@
 use crate::{Element, layout, Layout, math, RenderContext};
-use crate::interact::{Interaction, InteractSet};
-use crate::layout::{PrelayoutInput, LayoutCharacteristics, LayoutInput};
+use std::cell::{Cell, RefCell};
+
+use crate::interact::{Capability, Cursor, Interaction, InteractSet, UnionTree};
+use crate::layout::{PrelayoutInput, LayoutCharacteristics, LayoutContext, LayoutInput, TextMetrics};
 use crate::math::Axis;
-use crate::style::{LayoutStyle, ContainerLayoutStyle, Justify, Sizing, Direction, Color};
-use crate::tracking::{Computed, Computed2, ReadableSignal, TrackedVec};
+use crate::print::BreakHint;
+use crate::style::{LayoutStyle, ContainerLayoutStyle, Justify, Sizing, Direction, Color, PageBreak, PointerEvents, Position, Shadow, StyleSheet, Wrap};
+use crate::tracking::{Computed, Computed2, Derived, ReadableSignal, RwSignal, TrackedVec};
+use crate::widgets::modifier::draw_layer;
+use crate::widgets::state::{contains_focus, StateTracker};
 use crate::widgets::Widget;
 
 
 // todo move somewhere reasonable
-pub fn to_tiny_skia_path<S: kurbo::Shape>(shape: S) -> tiny_skia::Path {
+pub fn to_tiny_skia_path<S: kurbo::Shape>(shape: S) -> Option<tiny_skia::Path> {
     use kurbo::Point;
 
     let mut path_builder = tiny_skia::PathBuilder::new();
@@ -31,46 +36,205 @@ pub fn to_tiny_skia_path<S: kurbo::Shape>(shape: S) -> tiny_skia::Path {
             }
         }
     }
-    path_builder.finish().unwrap()
+    path_builder.finish()
+}
+
+/// Whether `point` is inside `rect` with its corners rounded by `radius`.
+pub(super) fn rounded_contains(rect: math::Rect, radius: f32, point: math::Point) -> bool {
+    if radius <= 0.0 {
+        return rect.contains(point);
+    }
+    kurbo::Shape::contains(&rounded_rect(rect, radius), kurbo::Point::new(point.x as f64, point.y as f64))
 }
 
+fn rounded_rect(rect: math::Rect, radius: f32) -> kurbo::RoundedRect {
+    kurbo::RoundedRect::from_rect(kurbo::Rect::from(rect), radius.max(0.0) as f64)
+}
+
+pub(super) fn fill_rounded_rect(context: &mut RenderContext, rect: math::Rect, radius: f32, color: Color) {
+    let mut paint = tiny_skia::Paint::default();
+    paint.set_color(color.into());
+    if radius <= 0.0 {
+        if let Ok(rect) = tiny_skia::Rect::try_from(rect) {
+            context.canvas.fill_rect(rect, &paint, tiny_skia::Transform::identity(), None);
+        }
+    } else if let Some(path) = to_tiny_skia_path(rounded_rect(rect, radius)) {
+        context.canvas.fill_path(&path, &paint, tiny_skia::FillRule::Winding, tiny_skia::Transform::identity(), None);
+    }
+}
+
+/// Draws `shadow` beneath the border box `rect`, whose corners are rounded by `radius`.
+pub(super) fn draw_shadow(context: &mut RenderContext, rect: math::Rect, radius: f32, shadow: Shadow, scale_factor: f32) {
+    let spread = shadow.spread * scale_factor;
+    let offset = math::Rect::from_xywh(rect.x + shadow.offset.x * scale_factor, rect.y + shadow.offset.y * scale_factor, rect.w, rect.h);
+    let shape = if spread >= 0.0 { offset.grow_by(spread.into()) } else { offset.shrink_by((-spread).into()) };
+    if shape.w <= 0.0 || shape.h <= 0.0 {
+        return;
+    }
+    // sharp corners stay sharp, rounded ones grow and shrink with the shadow
+    let radius = if radius > 0.0 { (radius + spread).max(0.0) } else { 0.0 };
+
+    // the blur is the size of the soft edge, which is about twice the standard deviation
+    let box_radius = (0.5 * shadow.blur * scale_factor).round() as usize;
+    if box_radius == 0 {
+        fill_rounded_rect(context, shape, radius, shadow.color);
+        return;
+    }
+
+    // three box blurs spread the shape by three times the box radius
+    let bounds = shape.grow_by((3.0 * box_radius as f32).into());
+    let (x, y) = (bounds.left().floor(), bounds.top().floor());
+    let (width, height) = ((bounds.right() - x).ceil() as u32, (bounds.bottom() - y).ceil() as u32);
+    let Some(mut layer) = context.pool.take_pixmap(width, height) else { return; };
+    let local = math::Rect::from_xywh(shape.x - x, shape.y - y, shape.w, shape.h);
+    fill_rounded_rect(&mut RenderContext { canvas: layer.as_mut(), text: context.text, pool: context.pool, theme: context.theme, overlays: context.overlays }, local, radius, shadow.color);
+    blur_pixmap(&mut layer, box_radius);
+
+    context.canvas.draw_pixmap(x as i32, y as i32, layer.as_ref(), &tiny_skia::PixmapPaint::default(), tiny_skia::Transform::identity(), None);
+    context.pool.give_pixmap(layer);
+}
+
+/// How far `draw_shadow` draws past the border box on each side.
+pub(super) fn shadow_outset(shadow: Shadow, scale_factor: f32) -> math::SizeRect {
+    let box_radius = (0.5 * shadow.blur * scale_factor).round();
+    // one more pixel for the layer being placed on whole pixels
+    let grown = (shadow.spread * scale_factor).max(0.0) + 3.0 * box_radius + 1.0;
+    let (x, y) = (shadow.offset.x * scale_factor, shadow.offset.y * scale_factor);
+    math::SizeRect::new((grown - x).max(0.0), (grown + x).max(0.0), (grown - y).max(0.0), (grown + y).max(0.0))
+}
+
+// approximates a gaussian blur with three box blurs of `radius` pixels in each direction
+fn blur_pixmap(pixmap: &mut tiny_skia::Pixmap, radius: usize) {
+    let (width, height) = (pixmap.width() as usize, pixmap.height() as usize);
+    let data = pixmap.data_mut();
+    let mut line = Vec::new();
+    for _ in 0..3 {
+        for row in 0..height {
+            box_blur_line(data, row * width * 4, 4, width, radius, &mut line);
+        }
+        for column in 0..width {
+            box_blur_line(data, column * 4, width * 4, height, radius, &mut line);
+        }
+    }
+}
+
+// blurs the `len` pixels which start at byte `start` and are `stride` bytes apart, treating the
+// pixels past either end as transparent. The data is premultiplied, so all channels are blurred
+// alike.
+fn box_blur_line(data: &mut [u8], start: usize, stride: usize, len: usize, radius: usize, line: &mut Vec<[u32; 4]>) {
+    line.clear();
+    line.extend((0..len).map(|index| {
+        let offset = start + index * stride;
+        [0, 1, 2, 3].map(|channel| data[offset + channel] as u32)
+    }));
+
+    let window = 2 * radius as u32 + 1;
+    let mut sums = [0u32; 4];
+    for pixel in line.iter().take(radius) {
+        sums.iter_mut().zip(pixel).for_each(|(sum, value)| *sum += value);
+    }
+    for index in 0..len {
+        if let Some(entering) = line.get(index + radius) {
+            sums.iter_mut().zip(entering).for_each(|(sum, value)| *sum += value);
+        }
+        let offset = start + index * stride;
+        for (channel, sum) in sums.iter().enumerate() {
+            data[offset + channel] = ((sum + window / 2) / window) as u8;
+        }
+        if index >= radius {
+            sums.iter_mut().zip(&line[index - radius]).for_each(|(sum, value)| *sum -= value);
+        }
+    }
+}
+
+/// Strokes a border of `width` centered on `half_border_box`, given the radius of the outer edge
+/// of the border.
+pub(super) fn stroke_border(context: &mut RenderContext, half_border_box: math::Rect, radius: f32, width: f32, color: Color) {
+    if let Some(path) = to_tiny_skia_path(rounded_rect(half_border_box, radius - 0.5 * width)) {
+        let stroke = tiny_skia::Stroke { width, ..Default::default() };
+        let mut paint = tiny_skia::Paint::default();
+        paint.set_color(color.into());
+        context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+    }
+}
+
+
+// a value produced by one of the `bind_*` closures
+#[derive(Copy, Clone, PartialEq)]
+enum BoundStyle {
+    Width(Sizing),
+    Height(Sizing),
+    Padding(math::SizeRect),
+    Margin(math::SizeRect),
+    Background(Option<Color>),
+    BorderColor(Option<Color>),
+    BorderRadius(f32)
+}
 
 pub struct Div<A> {
-    style: ContainerLayoutStyle,
+    style: Cell<ContainerLayoutStyle>,
     children: TrackedVec<Element<A>>,
+    // bumped by every setter, so that the caches which read the style are recomputed
+    style_revision: RwSignal<u64>,
+    bindings: Vec<Derived<A, Option<BoundStyle>>>,
 
     update_cache: Computed<()>,
-    prelayout_cache: Computed2<PrelayoutInput, math::Size>,
+    prelayout_cache: Computed2<PrelayoutInput, (math::Size, Option<TextMetrics>)>,
     layout_cache: Computed2<LayoutInput, Layout>,
     interactions_cache: Computed<InteractSet>,
+    // each child's contribution is cached separately so that a change in one child only
+    // recomputes that child's entry and its path in the union
+    child_interactions: RefCell<Vec<Computed<InteractSet>>>,
+    interactions_union: RefCell<UnionTree>,
 
-    border_color: Option<Color>,
-    background_color: Option<Color>,
+    border_color: Cell<Option<Color>>,
+    // of the outer edge of the border, in logical pixels
+    border_radius: Cell<f32>,
+    background_color: Cell<Option<Color>>,
+    shadow: Cell<Option<Shadow>>,
+    style_sheet: Option<StyleSheet>,
+    state: StateTracker<A>,
+    page_break: PageBreak,
+    cursor: Option<Cursor>
 }
 
 impl<A> Div<A> {
     pub fn new() -> Div<A> {
         Div {
-            style: ContainerLayoutStyle {
+            style: Cell::new(ContainerLayoutStyle {
                 layout_style: LayoutStyle {
                     border_size: 2.0,
                     padding: 2.0.into(),
                     margin: 1.0.into(),
                     width: Sizing::Fit,
-                    height: Sizing::Fit
+                    height: Sizing::Fit,
+                    pointer_events: PointerEvents::Auto,
+                    position: Position::Flow
                 },
                 main_axis: Axis::Vertical,
                 main_direction: Direction::Positive,
                 main_justify: Justify::Min,
-                cross_justify: Justify::Min
-            },
+                cross_justify: Justify::Min,
+                wrap: Wrap::NoWrap,
+                gap: 0.0
+            }),
             children: TrackedVec::new(),
+            style_revision: RwSignal::new(0),
+            bindings: Vec::new(),
             update_cache: Computed::new(),
             prelayout_cache: Computed2::new(),
             layout_cache: Computed2::new(),
             interactions_cache: Computed::new(),
-            border_color: Some(Color::BLACK),
-            background_color: None
+            child_interactions: RefCell::new(Vec::new()),
+            interactions_union: RefCell::new(UnionTree::new(0)),
+            border_color: Cell::new(Some(Color::BLACK)),
+            border_radius: Cell::new(0.0),
+            background_color: Cell::new(None),
+            shadow: Cell::new(None),
+            style_sheet: None,
+            state: StateTracker::new(),
+            page_break: PageBreak::Auto,
+            cursor: None
         }
     }
 
@@ -78,20 +242,243 @@ impl<A> Div<A> {
         self.children.push(element.into());
     }
 
+    /// An empty div which lays its children out left to right.
+    pub fn row() -> Div<A> {
+        let mut div = Div::new();
+        div.set_main_axis(Axis::Horizontal);
+        div
+    }
+
+    /// An empty div which lays its children out top to bottom, the same as `Div::new`.
+    pub fn column() -> Div<A> {
+        let mut div = Div::new();
+        div.set_main_axis(Axis::Vertical);
+        div
+    }
+
+    fn style_changed(&self) {
+        self.style_revision.update(|revision| *revision += 1);
+    }
+
+    fn update_style(&self, f: impl FnOnce(&mut LayoutStyle)) {
+        let mut style = self.style.get();
+        f(&mut style.layout_style);
+        self.style.set(style);
+        self.style_changed();
+    }
+
+    fn update_container_style(&self, f: impl FnOnce(&mut ContainerLayoutStyle)) {
+        let mut style = self.style.get();
+        f(&mut style);
+        self.style.set(style);
+        self.style_changed();
+    }
+
     pub fn set_width(&mut self, width: Sizing) {
-        self.style.layout_style.width = width;
+        self.update_style(|style| style.width = width);
     }
 
     pub fn set_height(&mut self, height: Sizing) {
-        self.style.layout_style.height = height;
+        self.update_style(|style| style.height = height);
     }
 
     pub fn set_margin(&mut self, margin: math::SizeRect) {
-        self.style.layout_style.margin = margin;
+        self.update_style(|style| style.margin = margin);
+    }
+
+    pub fn set_padding(&mut self, padding: math::SizeRect) {
+        self.update_style(|style| style.padding = padding);
+    }
+
+    /// Draws a border `size` logical pixels wide in `color`, or none with a size of 0 or no color.
+    pub fn set_border(&mut self, size: f32, color: impl Into<Option<Color>>) {
+        self.set_border_color(color);
+        self.set_border_size(size);
+    }
+
+    /// The space taken by the border in logical pixels, even when it has no color.
+    pub fn set_border_size(&mut self, size: f32) {
+        self.update_style(|style| style.border_size = size.max(0.0));
+    }
+
+    pub fn set_border_color(&mut self, color: impl Into<Option<Color>>) {
+        self.border_color.set(color.into());
+        self.style_changed();
     }
 
     pub fn set_background_color(&mut self, color: impl Into<Option<Color>>) {
-        self.background_color = color.into();
+        self.background_color.set(color.into());
+        self.style_changed();
+    }
+
+    /// Draws a shadow beneath the div, or none.
+    pub fn set_shadow(&mut self, shadow: impl Into<Option<Shadow>>) {
+        self.shadow.set(shadow.into());
+        self.style_changed();
+    }
+
+    /// Rounds the corners of the border and background by `radius` logical pixels.
+    pub fn set_border_radius(&mut self, radius: f32) {
+        self.border_radius.set(radius.max(0.0));
+        self.style_changed();
+    }
+
+    /// Lays the children out along `axis`, e.g. in a row with `Axis::Horizontal`, instead of
+    /// vertically.
+    pub fn set_main_axis(&mut self, axis: Axis) {
+        self.update_container_style(|style| style.main_axis = axis);
+    }
+
+    /// Lays the children out from the end of the main axis instead, e.g. bottom to top.
+    pub fn set_main_direction(&mut self, direction: Direction) {
+        self.update_container_style(|style| style.main_direction = direction);
+    }
+
+    /// Where the children go along the main axis when they don't fill it.
+    pub fn set_main_justify(&mut self, justify: Justify) {
+        self.update_container_style(|style| style.main_justify = justify);
+    }
+
+    /// Where each child goes across the main axis when it is narrower than the div.
+    pub fn set_cross_justify(&mut self, justify: Justify) {
+        self.update_container_style(|style| style.cross_justify = justify);
+    }
+
+    /// Leaves `gap` logical pixels between neighbouring children along the main axis.
+    pub fn set_gap(&mut self, gap: f32) {
+        self.update_container_style(|style| style.gap = gap.max(0.0));
+    }
+
+    /// Lets children which don't fit along the main axis flow onto new lines.
+    pub fn set_wrap(&mut self, wrap: Wrap) {
+        self.update_container_style(|style| style.wrap = wrap);
+    }
+
+    pub fn set_pointer_events(&mut self, pointer_events: PointerEvents) {
+        self.update_style(|style| style.pointer_events = pointer_events);
+    }
+
+    /// Changes how the div is drawn while it is hovered, pressed, disabled or holds a focused
+    /// widget, see `StyleSheet`.
+    pub fn set_style_sheet(&mut self, style_sheet: StyleSheet) {
+        self.style_sheet = Some(style_sheet);
+        self.style_changed();
+    }
+
+    /// Draws the div with its style sheet's disabled style while `disabled` returns true. Its
+    /// children still get interactions.
+    pub fn bind_disabled(&mut self, disabled: impl Fn(&mut A) -> bool + 'static) {
+        self.state.bind_disabled(disabled);
+    }
+
+    /// Shows `cursor` while the pointer is over the div, unless a child asks for another one.
+    pub fn set_cursor(&mut self, cursor: Cursor) {
+        self.cursor = Some(cursor);
+    }
+
+    pub fn set_page_break(&mut self, page_break: PageBreak) {
+        self.page_break = page_break;
+        self.style_changed();
+    }
+
+    /// Computes the width from the model instead of using a fixed value. The closure is re-run
+    /// whenever a signal it reads changes, and the div is only remeasured if the result differs.
+    /// To follow a signal in the model, pass `Bind::getter`.
+    pub fn bind_width(&mut self, width: impl Fn(&mut A) -> Sizing + 'static) {
+        self.bindings.push(Derived::new(move |model| Some(BoundStyle::Width(width(model)))));
+    }
+
+    pub fn bind_height(&mut self, height: impl Fn(&mut A) -> Sizing + 'static) {
+        self.bindings.push(Derived::new(move |model| Some(BoundStyle::Height(height(model)))));
+    }
+
+    pub fn bind_padding(&mut self, padding: impl Fn(&mut A) -> math::SizeRect + 'static) {
+        self.bindings.push(Derived::new(move |model| Some(BoundStyle::Padding(padding(model)))));
+    }
+
+    pub fn bind_margin(&mut self, margin: impl Fn(&mut A) -> math::SizeRect + 'static) {
+        self.bindings.push(Derived::new(move |model| Some(BoundStyle::Margin(margin(model)))));
+    }
+
+    pub fn bind_background_color(&mut self, color: impl Fn(&mut A) -> Option<Color> + 'static) {
+        self.bindings.push(Derived::new(move |model| Some(BoundStyle::Background(color(model)))));
+    }
+
+    pub fn bind_border_color(&mut self, color: impl Fn(&mut A) -> Option<Color> + 'static) {
+        self.bindings.push(Derived::new(move |model| Some(BoundStyle::BorderColor(color(model)))));
+    }
+
+    pub fn bind_border_radius(&mut self, radius: impl Fn(&mut A) -> f32 + 'static) {
+        self.bindings.push(Derived::new(move |model| Some(BoundStyle::BorderRadius(radius(model)))));
+    }
+
+    // re-evaluates the bindings whose inputs changed and applies the ones with a new value
+    fn update_bindings(&self, model: &mut A) {
+        for binding in &self.bindings {
+            if binding.maybe_update_if_changed(model) {
+                match binding.get_untracked() {
+                    Some(BoundStyle::Width(width)) => self.update_style(|style| style.width = width),
+                    Some(BoundStyle::Height(height)) => self.update_style(|style| style.height = height),
+                    Some(BoundStyle::Padding(padding)) => self.update_style(|style| style.padding = padding),
+                    Some(BoundStyle::Margin(margin)) => self.update_style(|style| style.margin = margin),
+                    Some(BoundStyle::Background(color)) => {
+                        self.background_color.set(color);
+                        self.style_changed();
+                    }
+                    Some(BoundStyle::BorderColor(color)) => {
+                        self.border_color.set(color);
+                        self.style_changed();
+                    }
+                    Some(BoundStyle::BorderRadius(radius)) => {
+                        self.border_radius.set(radius.max(0.0));
+                        self.style_changed();
+                    }
+                    None => {}
+                }
+            }
+            binding.track();
+        }
+    }
+}
+
+impl<A> Div<A> {
+    fn draw_contents(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let border_size = self.style.get().layout_style.border_size * layout.scale_factor;
+        let radius = self.border_radius.get() * layout.scale_factor;
+        let patch = self.style_sheet.map(|sheet| sheet.resolve(self.state.get())).unwrap_or_default();
+        if let Some(shadow) = self.shadow.get() {
+            draw_shadow(context, layout.border_box, radius, shadow, layout.scale_factor);
+        }
+        match patch.border {
+            // the border box stays where layout put it, so a patched border is drawn inside it
+            Some((color, width)) if width > 0.0 => {
+                let width = width * layout.scale_factor;
+                stroke_border(context, layout.border_box.shrink_by((0.5 * width).into()), radius, width, color);
+            }
+            Some(_) => {}
+            None => if let Some(border_color) = self.border_color.get() {
+                if border_size > 0.0 {
+                    stroke_border(context, layout.half_border_box, radius, border_size, border_color);
+                }
+            }
+        }
+
+        if let Some(background) = patch.background.or(self.background_color.get()) {
+            fill_rounded_rect(context, layout.padding_box, radius - border_size, background);
+        }
+
+        self.children.with_mut_untracked(|children| {
+            for child in children {
+                child.draw(context);
+            }
+        })
+    }
+}
+
+impl<A> Default for Div<A> {
+    fn default() -> Self {
+        Div::new()
     }
 }
 
@@ -104,6 +491,10 @@ impl<A: 'static> From<Div<A>> for Element<A> {
 impl<A> Widget<A> for Div<A> {
     fn update(&self, model: &mut A) {
         self.update_cache.maybe_update(|| {
+            self.update_bindings(model);
+            // style and state changes don't affect the update itself, but they need a redraw
+            self.style_revision.track();
+            self.state.update(model);
             self.children.with(|children| {
                 for child in children {
                     child.update(model);
@@ -113,47 +504,94 @@ impl<A> Widget<A> for Div<A> {
         self.update_cache.track();
     }
 
-    fn prelayout(&self, input: PrelayoutInput) -> LayoutCharacteristics {
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
         self.prelayout_cache.maybe_update(input, |&input| {
-            let characteristics = self.children.with(|items| layout::container::do_prelayout(&self.style, input, items));
-            characteristics.min_size
+            self.style_revision.track();
+            let characteristics = self.children.with(|items| layout::container::do_prelayout(&self.style.get(), ctx, input, items));
+            (characteristics.min_size, characteristics.text_metrics)
         });
+        let (min_size, text_metrics) = self.prelayout_cache.get();
         LayoutCharacteristics {
-            layout_style: &self.style.layout_style,
-            min_size: self.prelayout_cache.get_untracked()
+            layout_style: self.style.get().layout_style,
+            min_size,
+            text_metrics
         }
     }
 
-    fn layout(&self, input: LayoutInput) {
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
         self.layout_cache.maybe_update(input, |&input| {
             self.prelayout_cache.track();
+            let style = self.style.get();
             self.children.with(|children| {
-                let children_layouts = layout::container::do_layout(&self.style, input, children);
+                let children_layouts = layout::container::do_layout(&style, ctx, input, children);
                 for (child, child_layout) in children.iter().zip(children_layouts) {
-                    child.layout(child_layout);
+                    child.layout(ctx, child_layout);
                 }
             });
-            Layout::from_layout_input(&self.style.layout_style, input)
+            Layout::from_layout_input(&style.layout_style, input)
         });
 
         self.layout_cache.track()
     }
 
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn paint_outset(&self) -> math::SizeRect {
+        let scale_factor = self.layout_cache.get_untracked().scale_factor;
+        self.shadow.get().map_or(0.0.into(), |shadow| shadow_outset(shadow, scale_factor))
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        self.children.with_untracked(|children| children.iter().for_each(f))
+    }
+
     fn interactions(&self) -> InteractSet {
         self.interactions_cache.maybe_update(|| {
-            let mut set = InteractSet::default();
+            self.style_revision.track();
+            if self.style.get().layout_style.pointer_events == PointerEvents::None {
+                return InteractSet::default();
+            }
+            let mut child_interactions = self.child_interactions.borrow_mut();
+            let mut union = self.interactions_union.borrow_mut();
             self.children.with(|children| {
-                for child in children {
-                    set = set | child.interactions();
+                if union.len() != children.len() {
+                    child_interactions.clear();
+                    child_interactions.resize_with(children.len(), Computed::new);
+                    *union = UnionTree::new(children.len());
+                }
+                for (index, (child, contribution)) in children.iter().zip(child_interactions.iter()).enumerate() {
+                    if contribution.is_dirty() {
+                        contribution.maybe_update(|| child.interactions());
+                        union.set(index, contribution.get_untracked());
+                    }
+                    contribution.track();
                 }
             });
-            set
+            match self.style_sheet {
+                Some(_) => {
+                    let border_box = self.layout_cache.get().border_box;
+                    union.union().with(Capability::Hover, border_box).with(Capability::Drag, border_box)
+                }
+                None => union.union()
+            }
         });
         self.interactions_cache.get()
     }
 
     fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
         if self.interactions_cache.get_untracked().accepts(interaction) {
+            if self.style_sheet.is_some() {
+                let layout = self.layout_cache.get_untracked();
+                let radius = self.border_radius.get() * layout.scale_factor;
+                let children = &self.children;
+                self.state.handle_interaction(
+                    interaction,
+                    |point| rounded_contains(layout.border_box, radius, point),
+                    |focus| children.with_untracked(|children| children.iter().any(|child| contains_focus(child, focus)))
+                );
+            }
             self.children.with_mut_untracked(|children| {
                 for child in children.iter_mut() {
                     child.handle_interaction(interaction, model)
@@ -163,33 +601,35 @@ impl<A> Widget<A> for Div<A> {
     }
 
     fn draw(&mut self, context: &mut RenderContext) {
-        let layout = self.layout_cache.get_untracked();
-        let border_size = self.style.layout_style.border_size * layout.scale_factor;
-        if let Some(border_color) = self.border_color {
-            if border_size > 0.0 {
-                let border_box = layout.half_border_box;
-                let path = to_tiny_skia_path(kurbo::Rect::from(border_box));
-                let mut stroke = tiny_skia::Stroke::default();
-                stroke.width = border_size;
-                let mut paint = tiny_skia::Paint::default();
-                paint.set_color(border_color.into());
-                context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
-            }
+        let patch = self.style_sheet.map(|sheet| sheet.resolve(self.state.get())).unwrap_or_default();
+        match patch.opacity {
+            Some(opacity) => draw_layer(context, opacity, None, |context| self.draw_contents(context)),
+            None => self.draw_contents(context)
         }
+    }
 
-        if let Some(background) = self.background_color {
-            let padding_box = layout.padding_box;
-
-            let mut paint = tiny_skia::Paint::default();
-            paint.set_color(background.into());
-            context.canvas.fill_rect(padding_box.into(), &paint, tiny_skia::Transform::identity(), None);
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        if self.style.get().layout_style.pointer_events == PointerEvents::None {
+            return None;
         }
+        let from_children = self.children.with_untracked(|children| children.iter().rev().find_map(|child| child.cursor(point)));
+        from_children.or_else(|| {
+            let layout = self.layout_cache.get_untracked();
+            let over = rounded_contains(layout.border_box, self.border_radius.get() * layout.scale_factor, point);
+            self.cursor.filter(|_| over)
+        })
+    }
 
-        self.children.with_mut_untracked(|children| {
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        if self.page_break != PageBreak::Auto {
+            let border_box = self.layout_cache.get_untracked().border_box;
+            hints.push(BreakHint { top: border_box.top(), bottom: border_box.bottom(), page_break: self.page_break });
+        }
+        self.children.with(|children| {
             for child in children {
-                child.draw(context);
+                child.page_breaks(hints);
             }
-        })
+        });
     }
 }
 
@@ -198,28 +638,113 @@ impl<A> Widget<A> for Div<A> {
 macro_rules! div {
     (width=$e:expr $(, $($rest:tt)*)?) => {{
         use $crate::Widget;
-        let mut div = div!($( $($rest)* )?);
+        let mut div = $crate::div!($( $($rest)* )?);
         div.set_width(($e).into());
         div
     }};
     (height=$e:expr $(, $($rest:tt)*)?) => {{
         use $crate::Widget;
-        let mut div = div!($( $($rest)* )?);
+        let mut div = $crate::div!($( $($rest)* )?);
         div.set_height(($e).into());
         div
     }};
     (margin=$e:expr $(, $($rest:tt)*)?) => {{
         use $crate::Widget;
-        let mut div = div!($( $($rest)* )?);
+        let mut div = $crate::div!($( $($rest)* )?);
         div.set_margin(($e).into());
         div
     }};
+    (padding=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_padding(($e).into());
+        div
+    }};
+    (border=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        let (size, color) = $e;
+        div.set_border(size, color);
+        div
+    }};
     (background=$e:expr $(, $($rest:tt)*)?) => {{
         use $crate::Widget;
-        let mut div = div!($( $($rest)* )?);
+        let mut div = $crate::div!($( $($rest)* )?);
         div.set_background_color($e);
         div
     }};
+    (shadow=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_shadow($e);
+        div
+    }};
+    (border_radius=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_border_radius($e);
+        div
+    }};
+    (axis=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_main_axis($e);
+        div
+    }};
+    (direction=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_main_direction($e);
+        div
+    }};
+    (justify=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_main_justify($e);
+        div
+    }};
+    (cross_justify=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_cross_justify($e);
+        div
+    }};
+    (gap=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_gap($e);
+        div
+    }};
+    (wrap=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_wrap($e);
+        div
+    }};
+    (pointer_events=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_pointer_events($e);
+        div
+    }};
+    (style_sheet=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_style_sheet($e);
+        div
+    }};
+    (cursor=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_cursor($e);
+        div
+    }};
+    (page_break=$e:expr $(, $($rest:tt)*)?) => {{
+        use $crate::Widget;
+        let mut div = $crate::div!($( $($rest)* )?);
+        div.set_page_break($e);
+        div
+    }};
     ([$($item:expr),* $(,)?]) => {{
         let mut div = $crate::Div::new();
         $(
@@ -229,3 +754,20 @@ macro_rules! div {
     }};
     () => {{ $crate::Div::new() }};
 }
+
+/// Like `div!`, but lays the children out left to right.
+#[macro_export]
+macro_rules! row {
+    ($($t:tt)*) => {
+        $crate::div!(axis=$crate::math::Axis::Horizontal, $($t)*)
+    };
+}
+
+/// Like `div!`, but always lays the children out top to bottom. Named `col!` rather than
+/// `column!` so that it doesn't shadow `std::column!` where it is imported.
+#[macro_export]
+macro_rules! col {
+    ($($t:tt)*) => {
+        $crate::div!(axis=$crate::math::Axis::Vertical, $($t)*)
+    };
+}
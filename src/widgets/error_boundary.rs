@@ -0,0 +1,195 @@
+use std::any::Any;
+use std::cell::{Cell, OnceCell};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{Color, Element, RenderContext};
+#[cfg(feature = "text")]
+use crate::Label;
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, PrelayoutInput, LayoutInput};
+use crate::print::BreakHint;
+use crate::tracking::{ReadableSignal, RwSignal};
+use crate::widgets::{Div, Widget};
+
+
+fn catch<O>(f: impl FnOnce() -> O) -> Result<O, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(payload.as_ref()))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn default_fallback<A: 'static>(_message: &str) -> Element<A> {
+    let mut div = Div::new();
+    div.set_background_color(Color::from_rgb8(255, 200, 200));
+    #[cfg(feature = "text")]
+    {
+        let message = _message.to_string();
+        div.add_child(Label::new(move |_| format!("Error: {}", message)));
+    }
+    div.into()
+}
+
+type MakeFallback<A> = Box<dyn Fn(&str) -> Element<A>>;
+type ErrorCallback<A> = Box<dyn Fn(&mut A, &str)>;
+
+/// Isolates panics in a subtree.
+///
+/// If updating, laying out, drawing or handling an interaction in `inner` panics, the panic is
+/// caught, the subtree is permanently replaced by a fallback element, and the error is reported
+/// to the `on_error` callback during the next update.
+pub struct ErrorBoundary<A> {
+    inner: Element<A>,
+    fallback: OnceCell<Element<A>>,
+    make_fallback: MakeFallback<A>,
+
+    error: RwSignal<Option<String>>,
+    reported: Cell<bool>,
+    on_error: Option<ErrorCallback<A>>,
+    #[cfg(feature = "sound")]
+    cue: Option<crate::sound::Cue>
+}
+
+impl<A: 'static> ErrorBoundary<A> {
+    pub fn new(inner: impl Into<Element<A>>) -> ErrorBoundary<A> {
+        ErrorBoundary {
+            inner: inner.into(),
+            fallback: OnceCell::new(),
+            make_fallback: Box::new(default_fallback),
+
+            error: RwSignal::new(None),
+            reported: Cell::new(false),
+            on_error: None,
+            #[cfg(feature = "sound")]
+            cue: None
+        }
+    }
+
+    pub fn with_fallback(mut self, make_fallback: impl Fn(&str) -> Element<A> + 'static) -> ErrorBoundary<A> {
+        self.make_fallback = Box::new(make_fallback);
+        self
+    }
+
+    pub fn with_on_error(mut self, on_error: impl Fn(&mut A, &str) + 'static) -> ErrorBoundary<A> {
+        self.on_error = Some(Box::new(on_error));
+        self
+    }
+
+    /// Plays `cue` when the subtree panics.
+    #[cfg(feature = "sound")]
+    pub fn with_cue(mut self, cue: crate::sound::Cue) -> ErrorBoundary<A> {
+        self.cue = Some(cue);
+        self
+    }
+}
+
+impl<A> ErrorBoundary<A> {
+    pub fn error(&self) -> Option<String> {
+        self.error.get()
+    }
+
+    fn fail(&self, message: String) -> &Element<A> {
+        #[cfg(feature = "sound")]
+        if let Some(cue) = self.cue {
+            // the panic is reported to `on_error` either way
+            let _ = crate::sound::play(cue);
+        }
+        let fallback = self.fallback.get_or_init(|| (self.make_fallback)(&message));
+        self.error.update(|error| *error = Some(message));
+        fallback
+    }
+}
+
+impl<A: 'static> From<ErrorBoundary<A>> for Element<A> {
+    fn from(value: ErrorBoundary<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A> Widget<A> for ErrorBoundary<A> {
+    fn update(&self, model: &mut A) {
+        self.error.track();
+        if self.fallback.get().is_none() {
+            if let Err(message) = catch(|| self.inner.update(model)) {
+                self.fail(message);
+            }
+        }
+
+        if let Some(fallback) = self.fallback.get() {
+            if !self.reported.replace(true) {
+                if let (Some(on_error), Some(message)) = (&self.on_error, self.error.get_untracked()) {
+                    on_error(model, &message);
+                }
+            }
+            fallback.update(model);
+        }
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.error.track();
+        if let Some(fallback) = self.fallback.get() {
+            return fallback.prelayout(ctx, input);
+        }
+        match catch(|| self.inner.prelayout(ctx, input)) {
+            Ok(characteristics) => characteristics,
+            Err(message) => self.fail(message).prelayout(ctx, input)
+        }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.error.track();
+        if let Some(fallback) = self.fallback.get() {
+            return fallback.layout(ctx, input);
+        }
+        if let Err(message) = catch(|| self.inner.layout(ctx, input)) {
+            self.fail(message).layout(ctx, input);
+        }
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(self.fallback.get().unwrap_or(&self.inner))
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.error.track();
+        if let Some(fallback) = self.fallback.get() {
+            return fallback.interactions();
+        }
+        match catch(|| self.inner.interactions()) {
+            Ok(set) => set,
+            Err(message) => self.fail(message).interactions()
+        }
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        if self.fallback.get().is_some() {
+            return;
+        }
+        if let Err(message) = catch(|| self.inner.handle_interaction(interaction, model)) {
+            self.fail(message);
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        if let Some(fallback) = self.fallback.get_mut() {
+            return fallback.draw(context);
+        }
+        if let Err(message) = catch(|| self.inner.draw(context)) {
+            self.fail(message);
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        match self.fallback.get() {
+            Some(fallback) => fallback.page_breaks(hints),
+            None => self.inner.page_breaks(hints)
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{Element, Handled, math, RenderContext};
+use crate::focus::{self, FocusId};
+use crate::interact::{Capability, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::shortcut::{KeyInput, Modifiers};
+use crate::tracking::{Computed, Computed2, ReadableSignal};
+use crate::widgets::Widget;
+
+
+type KeyDownHandler<A> = Box<dyn Fn(&mut A, &KeyInput) -> Handled>;
+type KeyHandler<A> = Box<dyn Fn(&mut A, &KeyInput)>;
+type TextHandler<A> = Box<dyn Fn(&mut A, &str, Modifiers)>;
+type FocusHandler<A> = Box<dyn Fn(&mut A, bool)>;
+
+/// Lets a subtree hold the keyboard focus and receive key presses, releases and typed text while
+/// it does.
+///
+/// Clicking inside the subtree focuses it; `Root::set_focus` with its `id` does so from code.
+/// While it is focused it sees every key first. Keys its `on_key_down` handler doesn't handle go
+/// on to input scopes and the application's shortcuts.
+pub struct Focusable<A> {
+    inner: Element<A>,
+    id: FocusId,
+    focused: bool,
+
+    on_key_down: Option<KeyDownHandler<A>>,
+    on_key_up: Option<KeyHandler<A>>,
+    on_text: Option<TextHandler<A>>,
+    on_focus_change: Option<FocusHandler<A>>,
+
+    layout_cache: Computed2<LayoutInput, math::Rect>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Focusable<A> {
+    pub fn new(inner: impl Into<Element<A>>) -> Focusable<A> {
+        Focusable {
+            inner: inner.into(),
+            id: FocusId::unique(),
+            focused: false,
+
+            on_key_down: None,
+            on_key_up: None,
+            on_text: None,
+            on_focus_change: None,
+
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Uses `id` instead of a fresh one, so the model can keep it to focus this widget later.
+    pub fn with_id(mut self, id: FocusId) -> Focusable<A> {
+        self.id = id;
+        self
+    }
+
+    pub fn id(&self) -> FocusId {
+        self.id
+    }
+
+    /// Calls `handler` with every key pressed while the widget is focused. Returning
+    /// `Handled::No` lets the key go on to input scopes and shortcuts.
+    pub fn on_key_down(mut self, handler: impl Fn(&mut A, &KeyInput) -> Handled + 'static) -> Focusable<A> {
+        self.on_key_down = Some(Box::new(handler));
+        self
+    }
+
+    pub fn on_key_up(mut self, handler: impl Fn(&mut A, &KeyInput) + 'static) -> Focusable<A> {
+        self.on_key_up = Some(Box::new(handler));
+        self
+    }
+
+    pub fn on_text(mut self, handler: impl Fn(&mut A, &str, Modifiers) + 'static) -> Focusable<A> {
+        self.on_text = Some(Box::new(handler));
+        self
+    }
+
+    /// Calls `handler` with `true` when the widget gains the focus and `false` when it loses it.
+    pub fn on_focus_change(mut self, handler: impl Fn(&mut A, bool) + 'static) -> Focusable<A> {
+        self.on_focus_change = Some(Box::new(handler));
+        self
+    }
+}
+
+impl<A> Widget<A> for Focusable<A> {
+    fn update(&self, model: &mut A) {
+        self.inner.update(model)
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.inner.prelayout(ctx, input)
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.inner.layout(ctx, input);
+            input.allocated
+        });
+        self.layout_cache.track();
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner)
+    }
+
+    fn focus_id(&self) -> Option<FocusId> {
+        Some(self.id)
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            self.inner.interactions().with(Capability::Keyboard, self.layout_cache.get())
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match interaction {
+            Interaction::KeyDown(input, target) if *target == self.id => {
+                if let Some(on_key_down) = &self.on_key_down {
+                    if on_key_down(model, input) == Handled::Yes {
+                        focus::mark_key_used();
+                    }
+                }
+            }
+            Interaction::KeyUp(input, target) if *target == self.id => {
+                if let Some(on_key_up) = &self.on_key_up {
+                    on_key_up(model, input);
+                }
+            }
+            Interaction::Text { text, modifiers, target } if *target == self.id => {
+                if let Some(on_text) = &self.on_text {
+                    on_text(model, text, *modifiers);
+                }
+            }
+            &Interaction::Focus(focus) => {
+                let focused = focus == Some(self.id);
+                if focused != self.focused {
+                    self.focused = focused;
+                    if let Some(on_focus_change) = &self.on_focus_change {
+                        on_focus_change(model, focused);
+                    }
+                }
+                self.inner.handle_interaction(interaction, model);
+            }
+            _ => self.inner.handle_interaction(interaction, model)
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.inner.draw(context)
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+}
+
+impl<A: 'static> From<Focusable<A>> for Element<A> {
+    fn from(value: Focusable<A>) -> Self {
+        Element::new(value)
+    }
+}
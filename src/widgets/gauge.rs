@@ -0,0 +1,168 @@
+use std::f64::consts::PI;
+
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Computed2, Derived, ReadableSignal};
+use crate::widgets::div::to_tiny_skia_path;
+use crate::widgets::Widget;
+
+
+/// A radial gauge showing a value between 0 and 1 as a partially filled arc.
+///
+/// Angles are in degrees, clockwise from the top. The default sweep of 270° leaves a gap at the
+/// bottom; a sweep of 360° makes a circular progress indicator.
+pub struct Gauge<A> {
+    style: LayoutStyle,
+    diameter: f32,
+    thickness: f32,
+    start_angle: f32,
+    sweep: f32,
+    ticks: usize,
+    // the theme's track and accent colors if not set
+    colors: Option<(Color, Color)>,
+
+    value: Derived<A, f32>,
+    layout_cache: Computed2<LayoutInput, Layout>
+}
+
+impl<A> Gauge<A> {
+    pub fn new(value: impl (Fn(&mut A) -> f32) + 'static) -> Gauge<A> {
+        Gauge {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            diameter: 48.0,
+            thickness: 6.0,
+            start_angle: -135.0,
+            sweep: 270.0,
+            ticks: 0,
+            colors: None,
+
+            value: Derived::new(value),
+            layout_cache: Computed2::new()
+        }
+    }
+
+    /// Sets the diameter the gauge asks for and the thickness of its arc, before scaling.
+    pub fn with_size(mut self, diameter: f32, thickness: f32) -> Gauge<A> {
+        self.diameter = diameter;
+        self.thickness = thickness;
+        self
+    }
+
+    pub fn with_sweep(mut self, start_angle: f32, sweep: f32) -> Gauge<A> {
+        self.start_angle = start_angle;
+        self.sweep = sweep.clamp(-360.0, 360.0);
+        self
+    }
+
+    /// Draws `ticks` evenly spaced marks along the sweep, including both ends.
+    pub fn with_ticks(mut self, ticks: usize) -> Gauge<A> {
+        self.ticks = ticks;
+        self
+    }
+
+    pub fn with_colors(mut self, track: Color, fill: Color) -> Gauge<A> {
+        self.colors = Some((track, fill));
+        self
+    }
+
+    fn stroke_arc(&self, context: &mut RenderContext, center: kurbo::Point, radius: f64, sweep: f32, color: Color, width: f32) {
+        let arc = kurbo::Arc {
+            center,
+            radii: kurbo::Vec2::new(radius, radius),
+            // kurbo measures angles from the positive x axis
+            start_angle: (self.start_angle as f64 - 90.0) * PI / 180.0,
+            sweep_angle: sweep as f64 * PI / 180.0,
+            x_rotation: 0.0
+        };
+        if let Some(path) = to_tiny_skia_path(arc) {
+            let stroke = tiny_skia::Stroke { width, line_cap: tiny_skia::LineCap::Butt, ..Default::default() };
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(color.into());
+            paint.anti_alias = true;
+            context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+        }
+    }
+}
+
+impl<A> Widget<A> for Gauge<A> {
+    fn update(&self, model: &mut A) {
+        self.value.maybe_update(model);
+        self.value.track()
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| {
+            math::Size::new(scale_factor * self.diameter, scale_factor * self.diameter)
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        InteractSet::empty()
+    }
+
+    fn handle_interaction(&mut self, _interaction: &Interaction, _model: &mut A) {
+
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let area = layout.content_box;
+        let thickness = self.thickness * layout.scale_factor;
+        let radius = (0.5 * area.width().min(area.height()) - 0.5 * thickness) as f64;
+        if radius <= 0.0 {
+            return;
+        }
+        let center = kurbo::Point::new((area.left() + 0.5 * area.width()) as f64, (area.top() + 0.5 * area.height()) as f64);
+
+        let (track_color, fill_color) = self.colors.unwrap_or((context.theme.track, context.theme.accent));
+        self.stroke_arc(context, center, radius, self.sweep, track_color, thickness);
+        let value = self.value.get_untracked().clamp(0.0, 1.0);
+        if value > 0.0 {
+            self.stroke_arc(context, center, radius, value * self.sweep, fill_color, thickness);
+        }
+
+        if self.ticks > 0 {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(Color::DARK_GRAY.into());
+            paint.anti_alias = true;
+            let stroke = tiny_skia::Stroke { width: layout.scale_factor, ..Default::default() };
+            let (inner, outer) = (radius - 0.5 * thickness as f64, radius + 0.5 * thickness as f64);
+            for i in 0..self.ticks {
+                let fraction = if self.ticks > 1 { i as f32 / (self.ticks - 1) as f32 } else { 0.0 };
+                let angle = ((self.start_angle + fraction * self.sweep) as f64 - 90.0) * PI / 180.0;
+                let direction = kurbo::Vec2::new(angle.cos(), angle.sin());
+                let tick = kurbo::Line::new(center + inner * direction, center + outer * direction);
+                if let Some(path) = to_tiny_skia_path(tick) {
+                    context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+                }
+            }
+        }
+    }
+}
+
+impl<A: 'static> From<Gauge<A>> for Element<A> {
+    fn from(value: Gauge<A>) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Computed2, Derived, ReadableSignal};
+use crate::widgets::Widget;
+
+
+/// Maps values between `min` and `max` onto a gradient from `low` to `high`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ColorScale {
+    pub min: f32,
+    pub max: f32,
+    pub low: Color,
+    pub high: Color
+}
+
+impl ColorScale {
+    pub fn new(min: f32, max: f32, low: Color, high: Color) -> ColorScale {
+        ColorScale { min, max, low, high }
+    }
+
+    pub fn color(&self, value: f32) -> Color {
+        let t = if self.max > self.min { (value - self.min) / (self.max - self.min) } else { 0.0 };
+        self.low.lerp(self.high, t)
+    }
+}
+
+impl Default for ColorScale {
+    fn default() -> Self {
+        ColorScale::new(0.0, 1.0, Color::WHITE, Color::RED)
+    }
+}
+
+/// A grid of cells colored by value, given as rows of values.
+pub struct Heatmap<A> {
+    style: LayoutStyle,
+    scale: ColorScale,
+    cell_size: f32,
+
+    values: Derived<A, Vec<Vec<f32>>>,
+    layout_cache: Computed2<LayoutInput, Layout>
+}
+
+impl<A> Heatmap<A> {
+    pub fn new(values: impl (Fn(&mut A) -> Vec<Vec<f32>>) + 'static) -> Heatmap<A> {
+        Heatmap {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            scale: ColorScale::default(),
+            cell_size: 8.0,
+
+            values: Derived::new(values),
+            layout_cache: Computed2::new()
+        }
+    }
+
+    pub fn with_scale(mut self, scale: ColorScale) -> Heatmap<A> {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the size of each cell, before scaling.
+    pub fn with_cell_size(mut self, cell_size: f32) -> Heatmap<A> {
+        self.cell_size = cell_size;
+        self
+    }
+
+    fn grid_size(&self) -> (usize, usize) {
+        self.values.with_untracked(|rows| (rows.len(), rows.iter().map(Vec::len).max().unwrap_or(0)))
+    }
+}
+
+impl<A> Widget<A> for Heatmap<A> {
+    fn update(&self, model: &mut A) {
+        self.values.maybe_update(model);
+        self.values.track()
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.values.track();
+        let (rows, columns) = self.grid_size();
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| {
+            scale_factor * self.cell_size * math::Size::new(columns as f32, rows as f32)
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        InteractSet::empty()
+    }
+
+    fn handle_interaction(&mut self, _interaction: &Interaction, _model: &mut A) {
+
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let area = layout.content_box;
+        let (rows, columns) = self.grid_size();
+        if rows == 0 || columns == 0 {
+            return;
+        }
+
+        // cells stretch to fill the allocated area, which is at least the requested size
+        let cell_width = area.width() / columns as f32;
+        let cell_height = area.height() / rows as f32;
+        self.values.with_untracked(|values| {
+            for (row, values) in values.iter().enumerate() {
+                for (column, &value) in values.iter().enumerate() {
+                    let cell = tiny_skia::Rect::from_xywh(
+                        area.left() + column as f32 * cell_width,
+                        area.top() + row as f32 * cell_height,
+                        cell_width, cell_height
+                    );
+                    if let Some(cell) = cell {
+                        let mut paint = tiny_skia::Paint::default();
+                        paint.set_color(self.scale.color(value).into());
+                        context.canvas.fill_rect(cell, &paint, tiny_skia::Transform::identity(), None);
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl<A: 'static> From<Heatmap<A>> for Element<A> {
+    fn from(value: Heatmap<A>) -> Self {
+        Element::new(value)
+    }
+}
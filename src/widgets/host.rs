@@ -0,0 +1,216 @@
+use tiny_skia::{PixmapMut, PixmapPaint, Transform};
+
+use crate::{Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::focus::{self, FocusId};
+use crate::interact::{Capability, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::shortcut::{KeyInput, Modifiers};
+use crate::tracking::{Computed, Computed2, ReadableSignal, RwSignal};
+use crate::widgets::Widget;
+
+
+/// A UI from another (usually immediate-mode) toolkit, hosted inside a `Host` element.
+///
+/// Positions passed to the UI are relative to the top left of the hosted area, in physical pixels.
+/// Unless noted otherwise, the input handlers return whether the UI changed and should be redrawn,
+/// and ignore the input by default.
+pub trait HostedUi<A> {
+    /// The size the UI asks for, before scaling.
+    fn preferred_size(&self) -> math::Size;
+
+    /// Handles a click.
+    fn click(&mut self, model: &mut A, position: math::Point) -> bool;
+
+    /// Handles the cursor moving over the hosted area, or leaving it if `position` is `None`.
+    fn hover(&mut self, _model: &mut A, _position: Option<math::Point>) -> bool { false }
+
+    /// Handles the primary button going down over the hosted area.
+    fn press(&mut self, _model: &mut A, _position: math::Point) -> bool { false }
+
+    /// Handles the cursor moving while the button pressed over the hosted area is held. The
+    /// cursor may have left the area since.
+    fn drag(&mut self, _model: &mut A, _position: math::Point) -> bool { false }
+
+    /// Handles the button pressed over the hosted area going up, just before the `click` if it
+    /// goes up over the area.
+    fn release(&mut self, _model: &mut A, _position: math::Point) -> bool { false }
+
+    /// Handles a scroll over the hosted area by `delta` physical pixels, see `Interaction::Scroll`.
+    fn scroll(&mut self, _model: &mut A, _delta: math::Vector, _position: math::Point) -> bool { false }
+
+    /// Handles a key press while the host holds the keyboard focus. Returns whether the UI acted
+    /// on the key, which also redraws it; keys it doesn't act on go on to input scopes and
+    /// shortcuts, see `Root::handle_key`.
+    fn key(&mut self, _model: &mut A, _input: &KeyInput) -> bool { false }
+
+    /// Handles text typed while the host holds the keyboard focus.
+    fn text(&mut self, _model: &mut A, _text: &str, _modifiers: Modifiers) -> bool { false }
+
+    /// Draws the UI into `canvas`, which covers exactly the hosted area and starts out transparent.
+    fn paint(&mut self, canvas: &mut PixmapMut, scale_factor: f32);
+}
+
+/// Embeds a `HostedUi` in a yoru tree, forwarding input to it and compositing what it draws.
+///
+/// A host takes the keyboard focus when it is clicked, and sends the UI the keys and text typed
+/// while it holds it.
+pub struct Host<A, U> {
+    style: LayoutStyle,
+    ui: U,
+    focus_id: FocusId,
+    // whether the cursor was over the hosted area, to tell the UI once it leaves
+    hovered: bool,
+    // whether the primary button went down over the hosted area and is still held
+    dragging: bool,
+
+    // bumped whenever the hosted UI or the style changes, so that the tree is laid out and redrawn
+    revision: RwSignal<u64>,
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>,
+    _model: std::marker::PhantomData<fn(&mut A)>
+}
+
+impl<A, U: HostedUi<A>> Host<A, U> {
+    pub fn new(ui: U) -> Host<A, U> {
+        Host {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            ui,
+            focus_id: FocusId::unique(),
+            hovered: false,
+            dragging: false,
+
+            revision: RwSignal::new(0),
+            layout_cache: Computed2::new(),
+            interactions: Computed::new(),
+            _model: std::marker::PhantomData
+        }
+    }
+
+    pub fn set_width(&mut self, width: Sizing) {
+        self.style.width = width;
+        self.revision.update(|revision| *revision += 1);
+    }
+
+    pub fn set_height(&mut self, height: Sizing) {
+        self.style.height = height;
+        self.revision.update(|revision| *revision += 1);
+    }
+
+    pub fn ui(&self) -> &U {
+        &self.ui
+    }
+
+    /// Gives mutable access to the hosted UI, redrawing it afterwards.
+    pub fn ui_mut(&mut self) -> &mut U {
+        self.revision.update(|revision| *revision += 1);
+        &mut self.ui
+    }
+}
+
+impl<A, U: HostedUi<A>> Widget<A> for Host<A, U> {
+    fn update(&self, _model: &mut A) {
+        self.revision.track();
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.revision.track();
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| scale_factor * self.ui.preferred_size())
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.revision.track();
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn focus_id(&self) -> Option<FocusId> {
+        Some(self.focus_id)
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            let content_box = self.layout_cache.get().content_box;
+            InteractSet::empty()
+                .with(Capability::Click, content_box)
+                .with(Capability::Hover, content_box)
+                .with(Capability::Drag, content_box)
+                .with(Capability::Scroll, content_box)
+                .with(Capability::Keyboard, content_box)
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        let content_box = self.layout_cache.get_untracked().content_box;
+        let local = |point: math::Point| math::Point::new(point.x - content_box.left(), point.y - content_box.top());
+        let changed = match interaction {
+            &Interaction::Click(point) if content_box.contains(point) => self.ui.click(model, local(point)),
+            &Interaction::Hover(point) => {
+                let inside = point.filter(|&point| content_box.contains(point));
+                let was_hovered = std::mem::replace(&mut self.hovered, inside.is_some());
+                match inside {
+                    Some(point) => self.ui.hover(model, Some(local(point))),
+                    None if was_hovered => self.ui.hover(model, None),
+                    None => false
+                }
+            }
+            &Interaction::Press(point) if content_box.contains(point) => {
+                self.dragging = true;
+                self.ui.press(model, local(point))
+            }
+            &Interaction::Drag(point) if self.dragging => self.ui.drag(model, local(point)),
+            &Interaction::Release(point) if self.dragging => {
+                self.dragging = false;
+                self.ui.release(model, local(point))
+            }
+            &Interaction::Scroll { delta, position } if content_box.contains(position) => self.ui.scroll(model, delta, local(position)),
+            Interaction::KeyDown(input, target) if *target == self.focus_id => {
+                let used = self.ui.key(model, input);
+                if used {
+                    focus::mark_key_used();
+                }
+                used
+            }
+            Interaction::Text { text, modifiers, target } if *target == self.focus_id => self.ui.text(model, text, *modifiers),
+            _ => false
+        };
+        if changed {
+            self.revision.update(|revision| *revision += 1);
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let content_box = layout.content_box;
+        let (width, height) = (content_box.width().round() as u32, content_box.height().round() as u32);
+        let Some(mut pixmap) = context.pool.take_pixmap(width, height) else { return; };
+
+        self.ui.paint(&mut pixmap.as_mut(), layout.scale_factor);
+        context.canvas.draw_pixmap(
+            content_box.left().round() as i32, content_box.top().round() as i32,
+            pixmap.as_ref(), &PixmapPaint::default(), Transform::identity(), None
+        );
+        context.pool.give_pixmap(pixmap);
+    }
+}
+
+impl<A: 'static, U: HostedUi<A> + 'static> From<Host<A, U>> for Element<A> {
+    fn from(value: Host<A, U>) -> Self {
+        Element::new(value)
+    }
+}
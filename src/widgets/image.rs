@@ -0,0 +1,229 @@
+use std::path::Path;
+
+use tiny_skia::Pixmap;
+
+use crate::{Element, Error, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Computed2, ReadableSignal};
+use crate::widgets::Widget;
+
+
+/// How an `Image` is scaled into its content box.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ImageFit {
+    /// As large as fits entirely inside the box, keeping the aspect ratio.
+    #[default]
+    Contain,
+    /// As small as covers the whole box, keeping the aspect ratio and cutting off the rest.
+    Cover,
+    /// Stretched to the box.
+    Fill,
+    /// At its own size, centered and cut off at the box.
+    Fixed
+}
+
+/// How an `Image` picks its colors when it is drawn at another size than its own.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ImageSampling {
+    /// The color of the closest image pixel, which keeps pixel art and QR codes sharp.
+    Nearest,
+    /// A blend of the four closest image pixels, which smooths photos.
+    #[default]
+    Bilinear
+}
+
+/// Shows a raster image, e.g. one decoded with `from_png`, or `from_jpeg` with the `jpeg` feature.
+///
+/// Each pixel of the image counts as a logical pixel, so with `Sizing::Fit` the image asks for
+/// its own size. With `ImageFit::Contain` it asks for less when the space available is smaller,
+/// keeping its aspect ratio.
+pub struct Image {
+    style: LayoutStyle,
+    fit: ImageFit,
+    sampling: ImageSampling,
+    pixmap: Pixmap,
+
+    layout_cache: Computed2<LayoutInput, Layout>
+}
+
+impl Image {
+    /// Shows `pixmap`, whose pixels are in RGBA order like those `Pixmap::decode_png` gives.
+    pub fn new(mut pixmap: Pixmap) -> Image {
+        // the canvas is in BGRA order, see `Color`
+        for pixel in pixmap.data_mut().chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        Image {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            fit: ImageFit::Contain,
+            sampling: ImageSampling::Bilinear,
+            pixmap,
+
+            layout_cache: Computed2::new()
+        }
+    }
+
+    /// Decodes a PNG file held in memory.
+    pub fn from_png(data: &[u8]) -> crate::Result<Image> {
+        let pixmap = Pixmap::decode_png(data).map_err(|error| Error::InvalidImage(error.to_string()))?;
+        Ok(Image::new(pixmap))
+    }
+
+    /// Reads and decodes a PNG file.
+    pub fn load_png(path: impl AsRef<Path>) -> crate::Result<Image> {
+        Image::from_png(&std::fs::read(path)?)
+    }
+
+    /// Decodes a JPEG file held in memory. Grayscale and CMYK images are converted to RGB.
+    #[cfg(feature = "jpeg")]
+    pub fn from_jpeg(data: &[u8]) -> crate::Result<Image> {
+        let mut decoder = jpeg_decoder::Decoder::new(data);
+        let pixels = decoder.decode().map_err(|error| Error::InvalidImage(error.to_string()))?;
+        let info = decoder.info().ok_or_else(|| Error::InvalidImage("missing image header".to_string()))?;
+        let rgba: Vec<u8> = match info.pixel_format {
+            jpeg_decoder::PixelFormat::L8 => pixels.iter().flat_map(|&l| [l, l, l, 255]).collect(),
+            // in native byte order, of which only the most significant byte is kept
+            jpeg_decoder::PixelFormat::L16 => pixels.chunks_exact(2).flat_map(|l| {
+                let l = (u16::from_ne_bytes([l[0], l[1]]) >> 8) as u8;
+                [l, l, l, 255]
+            }).collect(),
+            jpeg_decoder::PixelFormat::RGB24 => pixels.chunks_exact(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255]).collect(),
+            jpeg_decoder::PixelFormat::CMYK32 => pixels.chunks_exact(4).flat_map(|cmyk| {
+                let channel = |color: u8| ((255 - color as u32) * (255 - cmyk[3] as u32) / 255) as u8;
+                [channel(cmyk[0]), channel(cmyk[1]), channel(cmyk[2]), 255]
+            }).collect()
+        };
+        let size = tiny_skia::IntSize::from_wh(info.width as u32, info.height as u32)
+            .ok_or_else(|| Error::InvalidImage("empty image".to_string()))?;
+        let pixmap = Pixmap::from_vec(rgba, size).ok_or_else(|| Error::InvalidImage("truncated image".to_string()))?;
+        Ok(Image::new(pixmap))
+    }
+
+    /// Reads and decodes a JPEG file.
+    #[cfg(feature = "jpeg")]
+    pub fn load_jpeg(path: impl AsRef<Path>) -> crate::Result<Image> {
+        Image::from_jpeg(&std::fs::read(path)?)
+    }
+
+    pub fn with_fit(mut self, fit: ImageFit) -> Image {
+        self.fit = fit;
+        self
+    }
+
+    /// Sets how the image is sampled when scaled, bilinear by default.
+    pub fn with_sampling(mut self, sampling: ImageSampling) -> Image {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> Image {
+        self.style.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: Sizing) -> Image {
+        self.style.height = height;
+        self
+    }
+
+    fn natural_size(&self, scale_factor: f32) -> math::Size {
+        math::Size::new(self.pixmap.width() as f32 * scale_factor, self.pixmap.height() as f32 * scale_factor)
+    }
+
+    // the scale from image pixels to physical pixels along each axis when drawn into `area`
+    fn scale(&self, area: math::Size, scale_factor: f32) -> (f32, f32) {
+        let (width, height) = (self.pixmap.width() as f32, self.pixmap.height() as f32);
+        let (x, y) = (area.width() / width, area.height() / height);
+        match self.fit {
+            ImageFit::Contain => (x.min(y), x.min(y)),
+            ImageFit::Cover => (x.max(y), x.max(y)),
+            ImageFit::Fill => (x, y),
+            ImageFit::Fixed => (scale_factor, scale_factor)
+        }
+    }
+}
+
+impl<A: 'static> From<Image> for Element<A> {
+    fn from(value: Image) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A> Widget<A> for Image {
+    fn update(&self, _model: &mut A) {
+
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&self.style, input, |available, scale_factor| {
+            let natural = self.natural_size(scale_factor);
+            if self.fit != ImageFit::Contain {
+                return natural;
+            }
+            // infinite along an axis the image can grow in, e.g. inside a scroll view
+            let shrink = (available.width() / natural.width()).min(available.height() / natural.height()).min(1.0);
+            if shrink.is_finite() && shrink > 0.0 {
+                math::Size::new(natural.width() * shrink, natural.height() * shrink)
+            } else {
+                natural
+            }
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        InteractSet::empty()
+    }
+
+    fn handle_interaction(&mut self, _interaction: &Interaction, _model: &mut A) {
+
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let area = layout.content_box;
+        if self.pixmap.width() == 0 || self.pixmap.height() == 0 {
+            return;
+        }
+
+        let (scale_x, scale_y) = self.scale(area.size(), layout.scale_factor);
+        let (width, height) = (self.pixmap.width() as f32 * scale_x, self.pixmap.height() as f32 * scale_y);
+        let left = area.left() + 0.5 * (area.width() - width);
+        let top = area.top() + 0.5 * (area.height() - height);
+
+        // covering and fixed images may be larger than the box, so only the overlap is filled
+        let visible = math::Rect::from_lrtb(
+            left.max(area.left()), (left + width).min(area.right()),
+            top.max(area.top()), (top + height).min(area.bottom())
+        );
+        let Ok(visible) = tiny_skia::Rect::try_from(visible) else { return; };
+        let transform = tiny_skia::Transform::from_row(scale_x, 0.0, 0.0, scale_y, left, top);
+        let quality = match self.sampling {
+            ImageSampling::Nearest => tiny_skia::FilterQuality::Nearest,
+            ImageSampling::Bilinear => tiny_skia::FilterQuality::Bilinear
+        };
+        let shader = tiny_skia::Pattern::new(self.pixmap.as_ref(), tiny_skia::SpreadMode::Pad, quality, 1.0, transform);
+        let paint = tiny_skia::Paint { shader, ..Default::default() };
+        context.canvas.fill_rect(visible, &paint, tiny_skia::Transform::identity(), None);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Element, math, RenderContext};
+use crate::interact::{Capability, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::shortcut::{Shortcut, Shortcuts};
+use crate::tracking::{Computed, Computed2, Derived, ReadableSignal};
+use crate::widgets::Widget;
+
+
+/// Claims the keyboard for a subtree while `active` holds, e.g. for a modal dialog or an open menu.
+///
+/// While any scope is active, key presses go to the active scope with the highest priority instead
+/// of the application's shortcuts, and only that scope's shortcuts run. Scopes nested inside it
+/// with the same or a higher priority take precedence over it. Once the scope is no longer active,
+/// keys go back to whichever scope (or the application) had them before.
+pub struct InputScope<A> {
+    inner: Element<A>,
+    active: Derived<A, bool>,
+    priority: u32,
+    shortcuts: Shortcuts<A>,
+
+    layout_cache: Computed2<LayoutInput, math::Rect>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A: 'static> InputScope<A> {
+    pub fn new(inner: impl Into<Element<A>>, active: impl (Fn(&mut A) -> bool) + 'static) -> InputScope<A> {
+        InputScope {
+            inner: inner.into(),
+            active: Derived::new(active),
+            priority: 1,
+            shortcuts: Shortcuts::new(),
+
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Sets the priority of the scope, at least 1. Scopes with a higher priority win over other
+    /// active scopes regardless of where they are in the tree.
+    pub fn with_priority(mut self, priority: u32) -> InputScope<A> {
+        self.priority = priority.max(1);
+        self
+    }
+
+    pub fn with_shortcut(mut self, shortcut: Shortcut, action: impl Fn(&mut A) + 'static) -> InputScope<A> {
+        self.shortcuts.add(shortcut, action);
+        self
+    }
+}
+
+impl<A> Widget<A> for InputScope<A> {
+    fn update(&self, model: &mut A) {
+        self.active.maybe_update(model);
+        self.inner.update(model);
+        self.active.track();
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.inner.prelayout(ctx, input)
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.inner.layout(ctx, input);
+            input.allocated
+        });
+        self.layout_cache.track();
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner)
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            let mut set = self.inner.interactions();
+            if self.active.get() {
+                set = set.with(Capability::Keyboard, self.layout_cache.get());
+                set.key_scope = set.key_scope.max(self.priority);
+            }
+            set
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match interaction {
+            &Interaction::Key(input, scope) => {
+                if self.inner.interactions().key_scope == scope {
+                    self.inner.handle_interaction(interaction, model);
+                } else if self.active.get_untracked() && self.priority == scope {
+                    self.shortcuts.handle(&input, model);
+                }
+            }
+            _ => self.inner.handle_interaction(interaction, model)
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.inner.draw(context)
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+}
+
+impl<A: 'static> From<InputScope<A>> for Element<A> {
+    fn from(value: InputScope<A>) -> Self {
+        Element::new(value)
+    }
+}
@@ -1,155 +1,364 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
-use crate::{Color, LayoutCharacteristics, Element, PrelayoutInput, LayoutStyle, math, RenderContext, Sizing, layout, Layout};
-use crate::interact::{Interaction, InteractSet};
-use crate::layout::LayoutInput;
-use crate::tracking::{Computed2, Derived, ReadableSignal, RwSignal};
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use crate::{Color, LayoutCharacteristics, Element, PrelayoutInput, LayoutStyle, math, RenderContext, Sizing, PointerEvents, Position, layout, Layout};
+use crate::clipboard;
+use crate::focus::{self, FocusId};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutContext, LayoutInput, TextMetrics};
+use crate::shortcut::Shortcut;
+use crate::text::{self, Truncation};
+use crate::tracking::{Bind, Computed2, Derived, ReadableSignal, RwSignal};
 use crate::widgets::Widget;
 
-thread_local! {
-    static FONTS: RefCell<cosmic_text::FontSystem> = RefCell::new(cosmic_text::FontSystem::new());
-    static GLYPH_CACHE: RefCell<GlyphCache> = RefCell::new(GlyphCache::new());
-}
-
 
-struct CachedGlyph {
-    offset: (i32, i32),
-    image: Option<tiny_skia::Pixmap>
+/// What a `Label` shows of text which doesn't fit.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum TextOverflow {
+    /// Leaves out whatever doesn't fit.
+    #[default]
+    Clip,
+    /// Ends the last line shown with an ellipsis when text is left out.
+    Ellipsis
 }
 
-struct GlyphCache {
-    swash_cache: cosmic_text::SwashCache,
-    cached_glyphs: HashMap<cosmic_text::CacheKey, CachedGlyph>
+/// A run of text with its own style, for `Label::rich`. Whatever a span doesn't set comes from
+/// the label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub color: Option<Color>,
+    pub weight: Option<cosmic_text::Weight>,
+    pub italic: bool,
+    pub underline: bool
 }
 
-impl GlyphCache {
-    fn new() -> GlyphCache {
-        GlyphCache {
-            swash_cache: cosmic_text::SwashCache::new(),
-            cached_glyphs: HashMap::new()
-        }
+impl Span {
+    pub fn new(text: impl Into<String>) -> Span {
+        Span { text: text.into(), color: None, weight: None, italic: false, underline: false }
     }
 
-    fn get_glyph(&mut self, fonts: &mut cosmic_text::FontSystem, key: cosmic_text::CacheKey) -> &CachedGlyph {
-        self.cached_glyphs.entry(key)
-            .or_insert_with_key(|&key| Self::render(fonts, &mut self.swash_cache, key))
+    pub fn with_color(mut self, color: Color) -> Span {
+        self.color = Some(color);
+        self
     }
 
-    fn render(fonts: &mut cosmic_text::FontSystem, swash_cache: &mut cosmic_text::SwashCache, key: cosmic_text::CacheKey) -> CachedGlyph {
-        if let Some(swash_image) = swash_cache.get_image_uncached(fonts, key) {
-            if let Some(mut image) = tiny_skia::Pixmap::new(swash_image.placement.width, swash_image.placement.height) {
-                let mask = tiny_skia::Mask::from_vec(swash_image.data, tiny_skia::IntSize::from_wh(swash_image.placement.width, swash_image.placement.height).unwrap()).unwrap();
-                let mut paint = tiny_skia::Paint::default();
-                paint.set_color(Color::BLACK.into());
+    pub fn with_weight(mut self, weight: cosmic_text::Weight) -> Span {
+        self.weight = Some(weight);
+        self
+    }
 
-                image.fill_rect(tiny_skia::Rect::from_xywh(
-                    0.0, 0.0,
-                    swash_image.placement.width as f32,
-                    swash_image.placement.height as f32
-                ).unwrap(), &paint, tiny_skia::Transform::identity(), Some(&mask));
+    pub fn bold(self) -> Span {
+        self.with_weight(cosmic_text::Weight::BOLD)
+    }
 
-                CachedGlyph {
-                    offset: (swash_image.placement.left, swash_image.placement.top),
-                    image: Some(image)
-                }
-            } else {
-                CachedGlyph {
-                    offset: (swash_image.placement.left, swash_image.placement.top),
-                    image: None
-                }
-            }
-        } else {
-            CachedGlyph {
-                offset: (0, 0),
-                image: None
-            }
-        }
+    pub fn italic(mut self) -> Span {
+        self.italic = true;
+        self
     }
-}
 
+    pub fn underline(mut self) -> Span {
+        self.underline = true;
+        self
+    }
+}
 
 pub struct Label<A> {
     style: LayoutStyle,
-    font_size: f32,
+    // the theme's font size and foreground color if not set
+    font_size: Option<f32>,
+    color: Option<Color>,
+    family: Option<String>,
+    weight: cosmic_text::Weight,
+    italic: bool,
+    max_lines: Option<usize>,
+    overflow: Option<TextOverflow>,
+    // only selectable labels can take the focus, which they need to see Ctrl+C
+    focus_id: Option<FocusId>,
 
-    text: Derived<A, String>,
+    text: Derived<A, Vec<Span>>,
+    // bumped only when the text actually changes, so recomputing an equal string doesn't relayout
+    revision: RwSignal<u64>,
 
-    sizing_buffer: RwSignal<cosmic_text::Buffer>,
+    // the buffers are shaped lazily since fonts are only available during layout and drawing
+    sizing_buffer: RefCell<cosmic_text::Buffer>,
+    sizing_buffer_stale: Cell<bool>,
     buffer: RefCell<cosmic_text::Buffer>,
+    buffer_stale: Cell<bool>,
+    // shaped along with `buffer`, since it uses the same font
+    ellipsis_buffer: RefCell<cosmic_text::Buffer>,
+
+    // the anchor and the moving end, as positions in `buffer`
+    selection: Cell<Option<(cosmic_text::Cursor, cosmic_text::Cursor)>>,
+    selecting: bool,
+    // bumped whenever the selection changes, so that it's redrawn
+    selection_revision: RwSignal<u64>,
 
-    prelayout_cache: Computed2<PrelayoutInput, math::Size>,
+    prelayout_cache: Computed2<PrelayoutInput, (math::Size, Option<TextMetrics>)>,
     layout_cache: Computed2<LayoutInput, Layout>
 }
 
 impl<A> Label<A> {
     pub fn new(compute: impl (Fn(&mut A) -> String) + 'static) -> Label<A> {
+        Label::rich(move |model| vec![Span::new(compute(model))])
+    }
+
+    /// A label whose text is made of spans with different styles, e.g. a chat message with a bold
+    /// sender name.
+    pub fn rich(compute: impl (Fn(&mut A) -> Vec<Span>) + 'static) -> Label<A> {
         let font_size = 15.0;
+        // replaced before shaping, once the font size is known
         let default_metrics = cosmic_text::Metrics { font_size, line_height: font_size };
 
-        let sizing_buffer = FONTS.with_borrow_mut(|fonts| {
-            let mut buffer = cosmic_text::Buffer::new(fonts, default_metrics);
-            buffer.set_size(fonts, f32::INFINITY, f32::INFINITY);
-            buffer
-        });
-
         Label {
             style: LayoutStyle {
                 border_size: 0.0,
                 padding: 0.0.into(),
                 margin: 0.0.into(),
                 width: Sizing::Fit,
-                height: Sizing::Fit
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
             },
-            font_size,
+            font_size: None,
+            color: None,
+            family: None,
+            weight: cosmic_text::Weight::NORMAL,
+            italic: false,
+            max_lines: None,
+            overflow: None,
+            focus_id: None,
             text: Derived::new(compute),
-            sizing_buffer: RwSignal::new(sizing_buffer),
-            buffer: RefCell::new(FONTS.with_borrow_mut(|fonts| {
-                cosmic_text::Buffer::new(fonts, default_metrics)
-            })),
+            revision: RwSignal::new(0),
+            sizing_buffer: RefCell::new(cosmic_text::Buffer::new_empty(default_metrics)),
+            sizing_buffer_stale: Cell::new(true),
+            buffer: RefCell::new(cosmic_text::Buffer::new_empty(default_metrics)),
+            buffer_stale: Cell::new(true),
+            ellipsis_buffer: RefCell::new(cosmic_text::Buffer::new_empty(default_metrics)),
+
+            selection: Cell::new(None),
+            selecting: false,
+            selection_revision: RwSignal::new(0),
 
             prelayout_cache: Computed2::new(),
             layout_cache: Computed2::new()
         }
     }
+
+    /// A label showing the current value of a bound signal.
+    pub fn bind<T: Display + Clone + 'static>(bind: Bind<A, T>) -> Label<A> where A: 'static {
+        let get = bind.getter();
+        Label::new(move |model| get(model).to_string())
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Label<A> {
+        self.font_size = Some(font_size);
+        self.font_changed();
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Label<A> {
+        self.color = Some(color);
+        self
+    }
+
+    /// Uses the font family with this name, falling back to the default font if it isn't installed.
+    pub fn with_family(mut self, family: impl Into<String>) -> Label<A> {
+        self.family = Some(family.into());
+        self.font_changed();
+        self
+    }
+
+    pub fn with_weight(mut self, weight: cosmic_text::Weight) -> Label<A> {
+        self.weight = weight;
+        self.font_changed();
+        self
+    }
+
+    pub fn with_italic(mut self, italic: bool) -> Label<A> {
+        self.italic = italic;
+        self.font_changed();
+        self
+    }
+
+    /// Shows at most `max_lines` lines of the wrapped text.
+    pub fn with_max_lines(mut self, max_lines: usize) -> Label<A> {
+        self.max_lines = Some(max_lines.max(1));
+        self.font_changed();
+        self
+    }
+
+    /// Cuts the text off at the edge of the label's content box as `overflow` says. The label
+    /// then never asks for more width than it is offered, instead of growing to fit a long word.
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Label<A> {
+        self.overflow = Some(overflow);
+        self.font_changed();
+        self
+    }
+
+    /// Lets the text be selected by dragging over it. Clicking the label focuses it, and the
+    /// platform's copy shortcut then puts the selected text on the clipboard, see `clipboard`.
+    pub fn with_selectable(mut self, selectable: bool) -> Label<A> {
+        self.focus_id = selectable.then(FocusId::unique);
+        self
+    }
+
+    fn set_selection(&self, selection: Option<(cosmic_text::Cursor, cosmic_text::Cursor)>) {
+        if self.selection.replace(selection) != selection {
+            self.selection_revision.update(|revision| *revision += 1);
+        }
+    }
+
+    // the selection from its first to its last position, unless it is empty
+    fn selected_range(&self) -> Option<(cosmic_text::Cursor, cosmic_text::Cursor)> {
+        let (anchor, end) = self.selection.get()?;
+        match (anchor.line, anchor.index).cmp(&(end.line, end.index)) {
+            std::cmp::Ordering::Less => Some((anchor, end)),
+            std::cmp::Ordering::Greater => Some((end, anchor)),
+            std::cmp::Ordering::Equal => None
+        }
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.selected_range()?;
+        let buffer = self.buffer.borrow();
+        let mut text = String::new();
+        for line in start.line..=end.line {
+            let line_text = buffer.lines[line].text();
+            let from = if line == start.line { start.index } else { 0 };
+            let to = if line == end.line { end.index } else { line_text.len() };
+            if line > start.line {
+                text.push('\n');
+            }
+            text.push_str(&line_text[from..to]);
+        }
+        Some(text)
+    }
+
+    // the position in the text under `point`, as the label was last drawn
+    fn hit(&self, point: math::Point) -> Option<cosmic_text::Cursor> {
+        let content_box = self.layout_cache.get_untracked().content_box;
+        self.buffer.borrow().hit(point.x - content_box.left(), point.y - content_box.top())
+    }
+
+    fn draw_selection(&self, context: &mut RenderContext, buffer: &cosmic_text::Buffer) {
+        let Some((start, end)) = self.selected_range() else { return; };
+        let content_box = self.layout_cache.get_untracked().content_box;
+        let mut paint = tiny_skia::Paint::default();
+        paint.set_color(Color { a: 96, ..context.theme.accent }.into());
+        for run in buffer.layout_runs() {
+            let selected = |glyph: &&cosmic_text::LayoutGlyph| {
+                let position = (run.line_i, glyph.start);
+                position >= (start.line, start.index) && position < (end.line, end.index)
+            };
+            let (left, right) = run.glyphs.iter().filter(selected)
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(left, right), glyph| (left.min(glyph.x), right.max(glyph.x + glyph.w)));
+            let rect = tiny_skia::Rect::from_ltrb(
+                content_box.left() + left, content_box.top() + run.line_top,
+                content_box.left() + right, content_box.top() + run.line_top + buffer.metrics().line_height
+            );
+            if let Some(rect) = rect {
+                context.canvas.fill_rect(rect, &paint, tiny_skia::Transform::identity(), None);
+            }
+        }
+    }
+
+    // text is only cut off when asked for, otherwise it spills out of the content box as it did
+    fn truncates(&self) -> bool {
+        self.max_lines.is_some() || self.overflow.is_some()
+    }
+
+    // the text has to be shaped and measured again with the new font
+    fn font_changed(&self) {
+        self.sizing_buffer_stale.set(true);
+        self.buffer_stale.set(true);
+        self.revision.update(|revision| *revision += 1);
+    }
+
+    fn attrs(&self) -> cosmic_text::Attrs<'_> {
+        let style = if self.italic { cosmic_text::Style::Italic } else { cosmic_text::Style::Normal };
+        let attrs = cosmic_text::Attrs::new().weight(self.weight).style(style);
+        match &self.family {
+            Some(family) => attrs.family(cosmic_text::Family::Name(family)),
+            None => attrs
+        }
+    }
+
+    fn span_attrs(&self, span: &Span) -> cosmic_text::Attrs<'_> {
+        let mut attrs = self.attrs();
+        if let Some(color) = span.color {
+            attrs = attrs.color(color.into());
+        }
+        if let Some(weight) = span.weight {
+            attrs = attrs.weight(weight);
+        }
+        if span.italic {
+            attrs = attrs.style(cosmic_text::Style::Italic);
+        }
+        if span.underline {
+            attrs = attrs.metadata(text::UNDERLINE);
+        }
+        attrs
+    }
+
+    fn shape(&self, fonts: &mut cosmic_text::FontSystem, buffer: &mut cosmic_text::Buffer) {
+        self.text.with_untracked(|spans| {
+            let spans = spans.iter().map(|span| (span.text.as_str(), self.span_attrs(span)));
+            buffer.set_rich_text(fonts, spans, self.attrs(), cosmic_text::Shaping::Advanced);
+        });
+    }
 }
 
 impl<A> Widget<A> for Label<A> {
     fn update(&self, model: &mut A) {
-        if self.text.maybe_update(model) {
-            let new_value = self.text.get();
-            FONTS.with_borrow_mut(|fonts| {
-                self.buffer.borrow_mut().set_text(fonts, &new_value, cosmic_text::Attrs::new(), cosmic_text::Shaping::Advanced);
-                self.sizing_buffer.update(|buffer| buffer.set_text(fonts, &new_value, cosmic_text::Attrs::new(), cosmic_text::Shaping::Advanced));
-            });
+        if self.text.maybe_update_if_changed(model) {
+            self.sizing_buffer_stale.set(true);
+            self.buffer_stale.set(true);
+            self.revision.update(|revision| *revision += 1);
+            self.set_selection(None);
         }
-        self.text.track()
+        self.text.track();
+        self.selection_revision.track()
     }
 
-    fn prelayout(&self, input: PrelayoutInput) -> LayoutCharacteristics {
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
         self.prelayout_cache.maybe_update(input, |&input| {
-            self.text.track();
+            self.revision.track();
+            // tracked, so that the label is measured again when the theme changes
+            let font_size = self.font_size.unwrap_or_else(|| ctx.theme().font_size);
+            let mut text_metrics = None;
             let characteristics = layout::leaf::do_prelayout(&self.style, input, |available, scale_factor| {
-                FONTS.with_borrow_mut(|fonts| {
-                    self.sizing_buffer.update(|buffer| buffer.set_metrics_and_size(
+                ctx.text.with_fonts(|fonts| {
+                    let mut buffer = self.sizing_buffer.borrow_mut();
+                    if self.sizing_buffer_stale.replace(false) {
+                        self.shape(fonts, &mut buffer);
+                    }
+                    buffer.set_metrics_and_size(
                         fonts,
-                        cosmic_text::Metrics::new(self.font_size * scale_factor, self.font_size * scale_factor),
+                        cosmic_text::Metrics::new(font_size * scale_factor, font_size * scale_factor),
                         available.width(), available.height()
-                    ));
-                    let min_size = self.sizing_buffer.with(|buffer| {
-                        let max_width = buffer.layout_runs().map(|run| run.line_w).max_by(f32::total_cmp).unwrap_or(0.0);
-                        let total_height = buffer.layout_runs().len() as f32 * buffer.metrics().line_height;
-                        math::Size::new(max_width, total_height)
+                    );
+                    let max_lines = self.max_lines.unwrap_or(usize::MAX);
+                    let mut max_width = buffer.layout_runs().take(max_lines).map(|run| run.line_w).max_by(f32::total_cmp).unwrap_or(0.0);
+                    if self.truncates() {
+                        max_width = max_width.min(available.width());
+                    }
+                    let total_height = buffer.layout_runs().take(max_lines).count() as f32 * buffer.metrics().line_height;
+                    text_metrics = buffer.layout_runs().next().and_then(|run| {
+                        let line = buffer.lines[run.line_i].layout_opt().as_ref()?.first()?;
+                        let content_top = scale_factor * (self.style.margin.top + self.style.border_size + self.style.padding.top);
+                        Some(TextMetrics { ascent: line.max_ascent, descent: line.max_descent, first_baseline: content_top + run.line_y })
                     });
-                    min_size
+                    math::Size::new(max_width, total_height)
                 })
             });
-            characteristics.min_size
+            (characteristics.min_size, text_metrics)
         });
 
-        LayoutCharacteristics { layout_style: &self.style, min_size: self.prelayout_cache.get() }
+        let (min_size, text_metrics) = self.prelayout_cache.get();
+        LayoutCharacteristics { layout_style: self.style, min_size, text_metrics }
     }
 
-    fn layout(&self, input: LayoutInput) {
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
         self.layout_cache.maybe_update(input, |&input| {
             self.prelayout_cache.track();
             layout::leaf::do_layout(&self.style, input);
@@ -158,48 +367,83 @@ impl<A> Widget<A> for Label<A> {
         self.layout_cache.track();
     }
 
-    fn interactions(&self) -> InteractSet {
-        InteractSet::empty()
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn focus_id(&self) -> Option<FocusId> {
+        self.focus_id
     }
 
-    fn handle_interaction(&mut self, _interaction: &Interaction, _model: &mut A) {
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        let over = self.layout_cache.get_untracked().border_box.contains(point);
+        (over && self.focus_id.is_some()).then_some(Cursor::Text)
+    }
 
+    fn interactions(&self) -> InteractSet {
+        if self.focus_id.is_none() {
+            return InteractSet::empty();
+        }
+        let border_box = self.layout_cache.get().border_box;
+        InteractSet::empty().with(Capability::Drag, border_box).with(Capability::Keyboard, border_box)
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, _model: &mut A) {
+        let Some(focus_id) = self.focus_id else { return; };
+        match interaction {
+            &Interaction::Press(point) if self.layout_cache.get_untracked().border_box.contains(point) => {
+                self.set_selection(self.hit(point).map(|cursor| (cursor, cursor)));
+                self.selecting = true;
+            }
+            &Interaction::Drag(point) if self.selecting => {
+                if let (Some((anchor, _)), Some(cursor)) = (self.selection.get(), self.hit(point)) {
+                    self.set_selection(Some((anchor, cursor)));
+                }
+            }
+            Interaction::Release(_) => self.selecting = false,
+            Interaction::KeyDown(input, target) if *target == focus_id && Shortcut::primary('c').matches(input) => {
+                if let Some(text) = self.selected_text() {
+                    clipboard::copy(text);
+                    focus::mark_key_used();
+                }
+            }
+            Interaction::Focus(focus) if *focus != Some(focus_id) => self.set_selection(None),
+            _ => {}
+        }
     }
 
     fn draw(&mut self, context: &mut RenderContext) {
         let layout = self.layout_cache.get_untracked();
-        FONTS.with_borrow_mut(|fonts| {
-            self.buffer.borrow_mut().set_metrics_and_size(
-                fonts,
-                cosmic_text::Metrics::new(self.font_size * layout.scale_factor, self.font_size * layout.scale_factor),
-                layout.content_box.width(), layout.content_box.height()
-            );
+        let font_size = self.font_size.unwrap_or(context.theme.font_size);
+        let metrics = cosmic_text::Metrics::new(font_size * layout.scale_factor, font_size * layout.scale_factor);
+        // when cutting text off, every line is laid out so that it's known whether any are hidden
+        let height = if self.truncates() { f32::MAX } else { layout.content_box.height() };
+        let mut buffer = self.buffer.borrow_mut();
+        let mut ellipsis = self.ellipsis_buffer.borrow_mut();
+        context.text.with_fonts(|fonts| {
+            if self.buffer_stale.replace(false) {
+                self.shape(fonts, &mut buffer);
+                ellipsis.set_text(fonts, "\u{2026}", self.attrs(), cosmic_text::Shaping::Advanced);
+            }
+            buffer.set_metrics_and_size(fonts, metrics, layout.content_box.width(), height);
+            if self.overflow == Some(TextOverflow::Ellipsis) {
+                ellipsis.set_metrics_and_size(fonts, metrics, f32::MAX, f32::MAX);
+            }
+        });
 
-            GLYPH_CACHE.with_borrow_mut(|glyph_cache| {
-                let mut paint = tiny_skia::Paint::default();
-                paint.set_color(Color::BLACK.into());
-                let content_top_left = layout.content_box.top_left();
-
-                for run in self.buffer.borrow().layout_runs() {
-                    for glyph in run.glyphs {
-                        let physical_glyph = glyph.physical((content_top_left.x, content_top_left.y), 1.0);
-
-                        let rendered_glyph = glyph_cache.get_glyph(fonts, physical_glyph.cache_key);
-                        if let Some(glyph_image) = &rendered_glyph.image {
-                            let x_off = content_top_left.x + glyph.x + glyph.x_offset;
-                            let y_off = content_top_left.y + glyph.y_offset + run.line_y;
-
-                            context.canvas.draw_pixmap(
-                                rendered_glyph.offset.0 + x_off as i32,
-                                -rendered_glyph.offset.1 + y_off as i32,
-                                glyph_image.as_ref(),
-                                &tiny_skia::PixmapPaint::default(), tiny_skia::Transform::identity(), None
-                            );
-                        }
-                    }
-                }
-            });
+        let truncation = self.truncates().then(|| {
+            // with a little slack, since the content box is the height of the lines it was measured with
+            let fitting_lines = (layout.content_box.height() / metrics.line_height + 0.01).floor().max(1.0) as usize;
+            Truncation {
+                max_lines: self.max_lines.unwrap_or(usize::MAX).min(fitting_lines),
+                width: layout.content_box.width(),
+                ellipsis: (self.overflow == Some(TextOverflow::Ellipsis)).then_some(&*ellipsis)
+            }
         });
+        self.draw_selection(context, &buffer);
+        let content_top_left = layout.content_box.top_left();
+        let color = self.color.unwrap_or(context.theme.foreground);
+        context.text.draw_buffer(&mut context.canvas, &buffer, (content_top_left.x, content_top_left.y), layout.scale_factor, color, truncation);
     }
 }
 
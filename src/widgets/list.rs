@@ -0,0 +1,197 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput, TextMetrics};
+use crate::math::Axis;
+use crate::print::BreakHint;
+use crate::style::{ContainerLayoutStyle, Direction, Justify, Wrap};
+use crate::tracking::{Computed, Computed2, ReadableSignal, RwSignal, TrackedVec};
+use crate::widgets::Widget;
+
+
+type ItemsLens<A, T> = dyn for<'a> Fn(&'a A) -> &'a TrackedVec<T>;
+type ItemView<A, T> = Box<dyn Fn(&T) -> Element<A>>;
+
+/// Shows one child per item of a `TrackedVec` in the model, keyed by an id taken from each item.
+///
+/// When the vector changes, children whose key is still present are kept and moved to their new
+/// position; only items with a new key get an element built by `view`, and children whose key
+/// is gone are dropped. Kept children hold on to their state and layout caches, so appending a
+/// message to a long log only builds and lays out the new one. An element shows the item it was
+/// built from, so an item whose contents change should get a new key or read signals inside it.
+pub struct List<A, T, K> {
+    style: ContainerLayoutStyle,
+    items: Box<ItemsLens<A, T>>,
+    key: Box<dyn Fn(&T) -> K>,
+    view: ItemView<A, T>,
+
+    children: RefCell<Vec<(K, Element<A>)>>,
+    // bumped whenever children are added, removed or reordered, so that layout is redone
+    revision: RwSignal<u64>,
+
+    update_cache: Computed<()>,
+    prelayout_cache: Computed2<PrelayoutInput, (math::Size, Option<TextMetrics>)>,
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A, T, K: Hash + Eq> List<A, T, K> {
+    pub fn new(
+        items: impl for<'a> Fn(&'a A) -> &'a TrackedVec<T> + 'static,
+        key: impl Fn(&T) -> K + 'static,
+        view: impl Fn(&T) -> Element<A> + 'static
+    ) -> List<A, T, K> {
+        List {
+            style: ContainerLayoutStyle {
+                layout_style: LayoutStyle {
+                    border_size: 0.0,
+                    padding: 0.0.into(),
+                    margin: 0.0.into(),
+                    width: Sizing::Fit,
+                    height: Sizing::Fit,
+                    pointer_events: PointerEvents::Auto,
+                    position: Position::Flow
+                },
+                main_axis: Axis::Vertical,
+                main_direction: Direction::Positive,
+                main_justify: Justify::Min,
+                cross_justify: Justify::Min,
+                wrap: Wrap::NoWrap,
+                gap: 0.0
+            },
+            items: Box::new(items),
+            key: Box::new(key),
+            view: Box::new(view),
+
+            children: RefCell::new(Vec::new()),
+            revision: RwSignal::new(0),
+
+            update_cache: Computed::new(),
+            prelayout_cache: Computed2::new(),
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Lays the children out along `axis` instead of vertically.
+    pub fn with_axis(mut self, axis: Axis) -> List<A, T, K> {
+        self.style.main_axis = axis;
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> List<A, T, K> {
+        self.style.layout_style.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: Sizing) -> List<A, T, K> {
+        self.style.layout_style.height = height;
+        self
+    }
+
+    // matches the children to the current items, keeping the ones whose key is still present
+    fn sync_children(&self, model: &A) {
+        (self.items)(model).with(|items| {
+            let mut children = self.children.borrow_mut();
+            let unchanged = children.len() == items.len()
+                && children.iter().zip(items).all(|((key, _), item)| *key == (self.key)(item));
+            if unchanged {
+                return;
+            }
+
+            let mut previous: HashMap<K, Element<A>> = children.drain(..).collect();
+            children.extend(items.iter().map(|item| {
+                let key = (self.key)(item);
+                let child = previous.remove(&key).unwrap_or_else(|| (self.view)(item));
+                (key, child)
+            }));
+            drop(children);
+            self.revision.update(|revision| *revision += 1);
+        });
+    }
+}
+
+impl<A, T, K: Hash + Eq> Widget<A> for List<A, T, K> {
+    fn update(&self, model: &mut A) {
+        self.update_cache.maybe_update(|| {
+            self.sync_children(model);
+            for (_, child) in self.children.borrow().iter() {
+                child.update(model);
+            }
+        });
+        self.update_cache.track();
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.prelayout_cache.maybe_update(input, |&input| {
+            self.revision.track();
+            let children = self.children.borrow();
+            let characteristics = layout::container::do_prelayout(&self.style, ctx, input, children.iter().map(|(_, child)| child));
+            (characteristics.min_size, characteristics.text_metrics)
+        });
+        let (min_size, text_metrics) = self.prelayout_cache.get();
+        LayoutCharacteristics {
+            layout_style: self.style.layout_style,
+            min_size,
+            text_metrics
+        }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.prelayout_cache.track();
+            let children = self.children.borrow();
+            let children_layouts = layout::container::do_layout(&self.style, ctx, input, children.iter().map(|(_, child)| child));
+            for ((_, child), child_layout) in children.iter().zip(children_layouts) {
+                child.layout(ctx, child_layout);
+            }
+            Layout::from_layout_input(&self.style.layout_style, input)
+        });
+        self.layout_cache.track()
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        self.children.borrow().iter().for_each(|(_, child)| f(child))
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            self.revision.track();
+            self.children.borrow().iter().fold(InteractSet::empty(), |set, (_, child)| set | child.interactions())
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        if self.interactions.get_untracked().accepts(interaction) {
+            for (_, child) in self.children.get_mut() {
+                child.handle_interaction(interaction, model);
+            }
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        for (_, child) in self.children.get_mut() {
+            child.draw(context);
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        for (_, child) in self.children.borrow().iter() {
+            child.page_breaks(hints);
+        }
+    }
+}
+
+impl<A: 'static, T: 'static, K: Hash + Eq + 'static> From<List<A, T, K>> for Element<A> {
+    fn from(value: List<A, T, K>) -> Self {
+        Element::new(value)
+    }
+}
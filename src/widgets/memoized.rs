@@ -0,0 +1,74 @@
+use std::cell::RefCell;
+
+use crate::{Element, RenderContext};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::widgets::Widget;
+
+
+/// Skips updating a subtree unless a key selected from the model has changed.
+///
+/// The selector runs on every update; the inner element is only updated when its result differs
+/// from the previous one. Signals the subtree reads are not tracked while it is skipped, so
+/// everything the subtree displays should be covered by the key.
+pub struct Memoized<A, K> {
+    inner: Element<A>,
+    selector: Box<dyn Fn(&mut A) -> K>,
+    key: RefCell<Option<K>>
+}
+
+impl<A: 'static, K: PartialEq + 'static> Memoized<A, K> {
+    pub fn new(inner: impl Into<Element<A>>, selector: impl (Fn(&mut A) -> K) + 'static) -> Memoized<A, K> {
+        Memoized {
+            inner: inner.into(),
+            selector: Box::new(selector),
+            key: RefCell::new(None)
+        }
+    }
+}
+
+impl<A, K: PartialEq> Widget<A> for Memoized<A, K> {
+    fn update(&self, model: &mut A) {
+        let key = (self.selector)(model);
+        if self.key.borrow().as_ref() == Some(&key) {
+            return;
+        }
+        self.inner.update(model);
+        *self.key.borrow_mut() = Some(key);
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.inner.prelayout(ctx, input)
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.inner.layout(ctx, input)
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner)
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.inner.interactions()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        self.inner.handle_interaction(interaction, model)
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.inner.draw(context)
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+}
+
+impl<A: 'static, K: PartialEq + 'static> From<Memoized<A, K>> for Element<A> {
+    fn from(value: Memoized<A, K>) -> Self {
+        Element::new(value)
+    }
+}
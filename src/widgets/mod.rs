@@ -1,23 +1,123 @@
+#[cfg(feature = "text")]
 mod label;
 mod div;
 mod select;
 mod button;
+mod canvas;
+mod checkbox;
+#[cfg(feature = "text")]
+mod context_menu;
+mod error_boundary;
+mod focusable;
+mod gauge;
+mod heatmap;
+mod host;
+mod image;
+mod input_scope;
+mod list;
+mod memoized;
+mod scroll_view;
+mod show;
+mod slider;
+mod split;
+mod stack;
+mod state;
+#[cfg(feature = "text")]
+mod toast_overlay;
+mod tooltip;
+mod tree_view;
+mod sparkline;
+mod virtual_list;
+mod modifier;
+#[cfg(feature = "text")]
+mod number_input;
+mod progress_bar;
+mod spinner;
+#[cfg(feature = "qr")]
+mod qr_code;
 
-use crate::RenderContext;
-use crate::layout::{LayoutCharacteristics, PrelayoutInput, LayoutInput};
-use crate::interact::{Interaction, InteractSet};
+use crate::{Element, math, RenderContext};
+use crate::focus::FocusId;
+use crate::layout::{Layout, LayoutCharacteristics, LayoutContext, PrelayoutInput, LayoutInput};
+use crate::interact::{Cursor, Interaction, InteractSet};
+use crate::print::BreakHint;
 
 pub use div::Div;
 pub use select::Select;
-pub use label::Label;
+#[cfg(feature = "text")]
+pub use label::{Label, Span, TextOverflow};
 pub use button::Button;
+pub use canvas::Canvas;
+pub use checkbox::Checkbox;
+#[cfg(feature = "text")]
+pub use context_menu::ContextMenu;
+pub use error_boundary::ErrorBoundary;
+pub use focusable::Focusable;
+pub use gauge::Gauge;
+pub use heatmap::{ColorScale, Heatmap};
+pub use host::{Host, HostedUi};
+pub use image::{Image, ImageFit, ImageSampling};
+pub use input_scope::InputScope;
+pub use list::List;
+pub use memoized::Memoized;
+#[cfg(feature = "text")]
+pub use number_input::NumberInput;
+pub use scroll_view::ScrollView;
+pub use show::Show;
+#[cfg(feature = "qr")]
+pub use qr_code::QrCode;
+#[cfg(feature = "qr")]
+pub use qrcodegen::QrCodeEcc;
+pub use slider::Slider;
+pub use split::Split;
+pub use progress_bar::ProgressBar;
+pub use spinner::Spinner;
+pub use stack::Stack;
+#[cfg(feature = "text")]
+pub use toast_overlay::ToastOverlay;
+pub use tooltip::Tooltip;
+pub use tree_view::TreeView;
+pub use sparkline::{Sparkline, SparklineKind};
+pub use virtual_list::{RowIndex, VirtualList};
 
 pub trait Widget<A> {
     fn update(&self, model: &mut A);
-    fn prelayout(&self, input: PrelayoutInput) -> LayoutCharacteristics;
-    fn layout(&self, input: LayoutInput);
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics;
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput);
     fn interactions(&self) -> InteractSet;
 
     fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A);
     fn draw(&mut self, context: &mut RenderContext);
+
+    /// Reports where this widget and its descendants prefer pages to be split, after layout.
+    fn page_breaks(&self, _hints: &mut Vec<BreakHint>) { }
+
+    /// The layout this widget was given in the last layout pass, for `Element::visit`. Wrappers
+    /// which only pass their layout on to a child leave it to the child.
+    fn last_layout(&self) -> Option<Layout> { None }
+
+    /// Calls `f` with each child this widget currently shows, for `Element::visit`.
+    fn for_each_child(&self, _f: &mut dyn FnMut(&Element<A>)) { }
+
+    /// Whether children may extend past this widget's content box on purpose because the widget
+    /// cuts them off there, like a scroll view.
+    fn clips_children(&self) -> bool { false }
+
+    /// How far past the rect it was laid out in the widget draws, e.g. for a shadow, in physical
+    /// pixels. When the widget changes, this much around it is drawn again, see
+    /// `Root::draw_damage`.
+    fn paint_outset(&self) -> math::SizeRect { 0.0.into() }
+
+    /// The id under which this widget can hold the keyboard focus. Clicking inside its keyboard
+    /// region focuses it.
+    fn focus_id(&self) -> Option<FocusId> { None }
+
+    /// The cursor to show while the pointer is at `point`, in physical pixels, or `None` to leave
+    /// it to the widgets around it. By default the last child which asks for one decides, since
+    /// later children are drawn on top.
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        let mut cursor = None;
+        self.for_each_child(&mut |child| cursor = child.cursor(point).or(cursor));
+        cursor
+    }
 }
\ No newline at end of file
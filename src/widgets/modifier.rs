@@ -0,0 +1,346 @@
+use std::cell::Cell;
+
+use tiny_skia::{FillRule, PixmapPaint, Transform};
+
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, RenderContext};
+use crate::interact::{Capability, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, LayoutNode, PrelayoutInput, TextMetrics};
+use crate::math::Axis;
+use crate::print::BreakHint;
+use crate::style::{ContainerLayoutStyle, Direction, Justify, Position, StylePatch, Wrap};
+use crate::tracking::{Computed, Computed2, Derived, ReadableSignal};
+use crate::widgets::div::to_tiny_skia_path;
+use crate::widgets::Widget;
+
+
+impl<A: 'static> Element<A> {
+    /// Surrounds the element with `padding`. The padded element keeps the element's sizing and
+    /// position.
+    pub fn padding(self, padding: impl Into<math::SizeRect>) -> Element<A> {
+        Element::new(Padded {
+            inner: self,
+            padding: padding.into(),
+            style: Cell::new(None),
+            prelayout_cache: Computed2::new(),
+            layout_cache: Computed2::new()
+        })
+    }
+
+    /// Fills the element's border box with `color` before drawing it.
+    pub fn background(self, color: Color) -> Element<A> {
+        Element::new(Decorated::new(self, Decoration::Background(color)))
+    }
+
+    /// Calls `on_click` whenever the element's border box is clicked.
+    pub fn on_click(self, on_click: impl Fn(&mut A) + 'static) -> Element<A> {
+        Element::new(Decorated::new(self, Decoration::OnClick(Box::new(on_click))))
+    }
+
+    /// Prevents the element from drawing outside its border box.
+    pub fn clip(self) -> Element<A> {
+        Element::new(Decorated::new(self, Decoration::Clip))
+    }
+
+    /// Draws the element with the given opacity, between 0 and 1.
+    pub fn opacity(self, opacity: f32) -> Element<A> {
+        Element::new(Decorated::new(self, Decoration::Opacity(opacity.clamp(0.0, 1.0))))
+    }
+
+    /// Takes the element out of its container's flow and places it against the container's edges
+    /// instead, e.g. with `right` and `bottom` set and `left` and `top` left as `None` for a
+    /// floating button in the bottom right corner.
+    pub fn position(self, position: Position) -> Element<A> {
+        Element::new(Decorated::new(self, Decoration::Position(position)))
+    }
+
+    /// Applies `patch` to the element whenever `condition` holds. The condition is reevaluated
+    /// when the signals it reads change.
+    pub fn style_when(self, condition: impl Fn(&mut A) -> bool + 'static, patch: StylePatch) -> Element<A> {
+        Element::new(Decorated::new(self, Decoration::When(Derived::new(condition), patch)))
+    }
+}
+
+
+struct Padded<A> {
+    inner: Element<A>,
+    padding: math::SizeRect,
+    // built from the inner element's style on every prelayout, so that changes to it carry over
+    style: Cell<Option<ContainerLayoutStyle>>,
+
+    prelayout_cache: Computed2<PrelayoutInput, (math::Size, Option<TextMetrics>)>,
+    layout_cache: Computed2<LayoutInput, Layout>
+}
+
+impl<A> Padded<A> {
+    fn style(&self, ctx: &LayoutContext, input: PrelayoutInput) -> ContainerLayoutStyle {
+        let inner_style = self.inner.prelayout(ctx, input).layout_style;
+        let style = ContainerLayoutStyle {
+            layout_style: LayoutStyle {
+                border_size: 0.0,
+                padding: self.padding,
+                margin: 0.0.into(),
+                width: inner_style.width,
+                height: inner_style.height,
+                pointer_events: inner_style.pointer_events,
+                position: inner_style.position
+            },
+            main_axis: Axis::Vertical,
+            main_direction: Direction::Positive,
+            main_justify: Justify::Min,
+            cross_justify: Justify::Min,
+            wrap: Wrap::NoWrap,
+            gap: 0.0
+        };
+        self.style.set(Some(style));
+        style
+    }
+}
+
+// the inner element of a `Padded`, which sits in the padding's flow since its position applies
+// to the padded element instead
+struct InFlow<'a, A>(&'a Element<A>);
+
+impl<A> LayoutNode<LayoutContext<'_>> for InFlow<'_, A> {
+    fn prelayout(&self, ctx: &LayoutContext<'_>, input: PrelayoutInput) -> LayoutCharacteristics {
+        let mut characteristics = self.0.prelayout(ctx, input);
+        characteristics.layout_style.position = Position::Flow;
+        characteristics
+    }
+}
+
+impl<A> Widget<A> for Padded<A> {
+    fn update(&self, model: &mut A) {
+        self.inner.update(model)
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        let style = self.style(ctx, input);
+        self.prelayout_cache.maybe_update(input, |&input| {
+            let characteristics = layout::container::do_prelayout(&style, ctx, input, &[InFlow(&self.inner)]);
+            (characteristics.min_size, characteristics.text_metrics)
+        });
+        let (min_size, text_metrics) = self.prelayout_cache.get();
+        LayoutCharacteristics { layout_style: style.layout_style, min_size, text_metrics }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.prelayout_cache.track();
+            let style = match self.style.get() {
+                Some(style) => style,
+                None => self.style(ctx, PrelayoutInput { available: input.allocated.size(), scale_factor: input.scale_factor })
+            };
+            let children_layout = layout::container::do_layout(&style, ctx, input, &[InFlow(&self.inner)]);
+            self.inner.layout(ctx, children_layout[0]);
+            Layout::from_layout_input(&style.layout_style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner)
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.inner.interactions()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        self.inner.handle_interaction(interaction, model)
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.inner.draw(context)
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+}
+
+
+enum Decoration<A> {
+    Background(Color),
+    OnClick(Box<dyn Fn(&mut A)>),
+    Clip,
+    Opacity(f32),
+    Position(Position),
+    When(Derived<A, bool>, StylePatch)
+}
+
+/// Decorates an element without changing its layout.
+struct Decorated<A> {
+    inner: Element<A>,
+    decoration: Decoration<A>,
+
+    inner_style: Cell<Option<LayoutStyle>>,
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Decorated<A> {
+    fn new(inner: Element<A>, decoration: Decoration<A>) -> Decorated<A> {
+        Decorated {
+            inner,
+            decoration,
+            inner_style: Cell::new(None),
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    fn draw_layer(&mut self, context: &mut RenderContext, opacity: f32, clip: Option<math::Rect>) {
+        draw_layer(context, opacity, clip, |context| self.inner.draw(context));
+    }
+}
+
+impl<A> Widget<A> for Decorated<A> {
+    fn update(&self, model: &mut A) {
+        if let Decoration::When(condition, _) = &self.decoration {
+            condition.maybe_update(model);
+            condition.track();
+        }
+        self.inner.update(model)
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        let mut characteristics = self.inner.prelayout(ctx, input);
+        self.inner_style.set(Some(characteristics.layout_style));
+        if let Decoration::Position(position) = self.decoration {
+            characteristics.layout_style.position = position;
+        }
+        characteristics
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.inner.layout(ctx, input);
+            self.inner_style.get()
+                .map(|style| Layout::from_layout_input(&style, input))
+                .unwrap_or_default()
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner)
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            let set = self.inner.interactions();
+            match self.decoration {
+                Decoration::OnClick(_) => {
+                    let this_set = InteractSet::empty().with(Capability::Click, self.layout_cache.get().border_box);
+                    this_set | set
+                }
+                _ => set
+            }
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        if let Decoration::OnClick(on_click) = &self.decoration {
+            if let Interaction::Click(point) = interaction {
+                if self.layout_cache.get_untracked().border_box.contains(*point) {
+                    on_click(model);
+                }
+            }
+        }
+        self.inner.handle_interaction(interaction, model);
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        match &self.decoration {
+            &Decoration::Background(color) => {
+                fill_background(context, layout.border_box, color);
+                self.inner.draw(context);
+            }
+            Decoration::OnClick(_) | Decoration::Position(_) => self.inner.draw(context),
+            Decoration::Clip => self.draw_layer(context, 1.0, Some(layout.border_box)),
+            &Decoration::Opacity(opacity) => self.draw_layer(context, opacity, None),
+            Decoration::When(condition, patch) => {
+                if !condition.get_untracked() {
+                    return self.inner.draw(context);
+                }
+                let patch = *patch;
+                if let Some(background) = patch.background {
+                    fill_background(context, layout.border_box, background);
+                }
+                match patch.opacity {
+                    Some(opacity) => self.draw_layer(context, opacity, None),
+                    None => self.inner.draw(context)
+                }
+                if let Some((color, width)) = patch.border {
+                    stroke_border(context, layout.border_box, color, width * layout.scale_factor);
+                }
+            }
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+}
+
+// draws to a separate layer, then composites it onto the canvas
+pub(super) fn draw_layer(context: &mut RenderContext, opacity: f32, clip: Option<math::Rect>, draw: impl FnOnce(&mut RenderContext)) {
+    let (width, height) = (context.canvas.width(), context.canvas.height());
+    let Some(mut layer) = context.pool.take_pixmap(width, height) else { return; };
+    draw(&mut RenderContext { canvas: layer.as_mut(), text: context.text, pool: context.pool, theme: context.theme, overlays: context.overlays });
+
+    let mask = clip.and_then(|clip| {
+        let mut mask = context.pool.take_mask(width, height)?;
+        if let Some(path) = to_tiny_skia_path(kurbo::Rect::from(clip)) {
+            mask.fill_path(&path, FillRule::Winding, true, Transform::identity());
+        }
+        Some(mask)
+    });
+    let paint = PixmapPaint { opacity, ..Default::default() };
+    context.canvas.draw_pixmap(0, 0, layer.as_ref(), &paint, Transform::identity(), mask.as_ref());
+
+    context.pool.give_pixmap(layer);
+    if let Some(mask) = mask {
+        context.pool.give_mask(mask);
+    }
+}
+
+fn fill_background(context: &mut RenderContext, rect: math::Rect, color: Color) {
+    if let Ok(rect) = tiny_skia::Rect::try_from(rect) {
+        let mut paint = tiny_skia::Paint::default();
+        paint.set_color(color.into());
+        context.canvas.fill_rect(rect, &paint, Transform::identity(), None);
+    }
+}
+
+// strokes just inside `rect` so the border does not overlap neighbouring elements
+fn stroke_border(context: &mut RenderContext, rect: math::Rect, color: Color, width: f32) {
+    if width <= 0.0 {
+        return;
+    }
+    let inset = rect.shrink_by((0.5 * width).into());
+    if let Some(path) = to_tiny_skia_path(kurbo::Rect::from(inset)) {
+        let stroke = tiny_skia::Stroke { width, ..Default::default() };
+        let mut paint = tiny_skia::Paint::default();
+        paint.set_color(color.into());
+        context.canvas.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    }
+}
+
+// draws to a layer which is composited over the whole tree once it has been drawn, see `Overlays`
+pub(super) fn draw_overlay(context: &mut RenderContext, draw: impl FnOnce(&mut RenderContext)) {
+    let (width, height) = (context.canvas.width(), context.canvas.height());
+    let Some(mut layer) = context.pool.take_pixmap(width, height) else { return; };
+    draw(&mut RenderContext { canvas: layer.as_mut(), text: context.text, pool: context.pool, theme: context.theme, overlays: context.overlays });
+    context.overlays.push(layer);
+}
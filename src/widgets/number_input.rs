@@ -0,0 +1,234 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::{Color, Element, Handled, RenderContext};
+use crate::interact::{Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::math::Axis;
+use crate::print::BreakHint;
+use crate::shortcut::{Key, KeyInput, NamedKey};
+use crate::style::Sizing;
+use crate::tracking::{ReadableSignal, RwSignal};
+use crate::widgets::{Button, Div, Focusable, Label, Span, Widget};
+
+
+// in logical pixels
+const FIELD_WIDTH: f32 = 64.0;
+// more decimals than this are noise from the step not being exact in binary
+const MAX_PRECISION: usize = 6;
+
+fn is_numeric(c: char) -> bool {
+    c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')
+}
+
+fn parse(text: &str) -> Option<f64> {
+    text.trim().parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+// shared by the field and the buttons, which each hold on to it from their handlers
+struct Shared<A> {
+    value: Box<dyn Fn(&mut A) -> f64>,
+    on_change: RefCell<Option<Box<dyn Fn(&mut A, f64)>>>,
+    min: Cell<f64>,
+    max: Cell<f64>,
+    step: Cell<f64>,
+    // the text being typed, until it is committed or reverted
+    draft: RwSignal<Option<String>>
+}
+
+impl<A> Shared<A> {
+    fn precision(&self) -> usize {
+        let step = self.step.get().to_string();
+        step.split_once('.').map_or(0, |(_, decimals)| decimals.len().min(MAX_PRECISION))
+    }
+
+    fn format(&self, value: f64) -> String {
+        format!("{:.*}", self.precision(), value)
+    }
+
+    fn set_draft(&self, draft: Option<String>) {
+        if self.draft.get_untracked() != draft {
+            self.draft.update(|current| *current = draft);
+        }
+    }
+
+    fn set(&self, model: &mut A, value: f64) {
+        let value = value.clamp(self.min.get(), self.max.get());
+        if value != (self.value)(model) {
+            if let Some(on_change) = &*self.on_change.borrow() {
+                on_change(model, value);
+            }
+        }
+    }
+
+    // text which doesn't parse is dropped, so the field goes back to showing the value
+    fn commit(&self, model: &mut A) {
+        if let Some(draft) = self.draft.get_untracked() {
+            self.set_draft(None);
+            if let Some(value) = parse(&draft) {
+                self.set(model, value);
+            }
+        }
+    }
+
+    fn step_by(&self, model: &mut A, steps: f64) {
+        self.commit(model);
+        let value = (self.value)(model) + steps * self.step.get();
+        // so that stepping by 0.1 lands on 0.3 rather than 0.30000000000000004
+        let scale = 10f64.powi(self.precision() as i32);
+        self.set(model, (value * scale).round() / scale);
+    }
+
+    fn edit(&self, model: &mut A, f: impl FnOnce(&mut String)) {
+        let mut draft = self.draft.get_untracked().unwrap_or_else(|| self.format((self.value)(model)));
+        f(&mut draft);
+        self.set_draft(Some(draft));
+    }
+
+    fn handle_key(&self, model: &mut A, input: &KeyInput) -> Handled {
+        match input.logical {
+            Key::Named(NamedKey::Enter) => self.commit(model),
+            Key::Named(NamedKey::Escape) => self.set_draft(None),
+            Key::Named(NamedKey::Backspace) => self.edit(model, |draft| { draft.pop(); }),
+            Key::Named(NamedKey::ArrowUp) => self.step_by(model, 1.0),
+            Key::Named(NamedKey::ArrowDown) => self.step_by(model, -1.0),
+            _ => return Handled::No
+        }
+        Handled::Yes
+    }
+}
+
+/// A field showing a number from the model, with buttons on either side which step it down and up.
+///
+/// Clicking the field focuses it so that a new number can be typed; Enter or moving the focus
+/// away commits it, Escape reverts it, and the arrow keys step the value. Typed text which isn't
+/// a number is shown in red and dropped when committed. Every new value is clamped to the range
+/// and handed to `on_change`, which is expected to store it where `value` reads it from.
+pub struct NumberInput<A> {
+    shared: Rc<Shared<A>>,
+    inner: Element<A>
+}
+
+impl<A: 'static> NumberInput<A> {
+    pub fn new(value: impl Fn(&mut A) -> f64 + 'static) -> NumberInput<A> {
+        let shared = Rc::new(Shared {
+            value: Box::new(value),
+            on_change: RefCell::new(None),
+            min: Cell::new(f64::NEG_INFINITY),
+            max: Cell::new(f64::INFINITY),
+            step: Cell::new(1.0),
+            draft: RwSignal::new(None)
+        });
+
+        let text = {
+            let shared = Rc::clone(&shared);
+            Label::rich(move |model| match shared.draft.get() {
+                Some(draft) if parse(&draft).is_none() => vec![Span::new(draft).with_color(Color::RED)],
+                Some(draft) => vec![Span::new(draft)],
+                None => vec![Span::new(shared.format((shared.value)(model)))]
+            })
+        };
+        let mut field = Div::new();
+        field.set_width(Sizing::Fixed(FIELD_WIDTH));
+        field.set_cursor(Cursor::Text);
+        field.add_child(text);
+        let field = {
+            let (on_key, on_text, on_focus) = (Rc::clone(&shared), Rc::clone(&shared), Rc::clone(&shared));
+            Focusable::new(field)
+                .on_key_down(move |model, input| on_key.handle_key(model, input))
+                .on_text(move |model, text, _| {
+                    on_text.edit(model, |draft| draft.extend(text.chars().filter(|&c| is_numeric(c))));
+                })
+                .on_focus_change(move |model, focused| {
+                    if !focused {
+                        on_focus.commit(model);
+                    }
+                })
+        };
+
+        let decrement = {
+            let shared = Rc::clone(&shared);
+            Button::text("-", move |model| shared.step_by(model, -1.0))
+        };
+        let increment = {
+            let shared = Rc::clone(&shared);
+            Button::text("+", move |model| shared.step_by(model, 1.0))
+        };
+
+        let mut row = Div::new();
+        row.set_main_axis(Axis::Horizontal);
+        row.bind_border_color(|_| None);
+        row.add_child(decrement);
+        row.add_child(field);
+        row.add_child(increment);
+
+        NumberInput { shared, inner: row.into() }
+    }
+}
+
+impl<A> NumberInput<A> {
+    /// Keeps the value at or above `min`.
+    pub fn with_min(self, min: f64) -> NumberInput<A> {
+        self.shared.min.set(min);
+        self
+    }
+
+    /// Keeps the value at or below `max`.
+    pub fn with_max(self, max: f64) -> NumberInput<A> {
+        self.shared.max.set(max);
+        self
+    }
+
+    /// Steps the value by `step` per button click or arrow key, 1 by default. The value is shown
+    /// with as many decimals as `step` has.
+    pub fn with_step(self, step: f64) -> NumberInput<A> {
+        self.shared.step.set(step.abs());
+        self
+    }
+
+    /// Calls `handler` with each new value, already clamped to the range.
+    pub fn on_change(self, handler: impl Fn(&mut A, f64) + 'static) -> NumberInput<A> {
+        *self.shared.on_change.borrow_mut() = Some(Box::new(handler));
+        self
+    }
+}
+
+impl<A: 'static> From<NumberInput<A>> for Element<A> {
+    fn from(value: NumberInput<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A> Widget<A> for NumberInput<A> {
+    fn update(&self, model: &mut A) {
+        self.inner.update(model)
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.inner.prelayout(ctx, input)
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.inner.layout(ctx, input)
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner)
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.inner.interactions()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        self.inner.handle_interaction(interaction, model)
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.inner.draw(context)
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+}
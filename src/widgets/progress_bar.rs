@@ -0,0 +1,111 @@
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Computed2, Derived, ReadableSignal};
+use crate::widgets::div::fill_rounded_rect;
+use crate::widgets::Widget;
+
+
+/// A horizontal bar which fills up from the left as a fraction between 0 and 1 grows, e.g. to
+/// show how far a long-running task has come.
+pub struct ProgressBar<A> {
+    style: LayoutStyle,
+    // in logical pixels
+    length: f32,
+    thickness: f32,
+    // the theme's track and accent colors if not set
+    colors: Option<(Color, Color)>,
+
+    fraction: Derived<A, f32>,
+    layout_cache: Computed2<LayoutInput, Layout>
+}
+
+impl<A> ProgressBar<A> {
+    pub fn new(fraction: impl (Fn(&mut A) -> f32) + 'static) -> ProgressBar<A> {
+        ProgressBar {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            length: 160.0,
+            thickness: 6.0,
+            colors: None,
+
+            fraction: Derived::new(fraction),
+            layout_cache: Computed2::new()
+        }
+    }
+
+    /// Sets the length the bar asks for and its thickness, before scaling.
+    pub fn with_size(mut self, length: f32, thickness: f32) -> ProgressBar<A> {
+        self.length = length;
+        self.thickness = thickness;
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> ProgressBar<A> {
+        self.style.width = width;
+        self
+    }
+
+    pub fn with_colors(mut self, track: Color, fill: Color) -> ProgressBar<A> {
+        self.colors = Some((track, fill));
+        self
+    }
+}
+
+impl<A> Widget<A> for ProgressBar<A> {
+    fn update(&self, model: &mut A) {
+        self.fraction.maybe_update(model);
+        self.fraction.track()
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| {
+            math::Size::new(scale_factor * self.length, scale_factor * self.thickness)
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        InteractSet::empty()
+    }
+
+    fn handle_interaction(&mut self, _interaction: &Interaction, _model: &mut A) {
+
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let area = self.layout_cache.get_untracked().content_box;
+        let radius = 0.5 * area.height();
+        let (track_color, fill_color) = self.colors.unwrap_or((context.theme.track, context.theme.accent));
+        fill_rounded_rect(context, area, radius, track_color);
+        let fraction = self.fraction.get_untracked().clamp(0.0, 1.0);
+        if fraction > 0.0 {
+            let filled = math::Rect::from_topleft_size(area.top_left(), math::Size::new(fraction * area.width(), area.height()));
+            fill_rounded_rect(context, filled, radius.min(0.5 * filled.width()), fill_color);
+        }
+    }
+}
+
+impl<A: 'static> From<ProgressBar<A>> for Element<A> {
+    fn from(value: ProgressBar<A>) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,173 @@
+use std::cell::{Cell, RefCell};
+
+use qrcodegen::QrCodeEcc;
+
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Computed2, Derived, ReadableSignal};
+use crate::widgets::Widget;
+
+
+// the specification asks for four light modules around the code
+const QUIET_ZONE: i32 = 4;
+
+type ValidityCallback<A> = Box<dyn Fn(&mut A, bool)>;
+
+/// Renders text from the model as a QR code.
+///
+/// Modules are snapped to whole pixels so the code stays crisp, and the code is centered in its
+/// layout box.
+pub struct QrCode<A> {
+    style: LayoutStyle,
+    module_size: f32,
+    error_correction: QrCodeEcc,
+    on_validity_change: Option<ValidityCallback<A>>,
+
+    text: Derived<A, String>,
+    code: RefCell<Option<qrcodegen::QrCode>>,
+    valid: Cell<bool>,
+    layout_cache: Computed2<LayoutInput, Layout>
+}
+
+impl<A> QrCode<A> {
+    /// Encodes the text `text` picks from the model. Text too long to fit in a QR code with the
+    /// chosen error correction leaves an empty box, which takes only the space the style asks
+    /// for; see `is_valid` and `on_validity_change` to notice it.
+    pub fn new(text: impl (Fn(&mut A) -> String) + 'static) -> QrCode<A> {
+        QrCode {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            module_size: 4.0,
+            error_correction: QrCodeEcc::Medium,
+            on_validity_change: None,
+
+            text: Derived::new(text),
+            code: RefCell::new(None),
+            valid: Cell::new(true),
+            layout_cache: Computed2::new()
+        }
+    }
+
+    /// Sets the size of a single module the code asks for, before scaling.
+    pub fn with_module_size(mut self, module_size: f32) -> QrCode<A> {
+        self.module_size = module_size;
+        self
+    }
+
+    pub fn with_error_correction(mut self, error_correction: QrCodeEcc) -> QrCode<A> {
+        self.error_correction = error_correction;
+        self
+    }
+
+    /// Calls `handler` with `false` when the text becomes too long to encode, and with `true`
+    /// once it fits again.
+    pub fn on_validity_change(mut self, handler: impl Fn(&mut A, bool) + 'static) -> QrCode<A> {
+        self.on_validity_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Whether the text last read from the model could be encoded, true before any was read.
+    pub fn is_valid(&self) -> bool {
+        self.valid.get()
+    }
+
+    fn modules(&self) -> i32 {
+        self.code.borrow().as_ref().map_or(0, |code| code.size() + 2 * QUIET_ZONE)
+    }
+}
+
+impl<A> Widget<A> for QrCode<A> {
+    fn update(&self, model: &mut A) {
+        if self.text.maybe_update_if_changed(model) {
+            let code = qrcodegen::QrCode::encode_text(&self.text.get_untracked(), self.error_correction).ok();
+            let valid = code.is_some();
+            *self.code.borrow_mut() = code;
+            if self.valid.replace(valid) != valid {
+                if let Some(on_validity_change) = &self.on_validity_change {
+                    on_validity_change(model, valid);
+                }
+            }
+        }
+        self.text.track()
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.text.track();
+        let modules = self.modules() as f32;
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| {
+            let side = (scale_factor * self.module_size).ceil() * modules;
+            math::Size::new(side, side)
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        InteractSet::empty()
+    }
+
+    fn handle_interaction(&mut self, _interaction: &Interaction, _model: &mut A) {
+
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let area = layout.content_box;
+        let code = self.code.borrow();
+        let Some(code) = code.as_ref() else { return; };
+
+        let modules = code.size() + 2 * QUIET_ZONE;
+        let module = (area.width().min(area.height()) / modules as f32).floor().max(1.0);
+        let side = module * modules as f32;
+        let left = (area.left() + 0.5 * (area.width() - side)).round();
+        let top = (area.top() + 0.5 * (area.height() - side)).round();
+
+        let mut paint = tiny_skia::Paint::default();
+        if let Some(background) = tiny_skia::Rect::from_xywh(left, top, side, side) {
+            paint.set_color(Color::WHITE.into());
+            context.canvas.fill_rect(background, &paint, tiny_skia::Transform::identity(), None);
+        }
+
+        let mut path = tiny_skia::PathBuilder::new();
+        for y in 0..code.size() {
+            for x in 0..code.size() {
+                if code.get_module(x, y) {
+                    let module_left = left + (x + QUIET_ZONE) as f32 * module;
+                    let module_top = top + (y + QUIET_ZONE) as f32 * module;
+                    if let Some(rect) = tiny_skia::Rect::from_xywh(module_left, module_top, module, module) {
+                        path.push_rect(rect);
+                    }
+                }
+            }
+        }
+        if let Some(path) = path.finish() {
+            paint.set_color(Color::BLACK.into());
+            paint.anti_alias = false;
+            context.canvas.fill_path(&path, &paint, tiny_skia::FillRule::Winding, tiny_skia::Transform::identity(), None);
+        }
+    }
+}
+
+impl<A: 'static> From<QrCode<A>> for Element<A> {
+    fn from(value: QrCode<A>) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,261 @@
+use std::cell::Cell;
+
+use crate::{Color, Element, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::math::Axis;
+use crate::print::BreakHint;
+use crate::tracking::{Computed, Computed2, Derived, ReadableSignal, RwSignal};
+use crate::widgets::modifier::draw_layer;
+use crate::widgets::Widget;
+
+
+pub(super) const SCROLLBAR_WIDTH: f32 = 4.0;
+pub(super) const SCROLLBAR_COLOR: Color = Color::from_rgba8(0, 0, 0, 96);
+
+/// Shows a part of an element which may be longer than the space available, and scrolls through
+/// it with the mouse wheel.
+///
+/// The inner element is measured as if it had unlimited space along the scroll axis, and is
+/// clipped to the scroll view's content box. A scroll view expands in both directions by default.
+pub struct ScrollView<A> {
+    inner: Element<A>,
+    style: LayoutStyle,
+    axis: Axis,
+    scrollbar: bool,
+    // in logical pixels, from the start of the inner element
+    offset: RwSignal<f32>,
+    offset_binding: Option<Derived<A, f32>>,
+    // the largest offset which still fills the viewport, updated on layout
+    max_offset: Cell<f32>,
+
+    prelayout_cache: Computed2<PrelayoutInput, math::Size>,
+    layout_cache: Computed2<LayoutInput, Layout>,
+    // the inner element's margin box, without the scroll offset
+    content_size: Cell<math::Size>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> ScrollView<A> {
+    pub fn new(inner: impl Into<Element<A>>) -> ScrollView<A> {
+        ScrollView {
+            inner: inner.into(),
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Expand(1.0),
+                height: Sizing::Expand(1.0),
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            axis: Axis::Vertical,
+            scrollbar: true,
+            offset: RwSignal::new(0.0),
+            offset_binding: None,
+            max_offset: Cell::new(0.0),
+
+            prelayout_cache: Computed2::new(),
+            layout_cache: Computed2::new(),
+            content_size: Cell::new(math::Size::new(0.0, 0.0)),
+            interactions: Computed::new()
+        }
+    }
+
+    pub fn with_axis(mut self, axis: Axis) -> ScrollView<A> {
+        self.axis = axis;
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> ScrollView<A> {
+        self.style.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: Sizing) -> ScrollView<A> {
+        self.style.height = height;
+        self
+    }
+
+    /// Whether to draw a thumb showing which part of the content is visible. On by default.
+    pub fn with_scrollbar(mut self, scrollbar: bool) -> ScrollView<A> {
+        self.scrollbar = scrollbar;
+        self
+    }
+
+    /// Scrolls to the offset computed from the model whenever a signal it reads changes, e.g. an
+    /// `AnimatedSignal` to scroll smoothly. The user can still scroll in between.
+    pub fn bind_offset(mut self, offset: impl Fn(&mut A) -> f32 + 'static) -> ScrollView<A> {
+        self.offset_binding = Some(Derived::new(offset));
+        self
+    }
+
+    /// How far the view is scrolled, in logical pixels.
+    pub fn offset(&self) -> f32 {
+        self.offset.get()
+    }
+
+    /// Scrolls to `offset` logical pixels from the start, as far as the content allows.
+    pub fn scroll_to(&self, offset: f32) {
+        let offset = offset.clamp(0.0, self.max_offset.get());
+        if offset != self.offset.get_untracked() {
+            self.offset.update(|current| *current = offset);
+        }
+    }
+
+    pub fn scroll_by(&self, delta: f32) {
+        self.scroll_to(self.offset.get_untracked() + delta);
+    }
+
+    // the size the inner element is measured with, given the size of the viewport
+    fn content_available(&self, viewport: math::Size) -> math::Size {
+        math::Size::from_axes(self.axis, f32::INFINITY, viewport.axis(self.axis.cross()))
+    }
+
+    fn viewport(&self) -> math::Rect {
+        self.layout_cache.get_untracked().content_box
+    }
+}
+
+impl<A: 'static> From<ScrollView<A>> for Element<A> {
+    fn from(value: ScrollView<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A> Widget<A> for ScrollView<A> {
+    fn update(&self, model: &mut A) {
+        if let Some(binding) = &self.offset_binding {
+            if binding.maybe_update(model) {
+                // not clamped to the content, which may not have been laid out yet; layout clamps it
+                let offset = binding.get_untracked().max(0.0);
+                if offset != self.offset.get_untracked() {
+                    self.offset.update(|current| *current = offset);
+                }
+            }
+            binding.track();
+        }
+        self.inner.update(model)
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.prelayout_cache.maybe_update(input, |&input| {
+            let spacing = input.scale_factor * self.style.spacing_size();
+            let viewport = input.available - spacing.sum_axes();
+            let available = self.content_available(viewport);
+            let content = self.inner.prelayout(ctx, PrelayoutInput { available, ..input }).min_size;
+            // along the scroll axis the view only needs as much as it can get
+            let main = content.axis(self.axis).min(viewport.axis(self.axis).max(0.0));
+            math::Size::from_axes(self.axis, main, content.axis(self.axis.cross())) + spacing.sum_axes()
+        });
+        LayoutCharacteristics { layout_style: self.style, min_size: self.prelayout_cache.get(), text_metrics: None }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.prelayout_cache.track();
+            let layout = Layout::from_layout_input(&self.style, input);
+            let viewport = layout.content_box;
+            let available = self.content_available(viewport.size());
+            let measured = self.inner.prelayout(ctx, PrelayoutInput { available, scale_factor: input.scale_factor }).min_size;
+            let main = measured.axis(self.axis).max(viewport.size().axis(self.axis));
+            let content = math::Size::from_axes(self.axis, main, viewport.size().axis(self.axis.cross()));
+            self.content_size.set(content);
+
+            let max_offset = ((main - viewport.size().axis(self.axis)) / input.scale_factor).max(0.0);
+            self.max_offset.set(max_offset);
+            let offset = self.offset.get().min(max_offset) * input.scale_factor;
+            let shift = match self.axis {
+                Axis::Vertical => math::Vector::new(0.0, -offset),
+                Axis::Horizontal => math::Vector::new(-offset, 0.0)
+            };
+            let allocated = math::Rect::from_topleft_size(viewport.top_left() + shift, content);
+            self.inner.layout(ctx, LayoutInput { allocated, scale_factor: input.scale_factor });
+            layout
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner)
+    }
+
+    fn clips_children(&self) -> bool {
+        true
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        // whatever is scrolled out of the viewport isn't under the pointer
+        if self.viewport().contains(point) { self.inner.cursor(point) } else { None }
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            if self.style.pointer_events == PointerEvents::None {
+                return InteractSet::empty();
+            }
+            let viewport = self.layout_cache.get().content_box;
+            self.inner.interactions().with(Capability::Scroll, viewport)
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        let viewport = self.viewport();
+        match interaction {
+            &Interaction::Scroll { delta, position } => {
+                if !viewport.contains(position) {
+                    return;
+                }
+                // a scrollable element under the cursor inside this one scrolls first
+                if self.inner.interactions().accepts(interaction) {
+                    return self.inner.handle_interaction(interaction, model);
+                }
+                let delta = match self.axis {
+                    Axis::Vertical => delta.y,
+                    Axis::Horizontal => delta.x
+                };
+                let scale_factor = self.layout_cache.get_untracked().scale_factor;
+                self.scroll_by(delta / scale_factor);
+            }
+            // clipped parts of the inner element can't be clicked
+            Interaction::Click(point) | Interaction::Press(point) if !viewport.contains(*point) => {}
+            Interaction::Hover(Some(point)) if !viewport.contains(*point) => {
+                self.inner.handle_interaction(&Interaction::Hover(None), model)
+            }
+            _ => self.inner.handle_interaction(interaction, model)
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let viewport = layout.content_box;
+        draw_layer(context, 1.0, Some(viewport), |context| self.inner.draw(context));
+
+        let content = self.content_size.get().axis(self.axis);
+        let visible = viewport.size().axis(self.axis);
+        if !self.scrollbar || content <= visible {
+            return;
+        }
+        let width = SCROLLBAR_WIDTH * layout.scale_factor;
+        let length = visible * visible / content;
+        let start = self.offset.get_untracked() * layout.scale_factor * visible / content;
+        let thumb = match self.axis {
+            Axis::Vertical => math::Rect::from_xywh(viewport.right() - width, viewport.top() + start, width, length),
+            Axis::Horizontal => math::Rect::from_xywh(viewport.left() + start, viewport.bottom() - width, length, width)
+        };
+        if let Ok(thumb) = tiny_skia::Rect::try_from(thumb) {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(SCROLLBAR_COLOR.into());
+            context.canvas.fill_rect(thumb, &paint, tiny_skia::Transform::identity(), None);
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+}
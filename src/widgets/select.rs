@@ -3,7 +3,8 @@ use std::ops::IndexMut;
 use crate::{Widget, RenderContext};
 use crate::element::Element;
 use crate::interact::{Interaction, InteractSet};
-use crate::layout::{LayoutCharacteristics, PrelayoutInput, LayoutInput};
+use crate::layout::{LayoutCharacteristics, LayoutContext, PrelayoutInput, LayoutInput};
+use crate::print::BreakHint;
 use crate::tracking::{Computed, Computed2, Derived, ReadableSignal};
 
 pub struct Select<A, S, O> {
@@ -43,18 +44,22 @@ impl<A, S, O> Widget<A> for Select<A, S, O> where O: IndexMut<S, Output=Element<
         self.update_cache.track()
     }
 
-    fn prelayout(&self, input: PrelayoutInput) -> LayoutCharacteristics {
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
         // todo cache?
-        self.options[self.selector.get_untracked()].prelayout(input)
+        self.options[self.selector.get_untracked()].prelayout(ctx, input)
     }
 
-    fn layout(&self, input: LayoutInput) {
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
         self.layout_cache.maybe_update(input, |&input| {
-            self.options[self.selector.get()].layout(input);
+            self.options[self.selector.get()].layout(ctx, input);
         });
         self.layout_cache.track()
     }
 
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.options[self.selector.get_untracked()])
+    }
+
     fn interactions(&self) -> InteractSet {
         self.interactions.maybe_update(|| self.options[self.selector.get()].interactions());
         self.interactions.get()
@@ -67,4 +72,8 @@ impl<A, S, O> Widget<A> for Select<A, S, O> where O: IndexMut<S, Output=Element<
     fn draw(&mut self, context: &mut RenderContext) {
         self.options[self.selector.get_untracked()].draw(context)
     }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.options[self.selector.get_untracked()].page_breaks(hints)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,130 @@
+use crate::{Element, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::tracking::{Computed, Computed2, Derived, ReadableSignal};
+use crate::widgets::Widget;
+
+
+/// Shows its inner element while a predicate on the model holds, and its fallback, if any,
+/// otherwise.
+///
+/// Whichever element is not shown is neither updated nor laid out, and with nothing to show the
+/// widget takes up no space.
+pub struct Show<A> {
+    when: Derived<A, bool>,
+    inner: Element<A>,
+    fallback: Option<Element<A>>,
+
+    update_cache: Computed<()>,
+    layout_cache: Computed2<LayoutInput, ()>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Show<A> {
+    pub fn new(when: impl (Fn(&mut A) -> bool) + 'static, inner: impl Into<Element<A>>) -> Show<A> {
+        Show {
+            when: Derived::new(when),
+            inner: inner.into(),
+            fallback: None,
+
+            update_cache: Computed::new(),
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Shows `fallback` while the predicate doesn't hold.
+    pub fn with_fallback(mut self, fallback: impl Into<Element<A>>) -> Show<A> {
+        self.fallback = Some(fallback.into());
+        self
+    }
+
+    fn shown(&self, visible: bool) -> Option<&Element<A>> {
+        if visible { Some(&self.inner) } else { self.fallback.as_ref() }
+    }
+
+    fn shown_mut(&mut self) -> Option<&mut Element<A>> {
+        if self.when.get_untracked() { Some(&mut self.inner) } else { self.fallback.as_mut() }
+    }
+}
+
+impl<A: 'static> From<Show<A>> for Element<A> {
+    fn from(value: Show<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A> Widget<A> for Show<A> {
+    fn update(&self, model: &mut A) {
+        self.update_cache.maybe_update(|| {
+            self.when.maybe_update(model);
+            // tracked, so that the other element is updated once the predicate flips
+            self.when.track();
+            if let Some(shown) = self.shown(self.when.get_untracked()) {
+                shown.update(model);
+            }
+        });
+        self.update_cache.track()
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        // tracked, so that the parent measures again when the shown element changes
+        match self.shown(self.when.get()) {
+            Some(shown) => shown.prelayout(ctx, input),
+            None => LayoutCharacteristics {
+                layout_style: LayoutStyle {
+                    border_size: 0.0,
+                    padding: 0.0.into(),
+                    margin: 0.0.into(),
+                    width: Sizing::Fit,
+                    height: Sizing::Fit,
+                    pointer_events: PointerEvents::None,
+                    position: Position::Flow
+                },
+                min_size: math::Size::new(0.0, 0.0),
+                text_metrics: None
+            }
+        }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            if let Some(shown) = self.shown(self.when.get()) {
+                shown.layout(ctx, input);
+            }
+        });
+        self.layout_cache.track()
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        if let Some(shown) = self.shown(self.when.get_untracked()) {
+            f(shown)
+        }
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            self.shown(self.when.get()).map_or(InteractSet::empty(), Element::interactions)
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        if let Some(shown) = self.shown_mut() {
+            shown.handle_interaction(interaction, model)
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        if let Some(shown) = self.shown_mut() {
+            shown.draw(context)
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        if let Some(shown) = self.shown(self.when.get_untracked()) {
+            shown.page_breaks(hints)
+        }
+    }
+}
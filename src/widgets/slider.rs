@@ -0,0 +1,211 @@
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Bind, Computed, Computed2, Derived, ReadableSignal};
+use crate::widgets::div::to_tiny_skia_path;
+use crate::widgets::Widget;
+
+
+type ChangeCallback<A> = Box<dyn Fn(&mut A, f32)>;
+
+/// A horizontal track with a thumb which can be dragged to pick a value in a range.
+///
+/// Pressing anywhere on the track moves the thumb there; `on_change` is called with the new value
+/// on the press and on every move of the drag. Like `Checkbox`, the slider shows whatever `value`
+/// reads, so `on_change` has to store the value for the thumb to follow.
+pub struct Slider<A> {
+    style: LayoutStyle,
+    min: f32,
+    max: f32,
+    step: Option<f32>,
+    length: f32,
+    thumb_size: f32,
+    // the theme's track and accent colors if not set
+    colors: Option<(Color, Color)>,
+
+    value: Derived<A, f32>,
+    on_change: ChangeCallback<A>,
+    dragging: bool,
+
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Slider<A> {
+    pub fn new(min: f32, max: f32, value: impl (Fn(&mut A) -> f32) + 'static, on_change: impl Fn(&mut A, f32) + 'static) -> Slider<A> {
+        Slider {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 1.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            min,
+            max,
+            step: None,
+            length: 120.0,
+            thumb_size: 16.0,
+            colors: None,
+
+            value: Derived::new(value),
+            on_change: Box::new(on_change),
+            dragging: false,
+
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// A slider showing a bound signal, which sets it when dragged.
+    pub fn bind(min: f32, max: f32, bind: Bind<A, f32>) -> Slider<A> where A: 'static {
+        Slider::new(min, max, bind.getter(), bind.setter())
+    }
+
+    /// Snaps values to multiples of `step` from `min`.
+    pub fn with_step(mut self, step: f32) -> Slider<A> {
+        self.step = (step > 0.0).then_some(step);
+        self
+    }
+
+    /// Sets the length of the track the slider asks for and the diameter of its thumb, before
+    /// scaling.
+    pub fn with_size(mut self, length: f32, thumb_size: f32) -> Slider<A> {
+        self.length = length;
+        self.thumb_size = thumb_size;
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> Slider<A> {
+        self.style.width = width;
+        self
+    }
+
+    pub fn with_colors(mut self, track: Color, thumb: Color) -> Slider<A> {
+        self.colors = Some((track, thumb));
+        self
+    }
+
+    pub fn set_pointer_events(&mut self, pointer_events: PointerEvents) {
+        self.style.pointer_events = pointer_events;
+        self.interactions.invalidate();
+    }
+
+    // the part of the content box the center of the thumb can be in
+    fn track(&self, layout: &Layout) -> (f32, f32) {
+        let radius = 0.5 * self.thumb_size * layout.scale_factor;
+        let area = layout.content_box;
+        (area.left() + radius, (area.width() - 2.0 * radius).max(0.0))
+    }
+
+    fn fraction(&self, value: f32) -> f32 {
+        if self.max == self.min {
+            return 0.0;
+        }
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+
+    fn value_at(&self, point: math::Point) -> f32 {
+        let layout = self.layout_cache.get_untracked();
+        let (start, length) = self.track(&layout);
+        let fraction = if length > 0.0 { ((point.x - start) / length).clamp(0.0, 1.0) } else { 0.0 };
+        let value = self.min + fraction * (self.max - self.min);
+        match self.step {
+            Some(step) => (self.min + ((value - self.min) / step).round() * step).clamp(self.min.min(self.max), self.min.max(self.max)),
+            None => value
+        }
+    }
+
+    fn change_to(&self, point: math::Point, model: &mut A) {
+        let value = self.value_at(point);
+        if value != self.value.get_untracked() {
+            (self.on_change)(model, value);
+        }
+    }
+}
+
+impl<A> Widget<A> for Slider<A> {
+    fn update(&self, model: &mut A) {
+        self.value.maybe_update(model);
+        self.value.track()
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| {
+            math::Size::new(scale_factor * self.length, scale_factor * self.thumb_size)
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            if self.style.pointer_events == PointerEvents::None {
+                return InteractSet::empty();
+            }
+            InteractSet::empty().with(Capability::Drag, self.layout_cache.get().border_box)
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match *interaction {
+            Interaction::Press(point) if self.layout_cache.get_untracked().border_box.contains(point) => {
+                self.dragging = true;
+                self.change_to(point, model);
+            }
+            Interaction::Drag(point) if self.dragging => self.change_to(point, model),
+            Interaction::Release(point) if self.dragging => {
+                self.dragging = false;
+                self.change_to(point, model);
+            }
+            _ => {}
+        }
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        let over = self.layout_cache.get_untracked().border_box.contains(point);
+        (over && self.style.pointer_events != PointerEvents::None).then_some(Cursor::Pointer)
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let (start, length) = self.track(&layout);
+        let center_y = layout.content_box.top() + 0.5 * layout.content_box.height();
+        let thickness = 0.25 * self.thumb_size * layout.scale_factor;
+        let (track_color, thumb_color) = self.colors.unwrap_or((context.theme.track, context.theme.accent));
+
+        if let Some(track) = tiny_skia::Rect::from_xywh(start, center_y - 0.5 * thickness, length, thickness) {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(track_color.into());
+            context.canvas.fill_rect(track, &paint, tiny_skia::Transform::identity(), None);
+        }
+
+        let center = kurbo::Point::new((start + self.fraction(self.value.get_untracked()) * length) as f64, center_y as f64);
+        let radius = 0.5 * (self.thumb_size * layout.scale_factor) as f64;
+        if let Some(path) = to_tiny_skia_path(kurbo::Circle::new(center, radius)) {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(thumb_color.into());
+            paint.anti_alias = true;
+            context.canvas.fill_path(&path, &paint, tiny_skia::FillRule::Winding, tiny_skia::Transform::identity(), None);
+        }
+    }
+}
+
+impl<A: 'static> From<Slider<A>> for Element<A> {
+    fn from(value: Slider<A>) -> Self {
+        Element::new(value)
+    }
+}
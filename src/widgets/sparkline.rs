@@ -0,0 +1,149 @@
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Computed2, Derived, ReadableSignal};
+use crate::widgets::div::to_tiny_skia_path;
+use crate::widgets::Widget;
+
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SparklineKind {
+    #[default]
+    Line,
+    Bar
+}
+
+/// A small line or bar chart of a series of values, sized like a line of text.
+pub struct Sparkline<A> {
+    style: LayoutStyle,
+    kind: SparklineKind,
+    color: Color,
+    size: math::Size,
+
+    values: Derived<A, Vec<f32>>,
+    layout_cache: Computed2<LayoutInput, Layout>
+}
+
+impl<A> Sparkline<A> {
+    pub fn new(values: impl (Fn(&mut A) -> Vec<f32>) + 'static) -> Sparkline<A> {
+        Sparkline {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            kind: SparklineKind::Line,
+            color: Color::DARK_GRAY,
+            size: math::Size::new(60.0, 15.0),
+
+            values: Derived::new(values),
+            layout_cache: Computed2::new()
+        }
+    }
+
+    pub fn with_kind(mut self, kind: SparklineKind) -> Sparkline<A> {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Sparkline<A> {
+        self.color = color;
+        self
+    }
+
+    /// Sets the size the sparkline asks for, before scaling.
+    pub fn with_size(mut self, size: math::Size) -> Sparkline<A> {
+        self.size = size;
+        self
+    }
+}
+
+impl<A> Widget<A> for Sparkline<A> {
+    fn update(&self, model: &mut A) {
+        self.values.maybe_update(model);
+        self.values.track()
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| scale_factor * self.size)
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        InteractSet::empty()
+    }
+
+    fn handle_interaction(&mut self, _interaction: &Interaction, _model: &mut A) {
+
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let area = layout.content_box;
+        let values = self.values.get_untracked();
+        let (Some(&first), false) = (values.first(), area.width() <= 0.0) else { return; };
+
+        let (min, max) = values.iter().fold((first, first), |(min, max), &value| (min.min(value), max.max(value)));
+        // bars grow from zero when every value has the same sign
+        let (min, max) = match self.kind {
+            SparklineKind::Line => (min, max),
+            SparklineKind::Bar => (min.min(0.0), max.max(0.0))
+        };
+        let range = if max > min { max - min } else { 1.0 };
+        let y = |value: f32| area.bottom() - (value - min) / range * area.height();
+
+        let mut paint = tiny_skia::Paint::default();
+        paint.set_color(self.color.into());
+        paint.anti_alias = true;
+
+        match self.kind {
+            SparklineKind::Line => {
+                let step = area.width() / (values.len().max(2) - 1) as f32;
+                let mut path = kurbo::BezPath::new();
+                for (i, &value) in values.iter().enumerate() {
+                    let point = kurbo::Point::new((area.left() + i as f32 * step) as f64, y(value) as f64);
+                    if i == 0 { path.move_to(point) } else { path.line_to(point) }
+                }
+                if let Some(path) = to_tiny_skia_path(path) {
+                    let stroke = tiny_skia::Stroke { width: layout.scale_factor, ..Default::default() };
+                    context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+                }
+            }
+            SparklineKind::Bar => {
+                let width = area.width() / values.len() as f32;
+                let zero = y(0.0);
+                for (i, &value) in values.iter().enumerate() {
+                    let top = y(value).min(zero);
+                    let height = (y(value) - zero).abs().max(1.0);
+                    // leave a gap between bars once they are wide enough to afford one
+                    let gap = if width > 3.0 { layout.scale_factor } else { 0.0 };
+                    let bar = tiny_skia::Rect::from_xywh(area.left() + i as f32 * width, top, (width - gap).max(1.0), height);
+                    if let Some(bar) = bar {
+                        context.canvas.fill_rect(bar, &paint, tiny_skia::Transform::identity(), None);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<A: 'static> From<Sparkline<A>> for Element<A> {
+    fn from(value: Sparkline<A>) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,147 @@
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+
+use crate::{Color, Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::tracking::{Alarm, Computed2, ReadableSignal};
+use crate::widgets::div::to_tiny_skia_path;
+use crate::widgets::Widget;
+
+
+// how often the spinner is redrawn while it is shown
+const SPINNER_FRAME: Duration = Duration::from_millis(16);
+// the length of the spinning arc, in degrees
+const SPINNER_SWEEP: f64 = 90.0;
+
+/// A spinning arc showing that something is happening without saying how far along it is.
+///
+/// The spinner keeps the tree redrawing for as long as it is updated, so hide it, e.g. with
+/// `Show`, once the work is done.
+pub struct Spinner {
+    style: LayoutStyle,
+    diameter: f32,
+    thickness: f32,
+    // how long one turn takes
+    period: Duration,
+    // the theme's accent color if not set
+    color: Option<Color>,
+
+    started: Instant,
+    next_frame: Alarm,
+    layout_cache: Computed2<LayoutInput, Layout>
+}
+
+impl Spinner {
+    pub fn new() -> Spinner {
+        Spinner {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            diameter: 24.0,
+            thickness: 3.0,
+            period: Duration::from_secs(1),
+            color: None,
+
+            started: Instant::now(),
+            next_frame: Alarm::new(),
+            layout_cache: Computed2::new()
+        }
+    }
+
+    /// Sets the diameter the spinner asks for and the thickness of its arc, before scaling.
+    pub fn with_size(mut self, diameter: f32, thickness: f32) -> Spinner {
+        self.diameter = diameter;
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets how long one turn takes, at least a tenth of a second.
+    pub fn with_period(mut self, period: Duration) -> Spinner {
+        self.period = period.max(Duration::from_millis(100));
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Spinner {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Spinner::new()
+    }
+}
+
+impl<A> Widget<A> for Spinner {
+    fn update(&self, _model: &mut A) {
+        // reruns the update, and with it the frame, once the next frame is due
+        self.next_frame.set(Some(Instant::now() + SPINNER_FRAME));
+        self.next_frame.is_due();
+    }
+
+    fn prelayout(&self, _ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        layout::leaf::do_prelayout(&self.style, input, |_, scale_factor| {
+            math::Size::new(scale_factor * self.diameter, scale_factor * self.diameter)
+        })
+    }
+
+    fn layout(&self, _ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            layout::leaf::do_layout(&self.style, input);
+            Layout::from_layout_input(&self.style, input)
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn interactions(&self) -> InteractSet {
+        InteractSet::empty()
+    }
+
+    fn handle_interaction(&mut self, _interaction: &Interaction, _model: &mut A) {
+
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let area = layout.content_box;
+        let thickness = self.thickness * layout.scale_factor;
+        let radius = (0.5 * area.width().min(area.height()) - 0.5 * thickness) as f64;
+        if radius <= 0.0 {
+            return;
+        }
+        let center = kurbo::Point::new((area.left() + 0.5 * area.width()) as f64, (area.top() + 0.5 * area.height()) as f64);
+        let turns = self.started.elapsed().as_secs_f64() / self.period.as_secs_f64();
+        let arc = kurbo::Arc {
+            center,
+            radii: kurbo::Vec2::new(radius, radius),
+            start_angle: turns.fract() * 2.0 * PI,
+            sweep_angle: SPINNER_SWEEP * PI / 180.0,
+            x_rotation: 0.0
+        };
+        if let Some(path) = to_tiny_skia_path(arc) {
+            let stroke = tiny_skia::Stroke { width: thickness, line_cap: tiny_skia::LineCap::Round, ..Default::default() };
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(self.color.unwrap_or(context.theme.accent).into());
+            paint.anti_alias = true;
+            context.canvas.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+        }
+    }
+}
+
+impl<A> From<Spinner> for Element<A> {
+    fn from(value: Spinner) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,258 @@
+use std::cell::Cell;
+
+use crate::{Element, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::math::Axis;
+use crate::print::BreakHint;
+use crate::tracking::{Bind, Computed, Computed2, Derived, ReadableSignal};
+use crate::widgets::Widget;
+
+
+type RatioSetter<A> = Box<dyn Fn(&mut A, f32)>;
+
+/// Two panes side by side or one above the other, with a divider between them which can be
+/// dragged to resize them.
+///
+/// How much of the space goes to the first pane is kept as a ratio between 0 and 1 in a signal of
+/// the model, so the split can be saved and restored with the rest of it. Dragging the divider
+/// never makes a pane smaller than its minimum size, see `with_min_sizes`. A split expands in
+/// both directions by default.
+pub struct Split<A> {
+    style: LayoutStyle,
+    axis: Axis,
+    first: Element<A>,
+    second: Element<A>,
+    ratio: Derived<A, f32>,
+    set_ratio: RatioSetter<A>,
+    // in logical pixels
+    min_sizes: (f32, f32),
+    divider_width: f32,
+    dragging: bool,
+
+    layout_cache: Computed2<LayoutInput, Layout>,
+    // in physical pixels, updated on layout
+    divider: Cell<math::Rect>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A: 'static> Split<A> {
+    /// `first` to the left of `second`, with a vertical divider.
+    pub fn horizontal(first: impl Into<Element<A>>, second: impl Into<Element<A>>, ratio: Bind<A, f32>) -> Split<A> {
+        Split::new(Axis::Horizontal, first.into(), second.into(), ratio)
+    }
+
+    /// `first` above `second`, with a horizontal divider.
+    pub fn vertical(first: impl Into<Element<A>>, second: impl Into<Element<A>>, ratio: Bind<A, f32>) -> Split<A> {
+        Split::new(Axis::Vertical, first.into(), second.into(), ratio)
+    }
+
+    fn new(axis: Axis, first: Element<A>, second: Element<A>, ratio: Bind<A, f32>) -> Split<A> {
+        Split {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Expand(1.0),
+                height: Sizing::Expand(1.0),
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            axis,
+            first,
+            second,
+            ratio: Derived::new(ratio.getter()),
+            set_ratio: Box::new(ratio.setter()),
+            min_sizes: (0.0, 0.0),
+            divider_width: 4.0,
+            dragging: false,
+
+            layout_cache: Computed2::new(),
+            divider: Cell::new(math::Rect::from_xywh(0.0, 0.0, 0.0, 0.0)),
+            interactions: Computed::new()
+        }
+    }
+}
+
+impl<A> Split<A> {
+    /// Keeps the first pane at least `first` and the second at least `second` logical pixels long
+    /// along the split. If both don't fit, the first pane gets its minimum.
+    pub fn with_min_sizes(mut self, first: f32, second: f32) -> Split<A> {
+        self.min_sizes = (first.max(0.0), second.max(0.0));
+        self
+    }
+
+    pub fn with_divider_width(mut self, width: f32) -> Split<A> {
+        self.divider_width = width.max(0.0);
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> Split<A> {
+        self.style.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: Sizing) -> Split<A> {
+        self.style.height = height;
+        self
+    }
+
+    // the length of the first pane for `ratio`, given the layout, in physical pixels
+    fn first_length(&self, layout: &Layout, ratio: f32) -> f32 {
+        let available = self.available(layout);
+        let min_first = self.min_sizes.0 * layout.scale_factor;
+        let max_first = available - self.min_sizes.1 * layout.scale_factor;
+        (ratio.clamp(0.0, 1.0) * available).min(max_first).max(min_first)
+    }
+
+    // the space the two panes share
+    fn available(&self, layout: &Layout) -> f32 {
+        (layout.content_box.size().axis(self.axis) - self.divider_width * layout.scale_factor).max(0.0)
+    }
+
+    fn ratio_at(&self, point: math::Point) -> f32 {
+        let layout = self.layout_cache.get_untracked();
+        let (position, start) = match self.axis {
+            Axis::Horizontal => (point.x, layout.content_box.left()),
+            Axis::Vertical => (point.y, layout.content_box.top())
+        };
+        let available = self.available(&layout);
+        if available <= 0.0 {
+            return self.ratio.get_untracked();
+        }
+        let length = position - start - 0.5 * self.divider_width * layout.scale_factor;
+        self.first_length(&layout, length / available) / available
+    }
+
+    fn drag_to(&self, point: math::Point, model: &mut A) {
+        let ratio = self.ratio_at(point);
+        if ratio != self.ratio.get_untracked() {
+            (self.set_ratio)(model, ratio);
+        }
+    }
+
+    fn divider_cursor(&self) -> Cursor {
+        match self.axis {
+            Axis::Horizontal => Cursor::ResizeHorizontal,
+            Axis::Vertical => Cursor::ResizeVertical
+        }
+    }
+}
+
+impl<A: 'static> From<Split<A>> for Element<A> {
+    fn from(value: Split<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A> Widget<A> for Split<A> {
+    fn update(&self, model: &mut A) {
+        self.ratio.maybe_update(model);
+        self.ratio.track();
+        self.first.update(model);
+        self.second.update(model);
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        let spacing = input.scale_factor * self.style.spacing_size();
+        let available = input.available - spacing.sum_axes();
+        let first = self.first.prelayout(ctx, PrelayoutInput { available, ..input }).min_size;
+        let second = self.second.prelayout(ctx, PrelayoutInput { available, ..input }).min_size;
+        let main = input.scale_factor * (self.min_sizes.0 + self.divider_width + self.min_sizes.1);
+        let cross = first.axis(self.axis.cross()).max(second.axis(self.axis.cross()));
+        LayoutCharacteristics {
+            layout_style: self.style,
+            min_size: math::Size::from_axes(self.axis, main, cross) + spacing.sum_axes(),
+            text_metrics: None
+        }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            let layout = Layout::from_layout_input(&self.style, input);
+            let content = layout.content_box;
+            let first_length = self.first_length(&layout, self.ratio.get());
+            let second_length = (self.available(&layout) - first_length).max(0.0);
+            let divider_width = self.divider_width * layout.scale_factor;
+
+            let (first, divider, second) = match self.axis {
+                Axis::Horizontal => (
+                    math::Rect::from_xywh(content.left(), content.top(), first_length, content.height()),
+                    math::Rect::from_xywh(content.left() + first_length, content.top(), divider_width, content.height()),
+                    math::Rect::from_xywh(content.left() + first_length + divider_width, content.top(), second_length, content.height())
+                ),
+                Axis::Vertical => (
+                    math::Rect::from_xywh(content.left(), content.top(), content.width(), first_length),
+                    math::Rect::from_xywh(content.left(), content.top() + first_length, content.width(), divider_width),
+                    math::Rect::from_xywh(content.left(), content.top() + first_length + divider_width, content.width(), second_length)
+                )
+            };
+            self.divider.set(divider);
+            self.first.layout(ctx, LayoutInput { allocated: first, scale_factor: input.scale_factor });
+            self.second.layout(ctx, LayoutInput { allocated: second, scale_factor: input.scale_factor });
+            layout
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.first);
+        f(&self.second);
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            self.layout_cache.track();
+            let children = self.first.interactions() | self.second.interactions();
+            if self.style.pointer_events == PointerEvents::None {
+                return children;
+            }
+            children.with(Capability::Drag, self.divider.get())
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match *interaction {
+            Interaction::Press(point) if self.divider.get().contains(point) => {
+                self.dragging = true;
+            }
+            Interaction::Drag(point) if self.dragging => self.drag_to(point, model),
+            Interaction::Release(point) if self.dragging => {
+                self.dragging = false;
+                self.drag_to(point, model);
+            }
+            _ => {
+                self.first.handle_interaction(interaction, model);
+                self.second.handle_interaction(interaction, model);
+            }
+        }
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        if self.dragging || self.divider.get().contains(point) {
+            return Some(self.divider_cursor());
+        }
+        self.second.cursor(point).or_else(|| self.first.cursor(point))
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.first.draw(context);
+        self.second.draw(context);
+
+        if let Ok(divider) = tiny_skia::Rect::try_from(self.divider.get()) {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(context.theme.track.into());
+            context.canvas.fill_rect(divider, &paint, tiny_skia::Transform::identity(), None);
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.first.page_breaks(hints);
+        self.second.page_breaks(hints);
+    }
+}
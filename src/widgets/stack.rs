@@ -0,0 +1,212 @@
+use crate::{Element, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::style::Justify;
+use crate::tracking::{Computed, Computed2, ReadableSignal};
+use crate::widgets::Widget;
+
+
+struct Layer<A> {
+    element: Element<A>,
+    horizontal: Justify,
+    vertical: Justify,
+    z_index: i32
+}
+
+/// Lays all its children over the same area, e.g. to show a tooltip or a dialog above the rest
+/// of the interface.
+///
+/// Each child is placed inside the stack's content box by its own alignment along each axis, and
+/// children which expand fill it. Children with a higher z-index are drawn on top and get clicks,
+/// presses and scrolls first; a pointer interaction only goes to the topmost child which accepts
+/// it. Children with the same z-index keep the order they were added in.
+pub struct Stack<A> {
+    style: LayoutStyle,
+    layers: Vec<Layer<A>>,
+    // indices into `layers` from the bottom up
+    order: Vec<usize>,
+
+    prelayout_cache: Computed2<PrelayoutInput, math::Size>,
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Stack<A> {
+    pub fn new() -> Stack<A> {
+        Stack {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Fit,
+                height: Sizing::Fit,
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            layers: Vec::new(),
+            order: Vec::new(),
+
+            prelayout_cache: Computed2::new(),
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Adds a child in the top left corner at z-index 0.
+    pub fn add_child(&mut self, element: impl Into<Element<A>>) {
+        self.add_layer(element, Justify::Min, Justify::Min, 0);
+    }
+
+    /// Adds a child aligned inside the stack by `horizontal` and `vertical`, drawn above the
+    /// children with a lower `z_index`.
+    pub fn add_layer(&mut self, element: impl Into<Element<A>>, horizontal: Justify, vertical: Justify, z_index: i32) {
+        self.layers.push(Layer { element: element.into(), horizontal, vertical, z_index });
+        self.order.push(self.layers.len() - 1);
+        let layers = &self.layers;
+        self.order.sort_by_key(|&index| layers[index].z_index);
+    }
+
+    pub fn set_width(&mut self, width: Sizing) {
+        self.style.width = width;
+    }
+
+    pub fn set_height(&mut self, height: Sizing) {
+        self.style.height = height;
+    }
+
+    fn spacing(&self, scale_factor: f32) -> math::SizeRect {
+        scale_factor * self.style.spacing_size()
+    }
+}
+
+impl<A> Default for Stack<A> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+impl<A: 'static> From<Stack<A>> for Element<A> {
+    fn from(value: Stack<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+// where a child of `length` starts inside a span from `start` to `end`
+fn align(justify: Justify, start: f32, end: f32, length: f32) -> f32 {
+    match justify {
+        Justify::Min | Justify::Baseline => start,
+        Justify::Max => end - length,
+        Justify::Center => start + 0.5 * (end - start - length)
+    }
+}
+
+// the length a child with `sizing` takes inside a content box which is `available` long
+fn layer_length(sizing: Sizing, available: f32, min_length: f32) -> f32 {
+    match sizing {
+        Sizing::Expand(_) => available.max(min_length),
+        Sizing::Percent(_) => sizing.as_percent_of(available).unwrap_or(min_length),
+        Sizing::Fit | Sizing::Fixed(_) => min_length
+    }
+}
+
+impl<A> Widget<A> for Stack<A> {
+    fn update(&self, model: &mut A) {
+        for layer in &self.layers {
+            layer.element.update(model);
+        }
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.prelayout_cache.maybe_update(input, |&input| {
+            let spacing = self.spacing(input.scale_factor);
+            let available = input.available - spacing.sum_axes();
+            let (mut width, mut height) = (0.0f32, 0.0f32);
+            for layer in &self.layers {
+                let size = layer.element.prelayout(ctx, PrelayoutInput { available, scale_factor: input.scale_factor }).min_size;
+                width = width.max(size.width());
+                height = height.max(size.height());
+            }
+            let width = self.style.width.as_definite(input.scale_factor).unwrap_or(width);
+            let height = self.style.height.as_definite(input.scale_factor).unwrap_or(height);
+            math::Size::new(width, height) + spacing.sum_axes()
+        });
+        LayoutCharacteristics {
+            layout_style: self.style,
+            min_size: self.prelayout_cache.get(),
+            text_metrics: None
+        }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.prelayout_cache.track();
+            let layout = Layout::from_layout_input(&self.style, input);
+            let area = layout.content_box;
+            for layer in &self.layers {
+                let characteristics = layer.element.prelayout(ctx, PrelayoutInput { available: area.size(), scale_factor: input.scale_factor });
+                let style = characteristics.layout_style;
+                let width = layer_length(style.width, area.width(), characteristics.min_size.width());
+                let height = layer_length(style.height, area.height(), characteristics.min_size.height());
+                let left = align(layer.horizontal, area.left(), area.right(), width);
+                let top = align(layer.vertical, area.top(), area.bottom(), height);
+                layer.element.layout(ctx, LayoutInput {
+                    allocated: math::Rect::from_lrtb(left, left + width, top, top + height),
+                    scale_factor: input.scale_factor
+                });
+            }
+            layout
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        for &index in &self.order {
+            f(&self.layers[index].element)
+        }
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            self.layers.iter().fold(InteractSet::empty(), |set, layer| set | layer.element.interactions())
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match interaction {
+            // only the topmost child under the pointer gets these, so that covered children don't
+            // react through an overlay
+            Interaction::Click(_) | Interaction::SecondaryClick(_) | Interaction::Press(_) | Interaction::Scroll { .. } => {
+                for &index in self.order.iter().rev() {
+                    let element = &mut self.layers[index].element;
+                    if element.interactions().accepts(interaction) {
+                        element.handle_interaction(interaction, model);
+                        break;
+                    }
+                }
+            }
+            _ => {
+                for layer in &mut self.layers {
+                    layer.element.handle_interaction(interaction, model);
+                }
+            }
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        for &index in &self.order {
+            self.layers[index].element.draw(context);
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        for layer in &self.layers {
+            layer.element.page_breaks(hints);
+        }
+    }
+}
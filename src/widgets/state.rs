@@ -0,0 +1,92 @@
+use std::cell::Cell;
+
+use crate::Element;
+use crate::focus::{FocusId, FocusSearch};
+use crate::interact::Interaction;
+use crate::math;
+use crate::style::WidgetState;
+use crate::tracking::{Derived, ReadableSignal, RwSignal};
+
+
+/// Follows the cursor, the primary button, the focus and an optional disabled binding to tell
+/// which `WidgetState` a widget with a `StyleSheet` is drawn in.
+pub(super) struct StateTracker<A> {
+    hovered: Cell<bool>,
+    pressed: Cell<bool>,
+    focused: Cell<bool>,
+    disabled: Option<Derived<A, bool>>,
+    state: RwSignal<WidgetState>
+}
+
+impl<A> StateTracker<A> {
+    pub fn new() -> StateTracker<A> {
+        StateTracker {
+            hovered: Cell::new(false),
+            pressed: Cell::new(false),
+            focused: Cell::new(false),
+            disabled: None,
+            state: RwSignal::new(WidgetState::Normal)
+        }
+    }
+
+    pub fn bind_disabled(&mut self, disabled: impl Fn(&mut A) -> bool + 'static) {
+        self.disabled = Some(Derived::new(disabled));
+    }
+
+    /// Re-evaluates the disabled binding. The state is read tracked, so that the enclosing update
+    /// reruns, and the tree is redrawn, when it changes.
+    pub fn update(&self, model: &mut A) {
+        if let Some(disabled) = &self.disabled {
+            if disabled.maybe_update_if_changed(model) {
+                self.refresh();
+            }
+            disabled.track();
+        }
+        self.state.track();
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled.as_ref().is_some_and(|disabled| disabled.get_untracked())
+    }
+
+    /// Follows pointer interactions against `contains`, which tells whether a point is over the
+    /// widget, and focus changes against `has_focus`.
+    pub fn handle_interaction(&self, interaction: &Interaction, contains: impl Fn(math::Point) -> bool, has_focus: impl FnOnce(FocusId) -> bool) {
+        match *interaction {
+            Interaction::Hover(point) => self.hovered.set(point.is_some_and(contains)),
+            Interaction::Press(point) => self.pressed.set(contains(point)),
+            Interaction::Release(_) => self.pressed.set(false),
+            Interaction::Focus(focus) => self.focused.set(focus.is_some_and(has_focus)),
+            _ => return
+        }
+        self.refresh();
+    }
+
+    fn refresh(&self) {
+        let state = if self.is_disabled() {
+            WidgetState::Disabled
+        } else if self.pressed.get() {
+            WidgetState::Pressed
+        } else if self.hovered.get() {
+            WidgetState::Hovered
+        } else if self.focused.get() {
+            WidgetState::Focused
+        } else {
+            WidgetState::Normal
+        };
+        if self.state.get_untracked() != state {
+            self.state.update(|current| *current = state);
+        }
+    }
+
+    pub fn get(&self) -> WidgetState {
+        self.state.get_untracked()
+    }
+}
+
+/// Whether the focusable element with `focus` is `element` or inside it.
+pub(super) fn contains_focus<A>(element: &Element<A>, focus: FocusId) -> bool {
+    let mut search = FocusSearch::new(focus);
+    element.visit(&mut search);
+    search.found
+}
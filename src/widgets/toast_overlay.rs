@@ -0,0 +1,192 @@
+use std::cell::RefCell;
+
+use crate::{Element, Label, math, RenderContext, Toasts};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::tracking::{Alarm, Computed, Computed2, ReadableSignal, RwSignal};
+use crate::widgets::div::{fill_rounded_rect, stroke_border};
+use crate::widgets::modifier::draw_overlay;
+use crate::widgets::Widget;
+
+
+// in logical pixels
+const TOAST_PADDING: f32 = 8.0;
+const TOAST_RADIUS: f32 = 4.0;
+const TOAST_BORDER: f32 = 1.0;
+// between the toasts, and between them and the corner
+const TOAST_GAP: f32 = 8.0;
+
+/// Shows the toasts of a `Toasts` stacked in the bottom right corner of its inner element, which
+/// is usually the whole content of the window.
+///
+/// The toasts are drawn over everything else in the tree, see `Overlays`, newest at the bottom.
+/// Clicking one dismisses it; clicks on a toast don't reach the element beneath it.
+pub struct ToastOverlay<A> {
+    inner: Element<A>,
+    toasts: Toasts,
+    // the label shown for each toast, by id
+    labels: RefCell<Vec<(u64, Element<A>)>>,
+    // bumped whenever labels are added or removed, so that the toasts are laid out again
+    revision: RwSignal<u64>,
+    expiry: Alarm,
+
+    layout_cache: Computed2<LayoutInput, LayoutInput>,
+    // the box around each toast, in the order of `labels`
+    frames: Computed<Vec<math::Rect>>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> ToastOverlay<A> {
+    pub fn new(toasts: Toasts, inner: impl Into<Element<A>>) -> ToastOverlay<A> {
+        ToastOverlay {
+            inner: inner.into(),
+            toasts,
+            labels: RefCell::new(Vec::new()),
+            revision: RwSignal::new(0),
+            expiry: Alarm::new(),
+
+            layout_cache: Computed2::new(),
+            frames: Computed::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    // matches the labels to the toasts, keeping the ones still shown
+    fn sync_labels(&self) where A: 'static {
+        let messages = self.toasts.messages();
+        let mut labels = self.labels.borrow_mut();
+        let unchanged = labels.len() == messages.len()
+            && labels.iter().zip(&messages).all(|((id, _), (message_id, _))| id == message_id);
+        if unchanged {
+            return;
+        }
+
+        let mut previous: Vec<(u64, Element<A>)> = labels.drain(..).collect();
+        labels.extend(messages.into_iter().map(|(id, message)| {
+            let label = match previous.iter().position(|(previous_id, _)| *previous_id == id) {
+                Some(index) => previous.swap_remove(index).1,
+                None => Label::new(move |_| message.clone()).into()
+            };
+            (id, label)
+        }));
+        drop(labels);
+        self.revision.update(|revision| *revision += 1);
+    }
+
+    fn toast_at(&self, point: math::Point) -> Option<usize> {
+        self.frames.get_untracked().iter().position(|frame| frame.contains(point))
+    }
+}
+
+impl<A: 'static> From<ToastOverlay<A>> for Element<A> {
+    fn from(value: ToastOverlay<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A: 'static> Widget<A> for ToastOverlay<A> {
+    fn update(&self, model: &mut A) {
+        self.inner.update(model);
+        // tracked, so that toasts are dismissed once their time is up
+        self.expiry.is_due();
+        self.expiry.set(self.toasts.expire());
+        self.sync_labels();
+        for (_, label) in self.labels.borrow().iter() {
+            label.update(model);
+        }
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.inner.prelayout(ctx, input)
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.inner.layout(ctx, input);
+            input
+        });
+        self.layout_cache.track();
+
+        self.frames.maybe_update(|| {
+            self.revision.track();
+            let LayoutInput { allocated, scale_factor } = self.layout_cache.get();
+            let padding = TOAST_PADDING * scale_factor;
+            let gap = TOAST_GAP * scale_factor;
+            let available = math::Size::new((allocated.width() - 2.0 * (gap + padding)).max(0.0), f32::INFINITY);
+
+            // stacked upwards from the corner, newest at the bottom
+            let mut bottom = allocated.bottom() - gap;
+            let mut frames: Vec<math::Rect> = self.labels.borrow().iter().rev().map(|(_, label)| {
+                let size = label.prelayout(ctx, PrelayoutInput { available, scale_factor }).min_size;
+                let frame_size = size + math::Size::new(2.0 * padding, 2.0 * padding);
+                let top_left = math::Point::new(allocated.right() - gap - frame_size.horizontal, bottom - frame_size.vertical);
+                let frame = math::Rect::from_topleft_size(top_left, frame_size);
+                label.layout(ctx, LayoutInput { allocated: frame.shrink_by(padding.into()), scale_factor });
+                bottom = frame.top() - gap;
+                frame
+            }).collect();
+            frames.reverse();
+            frames
+        });
+        self.frames.track();
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner);
+        for (_, label) in self.labels.borrow().iter() {
+            f(label);
+        }
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            self.frames.get().into_iter().fold(self.inner.interactions(), |set, frame| set.with(Capability::Click, frame))
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match *interaction {
+            Interaction::Click(point) if self.toast_at(point).is_some() => {
+                if let Some(index) = self.toast_at(point) {
+                    let id = self.labels.borrow()[index].0;
+                    self.toasts.dismiss(id);
+                }
+            }
+            // the element beneath a toast doesn't see the press and release of a click on it
+            Interaction::Press(point) | Interaction::Release(point) if self.toast_at(point).is_some() => {}
+            _ => self.inner.handle_interaction(interaction, model)
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.inner.draw(context);
+        let frames = self.frames.get_untracked();
+        if frames.is_empty() {
+            return;
+        }
+        let scale_factor = self.layout_cache.get_untracked().scale_factor;
+        let labels = self.labels.get_mut();
+        draw_overlay(context, |context| {
+            let radius = TOAST_RADIUS * scale_factor;
+            let border = TOAST_BORDER * scale_factor;
+            for (frame, (_, label)) in frames.into_iter().zip(labels.iter_mut()) {
+                fill_rounded_rect(context, frame, radius, context.theme.surface);
+                stroke_border(context, frame.shrink_by((0.5 * border).into()), radius, border, context.theme.foreground);
+                label.draw(context);
+            }
+        });
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        match self.toast_at(point) {
+            Some(_) => Some(Cursor::Pointer),
+            None => self.inner.cursor(point)
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+}
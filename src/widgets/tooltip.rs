@@ -0,0 +1,168 @@
+use std::time::{Duration, Instant};
+
+use crate::{Element, math, RenderContext};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::tracking::{Alarm, Computed, Computed2, ReadableSignal};
+use crate::widgets::div::{fill_rounded_rect, stroke_border};
+use crate::widgets::modifier::draw_overlay;
+use crate::widgets::Widget;
+
+
+// in logical pixels
+const TIP_PADDING: f32 = 4.0;
+const TIP_RADIUS: f32 = 3.0;
+const TIP_BORDER: f32 = 1.0;
+// the gap between the inner element and the tip
+const TIP_OFFSET: f32 = 4.0;
+
+/// Shows a tip, such as a short `Label`, in a small box below its inner element once the pointer
+/// has rested on the element for a while.
+///
+/// The tip goes above the element instead where it doesn't fit below it, and is moved sideways
+/// to stay inside the viewport.
+///
+/// The tip is drawn over everything else in the tree, see `Overlays`, and hides again when the
+/// pointer leaves the element or presses a button. It is measured without a width limit and
+/// doesn't take interactions.
+pub struct Tooltip<A> {
+    inner: Element<A>,
+    tip: Element<A>,
+    delay: Duration,
+    hovered: bool,
+    shown: Alarm,
+
+    layout_cache: Computed2<LayoutInput, LayoutInput>,
+    // the box around the tip while it is shown, including its padding, kept inside the viewport
+    tip_frame: Computed2<math::Rect, Option<math::Rect>>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> Tooltip<A> {
+    /// Shows `tip` after the pointer rested on `inner` for half a second.
+    pub fn new(inner: impl Into<Element<A>>, tip: impl Into<Element<A>>) -> Tooltip<A> {
+        Tooltip {
+            inner: inner.into(),
+            tip: tip.into(),
+            delay: Duration::from_millis(500),
+            hovered: false,
+            shown: Alarm::new(),
+
+            layout_cache: Computed2::new(),
+            tip_frame: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Tooltip<A> {
+        self.delay = delay;
+        self
+    }
+
+    fn set_hovered(&mut self, hovered: bool) {
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.shown.set(hovered.then(|| Instant::now() + self.delay));
+        }
+    }
+}
+
+impl<A: 'static> From<Tooltip<A>> for Element<A> {
+    fn from(value: Tooltip<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A> Widget<A> for Tooltip<A> {
+    fn update(&self, model: &mut A) {
+        self.inner.update(model);
+        self.tip.update(model);
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.inner.prelayout(ctx, input)
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.inner.layout(ctx, input);
+            input
+        });
+        self.layout_cache.track();
+
+        self.tip_frame.maybe_update(ctx.viewport(), |&viewport| {
+            // tracked, so that the tip is laid out and drawn once the delay is over
+            if !self.shown.is_due() {
+                return None;
+            }
+            let LayoutInput { allocated, scale_factor } = self.layout_cache.get();
+            let unbounded = math::Size::new(f32::INFINITY, f32::INFINITY);
+            let size = self.tip.prelayout(ctx, PrelayoutInput { available: unbounded, scale_factor }).min_size;
+            let padding = TIP_PADDING * scale_factor;
+            let offset = TIP_OFFSET * scale_factor;
+            let size = size + math::Size::new(2.0 * padding, 2.0 * padding);
+            let below = allocated.bottom() + offset;
+            let above = allocated.top() - offset - size.height();
+            let top = match below + size.height() > viewport.bottom() && above >= viewport.top() {
+                true => above,
+                false => below
+            };
+            let left = allocated.left().min(viewport.right() - size.width()).max(viewport.left());
+            let frame = math::Rect::from_topleft_size(math::Point::new(left, top), size);
+            self.tip.layout(ctx, LayoutInput { allocated: frame.shrink_by(padding.into()), scale_factor });
+            Some(frame)
+        });
+        self.tip_frame.track();
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        f(&self.inner);
+        if self.tip_frame.get_untracked().is_some() {
+            f(&self.tip);
+        }
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            let area = self.layout_cache.get().allocated;
+            self.inner.interactions().with(Capability::Hover, area).with(Capability::Drag, area)
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        match *interaction {
+            Interaction::Hover(point) => {
+                let area = self.layout_cache.get_untracked().allocated;
+                self.set_hovered(point.is_some_and(|point| area.contains(point)));
+            }
+            Interaction::Press(_) => self.set_hovered(false),
+            _ => {}
+        }
+        self.inner.handle_interaction(interaction, model);
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        self.inner.draw(context);
+        let Some(frame) = self.tip_frame.get_untracked() else { return; };
+        let scale_factor = self.layout_cache.get_untracked().scale_factor;
+        let tip = &mut self.tip;
+        draw_overlay(context, |context| {
+            let radius = TIP_RADIUS * scale_factor;
+            let border = TIP_BORDER * scale_factor;
+            fill_rounded_rect(context, frame, radius, context.theme.surface);
+            stroke_border(context, frame.shrink_by((0.5 * border).into()), radius, border, context.theme.foreground);
+            tip.draw(context);
+        });
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.inner.page_breaks(hints)
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        // the tip doesn't take the pointer
+        self.inner.cursor(point)
+    }
+}
@@ -0,0 +1,355 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::{Element, layout, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, SelectionModel, SelectModifiers, Sizing};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput, TextMetrics};
+use crate::math::Axis;
+use crate::print::BreakHint;
+use crate::style::{ContainerLayoutStyle, Direction, Justify, Wrap};
+use crate::tracking::{Computed, Computed2, ReadableSignal, RwSignal};
+use crate::widgets::div::to_tiny_skia_path;
+use crate::widgets::Widget;
+
+
+// in logical pixels
+const INDENT: f32 = 16.0;
+const DISCLOSURE_WIDTH: f32 = 16.0;
+const TRIANGLE_SIZE: f32 = 8.0;
+
+type RootsFn<A, K> = Box<dyn Fn(&mut A) -> Vec<K>>;
+type ChildrenFn<A, K> = Box<dyn Fn(&mut A, &K) -> Vec<K>>;
+type NodeView<A, K> = Box<dyn Fn(&K) -> Element<A>>;
+type LeafFn<A, K> = Box<dyn Fn(&mut A, &K) -> bool>;
+type SelectionLens<A, K> = dyn for<'a> Fn(&'a A) -> &'a SelectionModel<K>;
+
+struct Row<A, K> {
+    key: K,
+    depth: usize,
+    leaf: bool,
+    expanded: bool,
+    element: Element<A>
+}
+
+/// Shows a tree of nodes keyed by `K`, each indented below its parent with a triangle which
+/// expands and collapses it.
+///
+/// Only the children of expanded nodes are asked for, so a large or expensive tree, e.g. a file
+/// system, is only read as far as it is opened. Rows are kept by key like in `List`, so expanding a
+/// node only builds elements for the newly shown rows. Nodes for which `with_leaf` holds get no
+/// triangle; without it every node gets one until it is expanded and turns out to be empty.
+pub struct TreeView<A, K> {
+    style: ContainerLayoutStyle,
+    roots: RootsFn<A, K>,
+    children: ChildrenFn<A, K>,
+    view: NodeView<A, K>,
+    is_leaf: Option<LeafFn<A, K>>,
+    selection: Option<Box<SelectionLens<A, K>>>,
+
+    expanded: RwSignal<HashSet<K>>,
+    rows: RefCell<Vec<Row<A, K>>>,
+    // the selected keys as of the last update, for drawing
+    selected: RefCell<Vec<K>>,
+    // bumped whenever rows are added, removed or reordered, so that layout is redone
+    revision: RwSignal<u64>,
+    // the full width strip each row takes up, in physical pixels
+    row_rects: RefCell<Vec<math::Rect>>,
+
+    update_cache: Computed<()>,
+    prelayout_cache: Computed2<PrelayoutInput, (math::Size, Option<TextMetrics>)>,
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A, K: Clone + Eq + Hash> TreeView<A, K> {
+    /// A tree whose top level nodes are `roots`, where `children` gives the nodes below an
+    /// expanded node and `view` builds the element shown for a node.
+    pub fn new(
+        roots: impl Fn(&mut A) -> Vec<K> + 'static,
+        children: impl Fn(&mut A, &K) -> Vec<K> + 'static,
+        view: impl Fn(&K) -> Element<A> + 'static
+    ) -> TreeView<A, K> {
+        TreeView {
+            style: ContainerLayoutStyle {
+                layout_style: LayoutStyle {
+                    border_size: 0.0,
+                    padding: 0.0.into(),
+                    margin: 0.0.into(),
+                    width: Sizing::Fit,
+                    height: Sizing::Fit,
+                    pointer_events: PointerEvents::Auto,
+                    position: Position::Flow
+                },
+                main_axis: Axis::Vertical,
+                main_direction: Direction::Positive,
+                main_justify: Justify::Min,
+                cross_justify: Justify::Min,
+                wrap: Wrap::NoWrap,
+                gap: 0.0
+            },
+            roots: Box::new(roots),
+            children: Box::new(children),
+            view: Box::new(view),
+            is_leaf: None,
+            selection: None,
+
+            expanded: RwSignal::new(HashSet::new()),
+            rows: RefCell::new(Vec::new()),
+            selected: RefCell::new(Vec::new()),
+            revision: RwSignal::new(0),
+            row_rects: RefCell::new(Vec::new()),
+
+            update_cache: Computed::new(),
+            prelayout_cache: Computed2::new(),
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Marks the nodes for which `is_leaf` holds as having no children, so they aren't drawn
+    /// with a triangle.
+    pub fn with_leaf(mut self, is_leaf: impl Fn(&mut A, &K) -> bool + 'static) -> TreeView<A, K> {
+        self.is_leaf = Some(Box::new(is_leaf));
+        self
+    }
+
+    /// Selects a node when its row is clicked, in the `SelectionModel` found by `selection`, and
+    /// highlights the selected rows.
+    pub fn with_selection(mut self, selection: impl for<'a> Fn(&'a A) -> &'a SelectionModel<K> + 'static) -> TreeView<A, K> {
+        self.selection = Some(Box::new(selection));
+        self
+    }
+
+    /// Starts with the nodes in `keys` expanded.
+    pub fn with_expanded(self, keys: impl IntoIterator<Item=K>) -> TreeView<A, K> {
+        self.expanded.update(|expanded| expanded.extend(keys));
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> TreeView<A, K> {
+        self.style.layout_style.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: Sizing) -> TreeView<A, K> {
+        self.style.layout_style.height = height;
+        self
+    }
+
+    fn toggle(&self, key: &K) {
+        self.expanded.update(|expanded| {
+            if !expanded.remove(key) {
+                expanded.insert(key.clone());
+            }
+        });
+    }
+
+    // the shown nodes in display order, with their depth, whether they are leaves and whether
+    // they are expanded
+    fn visible_nodes(&self, model: &mut A) -> Vec<(K, usize, bool, bool)> {
+        let expanded = self.expanded.get();
+        let mut nodes = Vec::new();
+        let mut stack: Vec<(K, usize)> = (self.roots)(model).into_iter().rev().map(|key| (key, 0)).collect();
+        while let Some((key, depth)) = stack.pop() {
+            let mut leaf = self.is_leaf.as_ref().is_some_and(|is_leaf| is_leaf(model, &key));
+            let is_expanded = !leaf && expanded.contains(&key);
+            if is_expanded {
+                let children = (self.children)(model, &key);
+                leaf = children.is_empty();
+                stack.extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+            }
+            nodes.push((key, depth, leaf, is_expanded && !leaf));
+        }
+        nodes
+    }
+
+    // matches the rows to the shown nodes, keeping the elements of nodes still shown at the same
+    // depth
+    fn sync_rows(&self, model: &mut A) where A: 'static {
+        let nodes = self.visible_nodes(model);
+        let mut rows = self.rows.borrow_mut();
+        let unchanged = rows.len() == nodes.len()
+            && rows.iter().zip(&nodes).all(|(row, (key, depth, leaf, expanded))| {
+                row.key == *key && row.depth == *depth && row.leaf == *leaf && row.expanded == *expanded
+            });
+        if unchanged {
+            return;
+        }
+
+        let mut previous: HashMap<K, (usize, Element<A>)> = rows.drain(..).map(|row| (row.key, (row.depth, row.element))).collect();
+        rows.extend(nodes.into_iter().map(|(key, depth, leaf, expanded)| {
+            let element = match previous.remove(&key) {
+                Some((previous_depth, element)) if previous_depth == depth => element,
+                _ => (self.view)(&key).padding((depth as f32 * INDENT + DISCLOSURE_WIDTH, 0.0, 0.0, 0.0))
+            };
+            Row { key, depth, leaf, expanded, element }
+        }));
+        drop(rows);
+        self.revision.update(|revision| *revision += 1);
+    }
+
+    fn row_at(&self, point: math::Point) -> Option<usize> {
+        self.row_rects.borrow().iter().position(|rect| rect.contains(point))
+    }
+
+    // whether `point` is on the triangle of the row at `index`
+    fn on_disclosure(&self, index: usize, point: math::Point) -> bool {
+        let rows = self.rows.borrow();
+        let row = &rows[index];
+        let scale_factor = self.layout_cache.get_untracked().scale_factor;
+        let left = self.row_rects.borrow()[index].left() + row.depth as f32 * INDENT * scale_factor;
+        !row.leaf && point.x >= left && point.x < left + DISCLOSURE_WIDTH * scale_factor
+    }
+}
+
+impl<A: 'static, K: Clone + Eq + Hash> Widget<A> for TreeView<A, K> {
+    fn update(&self, model: &mut A) {
+        self.update_cache.maybe_update(|| {
+            self.sync_rows(model);
+            for row in self.rows.borrow().iter() {
+                row.element.update(model);
+            }
+        });
+        self.update_cache.track();
+        if let Some(selection) = &self.selection {
+            // tracked, so that the tree is redrawn when the selection changes
+            *self.selected.borrow_mut() = selection(model).selected().get();
+        }
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.prelayout_cache.maybe_update(input, |&input| {
+            self.revision.track();
+            let rows = self.rows.borrow();
+            let characteristics = layout::container::do_prelayout(&self.style, ctx, input, rows.iter().map(|row| &row.element));
+            (characteristics.min_size, characteristics.text_metrics)
+        });
+        let (min_size, text_metrics) = self.prelayout_cache.get();
+        LayoutCharacteristics {
+            layout_style: self.style.layout_style,
+            min_size,
+            text_metrics
+        }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.prelayout_cache.track();
+            let layout = Layout::from_layout_input(&self.style.layout_style, input);
+            let rows = self.rows.borrow();
+            let rows_layouts = layout::container::do_layout(&self.style, ctx, input, rows.iter().map(|row| &row.element));
+            let mut row_rects = self.row_rects.borrow_mut();
+            row_rects.clear();
+            for (row, row_layout) in rows.iter().zip(rows_layouts) {
+                row.element.layout(ctx, row_layout);
+                let content = layout.content_box;
+                row_rects.push(math::Rect::from_lrtb(content.left(), content.right(), row_layout.allocated.top(), row_layout.allocated.bottom()));
+            }
+            layout
+        });
+        self.layout_cache.track()
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        self.rows.borrow().iter().for_each(|row| f(&row.element))
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            self.revision.track();
+            let own = InteractSet::empty().with(Capability::Click, self.layout_cache.get().content_box);
+            self.rows.borrow().iter().fold(own, |set, row| set | row.element.interactions())
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        if !self.interactions.get_untracked().accepts(interaction) {
+            return;
+        }
+        if let &Interaction::Click(point) = interaction {
+            if let Some(index) = self.row_at(point) {
+                let key = self.rows.borrow()[index].key.clone();
+                if self.on_disclosure(index, point) {
+                    self.toggle(&key);
+                } else if let Some(selection) = &self.selection {
+                    let order: Vec<K> = self.rows.borrow().iter().map(|row| row.key.clone()).collect();
+                    selection(model).click(key, SelectModifiers::default(), &order);
+                }
+            }
+        }
+        for row in self.rows.get_mut() {
+            row.element.handle_interaction(interaction, model);
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let scale_factor = self.layout_cache.get_untracked().scale_factor;
+        let row_rects = self.row_rects.borrow();
+        let selected = self.selected.borrow();
+        for (row, rect) in self.rows.get_mut().iter_mut().zip(row_rects.iter()) {
+            if selected.contains(&row.key) {
+                if let Ok(rect) = tiny_skia::Rect::try_from(*rect) {
+                    let mut color: tiny_skia::Color = context.theme.accent.into();
+                    color.set_alpha(0.3);
+                    let mut paint = tiny_skia::Paint::default();
+                    paint.set_color(color);
+                    context.canvas.fill_rect(rect, &paint, tiny_skia::Transform::identity(), None);
+                }
+            }
+
+            if !row.leaf {
+                // pointing right while collapsed and down while expanded
+                let size = (TRIANGLE_SIZE * scale_factor) as f64;
+                let center = kurbo::Point::new(
+                    (rect.left() + (row.depth as f32 * INDENT + 0.5 * DISCLOSURE_WIDTH) * scale_factor) as f64,
+                    (0.5 * (rect.top() + rect.bottom())) as f64
+                );
+                let mut triangle = kurbo::BezPath::new();
+                if row.expanded {
+                    triangle.move_to((center.x - 0.5 * size, center.y - 0.25 * size));
+                    triangle.line_to((center.x + 0.5 * size, center.y - 0.25 * size));
+                    triangle.line_to((center.x, center.y + 0.25 * size));
+                } else {
+                    triangle.move_to((center.x - 0.25 * size, center.y - 0.5 * size));
+                    triangle.line_to((center.x + 0.25 * size, center.y));
+                    triangle.line_to((center.x - 0.25 * size, center.y + 0.5 * size));
+                }
+                triangle.close_path();
+                if let Some(path) = to_tiny_skia_path(triangle) {
+                    let mut paint = tiny_skia::Paint::default();
+                    paint.set_color(context.theme.foreground.into());
+                    paint.anti_alias = true;
+                    context.canvas.fill_path(&path, &paint, tiny_skia::FillRule::Winding, tiny_skia::Transform::identity(), None);
+                }
+            }
+
+            row.element.draw(context);
+        }
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        let from_rows = self.rows.borrow().iter().rev().find_map(|row| row.element.cursor(point));
+        from_rows.or_else(|| {
+            let index = self.row_at(point)?;
+            self.on_disclosure(index, point).then_some(Cursor::Pointer)
+        })
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        for row in self.rows.borrow().iter() {
+            row.element.page_breaks(hints);
+        }
+    }
+}
+
+impl<A: 'static, K: Clone + Eq + Hash + 'static> From<TreeView<A, K>> for Element<A> {
+    fn from(value: TreeView<A, K>) -> Self {
+        Element::new(value)
+    }
+}
@@ -0,0 +1,361 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::{Element, Layout, LayoutStyle, math, PointerEvents, Position, RenderContext, Sizing};
+use crate::interact::{Capability, Cursor, Interaction, InteractSet};
+use crate::layout::{LayoutCharacteristics, LayoutContext, LayoutInput, PrelayoutInput};
+use crate::print::BreakHint;
+use crate::tracking::{Computed, Computed2, Derived, ReadableSignal, RwSignal};
+use crate::widgets::modifier::draw_layer;
+use crate::widgets::scroll_view::{SCROLLBAR_COLOR, SCROLLBAR_WIDTH};
+use crate::widgets::Widget;
+
+
+/// The index of the item a row of a `VirtualList` currently shows.
+///
+/// Rows are reused for other items while scrolling, so an element built for a row should read the
+/// index through this handle whenever it computes something from its item, rather than once when
+/// it is built.
+#[derive(Clone)]
+pub struct RowIndex(Rc<RwSignal<usize>>);
+
+impl RowIndex {
+    /// The index of the item, tracked, so that whatever reads it is recomputed when the row is
+    /// moved to another item.
+    pub fn get(&self) -> usize {
+        self.0.get()
+    }
+}
+
+struct Slot<A> {
+    index: Rc<RwSignal<usize>>,
+    element: Element<A>
+}
+
+/// A vertical list which scrolls through any number of rows of the same height, but only builds,
+/// lays out and draws the rows which are visible.
+///
+/// Rows are built by `view` as they are first needed and reused for other items as the list
+/// scrolls, so a list of a million items only ever holds a screenful of elements. Each row
+/// gets a `RowIndex` telling it which item it shows. The row height is measured from the first
+/// row shown unless it is given with `with_row_height`. Like a `ScrollView`, the list expands in
+/// both directions by default.
+pub struct VirtualList<A> {
+    style: LayoutStyle,
+    len: Derived<A, usize>,
+    view: Box<dyn Fn(RowIndex) -> Element<A>>,
+    // in logical pixels, fixed if given, otherwise measured on layout
+    fixed_row_height: Option<f32>,
+    row_height: Cell<f32>,
+    viewport_height: Cell<f32>,
+    // bumped by layout when the row or viewport height changes, so that update picks new rows
+    metrics_revision: RwSignal<u64>,
+    // in logical pixels, from the top of the first row
+    offset: RwSignal<f32>,
+
+    slots: RefCell<Vec<Slot<A>>>,
+    // which slots are shown, in order, starting with the item at `first`
+    shown: RefCell<Vec<usize>>,
+    first: Cell<usize>,
+    // bumped whenever the shown rows change, so that layout is redone
+    revision: RwSignal<u64>,
+
+    update_cache: Computed<()>,
+    prelayout_cache: Computed2<PrelayoutInput, math::Size>,
+    layout_cache: Computed2<LayoutInput, Layout>,
+    interactions: Computed<InteractSet>
+}
+
+impl<A> VirtualList<A> {
+    /// A list of `len` items, with rows built by `view`.
+    pub fn new(len: impl Fn(&mut A) -> usize + 'static, view: impl Fn(RowIndex) -> Element<A> + 'static) -> VirtualList<A> {
+        VirtualList {
+            style: LayoutStyle {
+                border_size: 0.0,
+                padding: 0.0.into(),
+                margin: 0.0.into(),
+                width: Sizing::Expand(1.0),
+                height: Sizing::Expand(1.0),
+                pointer_events: PointerEvents::Auto,
+                position: Position::Flow
+            },
+            len: Derived::new(len),
+            view: Box::new(view),
+            fixed_row_height: None,
+            row_height: Cell::new(0.0),
+            viewport_height: Cell::new(0.0),
+            metrics_revision: RwSignal::new(0),
+            offset: RwSignal::new(0.0),
+
+            slots: RefCell::new(Vec::new()),
+            shown: RefCell::new(Vec::new()),
+            first: Cell::new(0),
+            revision: RwSignal::new(0),
+
+            update_cache: Computed::new(),
+            prelayout_cache: Computed2::new(),
+            layout_cache: Computed2::new(),
+            interactions: Computed::new()
+        }
+    }
+
+    /// Gives every row a height of `height` logical pixels instead of measuring the first one.
+    pub fn with_row_height(mut self, height: f32) -> VirtualList<A> {
+        self.fixed_row_height = Some(height.max(1.0));
+        self.row_height.set(height.max(1.0));
+        self
+    }
+
+    pub fn with_width(mut self, width: Sizing) -> VirtualList<A> {
+        self.style.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: Sizing) -> VirtualList<A> {
+        self.style.height = height;
+        self
+    }
+
+    /// How far the list is scrolled, in logical pixels.
+    pub fn offset(&self) -> f32 {
+        self.offset.get()
+    }
+
+    /// Scrolls to `offset` logical pixels from the top, as far as the rows allow.
+    pub fn scroll_to(&self, offset: f32) {
+        let offset = offset.clamp(0.0, self.max_offset());
+        if offset != self.offset.get_untracked() {
+            self.offset.update(|current| *current = offset);
+        }
+    }
+
+    pub fn scroll_by(&self, delta: f32) {
+        self.scroll_to(self.offset.get_untracked() + delta);
+    }
+
+    fn max_offset(&self) -> f32 {
+        let content = self.len.get_untracked() as f32 * self.row_height.get();
+        (content - self.viewport_height.get()).max(0.0)
+    }
+
+    fn viewport(&self) -> math::Rect {
+        self.layout_cache.get_untracked().content_box
+    }
+
+    // the first item shown and how many are, given the current offset and heights
+    fn window(&self, len: usize) -> (usize, usize) {
+        let row_height = self.row_height.get();
+        if row_height <= 0.0 {
+            // nothing measured yet, so only the first row is shown to measure it
+            return (0, len.min(1));
+        }
+        let offset = self.offset.get().min(self.max_offset());
+        let first = ((offset / row_height) as usize).min(len);
+        let count = (self.viewport_height.get() / row_height).ceil() as usize + 1;
+        (first, count.min(len - first))
+    }
+
+    // points the slots at the items in the window, building more slots if there aren't enough
+    fn sync_slots(&self, len: usize) where A: 'static {
+        let (first, count) = self.window(len);
+        let mut slots = self.slots.borrow_mut();
+        while slots.len() < count {
+            let index = Rc::new(RwSignal::new(usize::MAX));
+            let element = (self.view)(RowIndex(Rc::clone(&index)));
+            slots.push(Slot { index, element });
+        }
+
+        // each item keeps the same slot while it stays in view, so scrolling by one row only
+        // moves one slot to a new item
+        let shown: Vec<usize> = (first..first + count).map(|item| item % slots.len().max(1)).collect();
+        for (item, &slot) in (first..first + count).zip(&shown) {
+            let index = &slots[slot].index;
+            if index.get_untracked() != item {
+                index.update(|current| *current = item);
+            }
+        }
+        drop(slots);
+
+        if self.first.get() != first || *self.shown.borrow() != shown {
+            self.first.set(first);
+            *self.shown.borrow_mut() = shown;
+            self.revision.update(|revision| *revision += 1);
+        }
+    }
+
+    fn for_each_shown(&self, mut f: impl FnMut(usize, &Element<A>)) {
+        let slots = self.slots.borrow();
+        for (item, &slot) in (self.first.get()..).zip(self.shown.borrow().iter()) {
+            f(item, &slots[slot].element);
+        }
+    }
+}
+
+impl<A: 'static> From<VirtualList<A>> for Element<A> {
+    fn from(value: VirtualList<A>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<A: 'static> Widget<A> for VirtualList<A> {
+    fn update(&self, model: &mut A) {
+        self.update_cache.maybe_update(|| {
+            self.len.maybe_update(model);
+            let len = self.len.get();
+            self.metrics_revision.track();
+            self.sync_slots(len);
+            self.for_each_shown(|_, element| element.update(model));
+        });
+        self.update_cache.track();
+    }
+
+    fn prelayout(&self, ctx: &LayoutContext, input: PrelayoutInput) -> LayoutCharacteristics {
+        self.prelayout_cache.maybe_update(input, |&input| {
+            self.revision.track();
+            let spacing = input.scale_factor * self.style.spacing_size();
+            let viewport = input.available - spacing.sum_axes();
+            let row_available = math::Size::new(viewport.horizontal, f32::INFINITY);
+            let mut width: f32 = 0.0;
+            self.for_each_shown(|_, element| {
+                let min_size = element.prelayout(ctx, PrelayoutInput { available: row_available, ..input }).min_size;
+                width = width.max(min_size.horizontal);
+            });
+            // vertically the list only needs as much as it can get
+            let content = self.len.get_untracked() as f32 * self.row_height.get() * input.scale_factor;
+            math::Size::new(width, content.min(viewport.vertical.max(0.0))) + spacing.sum_axes()
+        });
+        LayoutCharacteristics { layout_style: self.style, min_size: self.prelayout_cache.get(), text_metrics: None }
+    }
+
+    fn layout(&self, ctx: &LayoutContext, input: LayoutInput) {
+        self.layout_cache.maybe_update(input, |&input| {
+            self.prelayout_cache.track();
+            let layout = Layout::from_layout_input(&self.style, input);
+            let viewport = layout.content_box;
+            let scale_factor = input.scale_factor;
+
+            let row_height = self.fixed_row_height.unwrap_or_else(|| {
+                let mut measured = self.row_height.get();
+                self.for_each_shown(|item, element| {
+                    if item == self.first.get() {
+                        let available = math::Size::new(viewport.width(), f32::INFINITY);
+                        let min_size = element.prelayout(ctx, PrelayoutInput { available, scale_factor }).min_size;
+                        measured = (min_size.vertical / scale_factor).max(1.0);
+                    }
+                });
+                measured
+            });
+            let viewport_height = viewport.height() / scale_factor;
+            if row_height != self.row_height.get() || viewport_height != self.viewport_height.get() {
+                self.row_height.set(row_height);
+                self.viewport_height.set(viewport_height);
+                self.metrics_revision.update(|revision| *revision += 1);
+            }
+
+            let offset = self.offset.get().min(self.max_offset());
+            self.for_each_shown(|item, element| {
+                let top = viewport.top() + (item as f32 * row_height - offset) * scale_factor;
+                let allocated = math::Rect::from_xywh(viewport.left(), top, viewport.width(), row_height * scale_factor);
+                element.layout(ctx, LayoutInput { allocated, scale_factor });
+            });
+            layout
+        });
+        self.layout_cache.track();
+    }
+
+    fn last_layout(&self) -> Option<Layout> {
+        Some(self.layout_cache.get_untracked())
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&Element<A>)) {
+        self.for_each_shown(|_, element| f(element))
+    }
+
+    fn clips_children(&self) -> bool {
+        true
+    }
+
+    fn cursor(&self, point: math::Point) -> Option<Cursor> {
+        if !self.viewport().contains(point) {
+            return None;
+        }
+        let mut cursor = None;
+        self.for_each_shown(|_, element| cursor = element.cursor(point).or(cursor));
+        cursor
+    }
+
+    fn interactions(&self) -> InteractSet {
+        self.interactions.maybe_update(|| {
+            if self.style.pointer_events == PointerEvents::None {
+                return InteractSet::empty();
+            }
+            self.revision.track();
+            let viewport = self.layout_cache.get().content_box;
+            let mut set = InteractSet::empty().with(Capability::Scroll, viewport);
+            self.for_each_shown(|_, element| set = set | element.interactions());
+            set
+        });
+        self.interactions.get()
+    }
+
+    fn handle_interaction(&mut self, interaction: &Interaction, model: &mut A) {
+        let viewport = self.viewport();
+        match interaction {
+            &Interaction::Scroll { delta, position } => {
+                if !viewport.contains(position) {
+                    return;
+                }
+                // a scrollable element in a row under the cursor scrolls first
+                let mut nested = false;
+                self.for_each_shown(|_, element| nested |= element.interactions().accepts(interaction));
+                if !nested {
+                    let scale_factor = self.layout_cache.get_untracked().scale_factor;
+                    return self.scroll_by(delta.y / scale_factor);
+                }
+            }
+            // rows scrolled partly out of view can't be clicked there
+            Interaction::Click(point) | Interaction::Press(point) if !viewport.contains(*point) => return,
+            _ => {}
+        }
+        let interaction = match interaction {
+            Interaction::Hover(Some(point)) if !viewport.contains(*point) => &Interaction::Hover(None),
+            _ => interaction
+        };
+        let shown = self.shown.borrow().clone();
+        let slots = self.slots.get_mut();
+        for slot in shown {
+            slots[slot].element.handle_interaction(interaction, model);
+        }
+    }
+
+    fn draw(&mut self, context: &mut RenderContext) {
+        let layout = self.layout_cache.get_untracked();
+        let viewport = layout.content_box;
+        let shown = self.shown.borrow().clone();
+        let slots = self.slots.get_mut();
+        draw_layer(context, 1.0, Some(viewport), |context| {
+            for slot in shown {
+                slots[slot].element.draw(context);
+            }
+        });
+
+        let content = self.len.get_untracked() as f32 * self.row_height.get() * layout.scale_factor;
+        let visible = viewport.height();
+        if content <= visible {
+            return;
+        }
+        let width = SCROLLBAR_WIDTH * layout.scale_factor;
+        let length = visible * visible / content;
+        let start = self.offset.get_untracked().min(self.max_offset()) * layout.scale_factor * visible / content;
+        let thumb = math::Rect::from_xywh(viewport.right() - width, viewport.top() + start, width, length);
+        if let Ok(thumb) = tiny_skia::Rect::try_from(thumb) {
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color(SCROLLBAR_COLOR.into());
+            context.canvas.fill_rect(thumb, &paint, tiny_skia::Transform::identity(), None);
+        }
+    }
+
+    fn page_breaks(&self, hints: &mut Vec<BreakHint>) {
+        self.for_each_shown(|_, element| element.page_breaks(hints))
+    }
+}
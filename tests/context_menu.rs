@@ -0,0 +1,50 @@
+#![cfg(feature = "text")]
+
+use yoru::{div, math, Div, Element, Interaction, Modals, Root, Sizing, TextContext};
+use yoru::widgets::{Button, ContextMenu};
+
+#[derive(Default)]
+struct Model {
+    clicks: u32,
+    copied: bool
+}
+
+fn fixed(width: f32, height: f32) -> Element<Model> {
+    div!(width=Sizing::Fixed(width), height=Sizing::Fixed(height)).into()
+}
+
+fn frame(root: &mut Root<Model>, model: &mut Model) {
+    root.update(model);
+    root.layout(&TextContext::new(), math::Size::new(200.0, 100.0), 1.0);
+    root.interactions();
+}
+
+#[test]
+fn open_menu_takes_input_from_the_tree() {
+    let modals = Modals::new();
+    let menu = ContextMenu::new(modals.clone(), fixed(50.0, 50.0))
+        .with_item("Copy", |model: &mut Model| model.copied = true);
+    let mut row = Div::row();
+    row.add_child(menu);
+    row.add_child(Button::new(fixed(50.0, 50.0), |model: &mut Model| model.clicks += 1));
+    let mut root = Root::new(row.into()).with_modals(modals);
+    let model = &mut Model::default();
+    let (on_menu, on_button) = (math::Point::new(20.0, 20.0), math::Point::new(90.0, 20.0));
+    frame(&mut root, model);
+    root.handle_interaction(&Interaction::Click(on_button), model);
+    assert_eq!(model.clicks, 1);
+
+    root.handle_interaction(&Interaction::SecondaryClick(on_menu), model);
+    assert_eq!(root.modals().len(), 1);
+    frame(&mut root, model);
+    // closes the menu without reaching the button beneath it
+    root.handle_interaction(&Interaction::Click(on_button), model);
+    assert_eq!(model.clicks, 1);
+    assert!(root.modals().is_empty());
+
+    root.handle_interaction(&Interaction::SecondaryClick(on_menu), model);
+    frame(&mut root, model);
+    root.handle_interaction(&Interaction::Click(on_menu + math::Vector::new(8.0, 8.0)), model);
+    assert!(model.copied);
+    assert!(root.modals().is_empty());
+}
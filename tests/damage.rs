@@ -0,0 +1,58 @@
+use tiny_skia::Pixmap;
+use yoru::{div, math, Color, Damage, Div, Element, Overlays, PixmapPool, RenderContext, Root, Sizing, TextContext, Theme};
+use yoru::tracking::{ReadableSignal, RwSignal};
+use yoru::widgets::Show;
+
+struct Model {
+    visible: RwSignal<bool>
+}
+
+fn tree() -> Element<Model> {
+    let mut row = Div::row();
+    row.add_child(div!(width=Sizing::Fixed(50.0), height=Sizing::Fixed(50.0), background=Color::RED));
+    row.add_child(Show::new(
+        |model: &mut Model| model.visible.get(),
+        div!(width=Sizing::Fixed(50.0), height=Sizing::Fixed(50.0), background=Color::BLUE)
+    ));
+    row.into()
+}
+
+fn frame(root: &mut Root<Model>, model: &mut Model, text: &TextContext, canvas: &mut Pixmap) -> Damage {
+    let viewport = math::Size::new(canvas.width() as f32, canvas.height() as f32);
+    root.update(model);
+    root.layout(text, viewport, 1.0);
+    root.interactions();
+    let theme = Theme::default();
+    root.draw_damage(&mut RenderContext { canvas: canvas.as_mut(), text, pool: &PixmapPool::new(), theme: &theme, overlays: &Overlays::new() })
+}
+
+#[test]
+fn only_what_changed_is_drawn_again() {
+    let text = TextContext::new();
+    let mut model = Model { visible: RwSignal::new(true) };
+    let mut root = Root::new(tree());
+    let mut canvas = Pixmap::new(200, 100).unwrap();
+    assert_eq!(frame(&mut root, &mut model, &text, &mut canvas), Damage::Full);
+    assert!(frame(&mut root, &mut model, &text, &mut canvas).is_empty());
+
+    let red = bounds_of(&canvas, Color::RED).unwrap();
+    model.visible.update(|visible| *visible = false);
+    let Damage::Rects(rects) = frame(&mut root, &mut model, &text, &mut canvas) else { panic!("expected damaged rects") };
+    assert!(!rects.is_empty());
+    // the first child stays as it was
+    assert!(rects.iter().all(|rect| !rect.intersects(&red)));
+
+    let mut expected = Pixmap::new(200, 100).unwrap();
+    frame(&mut Root::new(tree()), &mut model, &text, &mut expected);
+    assert!(canvas == expected);
+}
+
+// the rect around the pixels of `color`
+fn bounds_of(canvas: &Pixmap, color: Color) -> Option<math::Rect> {
+    let color = tiny_skia::Color::from(color).premultiply().to_color_u8();
+    let pixels = canvas.pixels().iter().enumerate().filter(|(_, pixel)| **pixel == color);
+    math::Rect::bounding_box(pixels.map(|(index, _)| {
+        let (x, y) = (index as u32 % canvas.width(), index as u32 / canvas.width());
+        math::Rect::from_xywh(x as f32, y as f32, 1.0, 1.0)
+    }))
+}
@@ -0,0 +1,51 @@
+use yoru::{div, math, Div, ElementInfo, Root, Sizing, TextContext, Visitor, Wrap};
+
+// the margin box of each child of the root's element
+#[derive(Default)]
+struct Children(Vec<math::Rect>);
+
+impl Visitor for Children {
+    fn enter(&mut self, element: &ElementInfo) {
+        if let (1, Some(layout)) = (element.depth, element.layout) {
+            self.0.push(layout.margin_box);
+        }
+    }
+
+    fn leave(&mut self, _element: &ElementInfo) { }
+}
+
+// lays `div` out over a 200 by 100 viewport; its content box starts 5 pixels in
+fn lay_out(div: Div<()>) -> Vec<math::Rect> {
+    let mut root = Root::new(div.into());
+    root.update(&mut ());
+    root.layout(&TextContext::new(), math::Size::new(200.0, 100.0), 1.0);
+    let mut children = Children::default();
+    root.visit(&mut children);
+    children.0
+}
+
+#[test]
+fn expanding_children_share_what_the_gaps_leave() {
+    let mut row = div!(axis=math::Axis::Horizontal, gap=10.0);
+    row.add_child(div!(width=Sizing::Fixed(50.0)));
+    row.add_child(div!(width=Sizing::Expand(1.0)));
+    let children = lay_out(row);
+
+    assert_eq!(children[0].right(), 65.0);
+    assert_eq!(children[1].left(), 75.0);
+    assert_eq!(children[1].right(), 195.0);
+}
+
+#[test]
+fn wrapping_counts_the_gaps() {
+    // the three children would fit into the 190 pixels without the gaps between them
+    let mut row = div!(axis=math::Axis::Horizontal, gap=10.0, wrap=Wrap::Wrap { spacing: 0.0 });
+    for _ in 0..3 {
+        row.add_child(div!(width=Sizing::Fixed(50.0), height=Sizing::Fixed(10.0)));
+    }
+    let children = lay_out(row);
+
+    assert_eq!(children[1].left(), children[0].right() + 10.0);
+    assert_eq!(children[2].left(), 5.0);
+    assert!(children[2].top() >= children[0].bottom());
+}
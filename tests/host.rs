@@ -0,0 +1,95 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use tiny_skia::PixmapMut;
+use yoru::{div, math, Interaction, Key, KeyInput, Modifiers, NamedKey, Root, TextContext};
+use yoru::widgets::{Host, HostedUi};
+
+// records what reaches it, and acts on Enter only
+struct Recorder {
+    events: Rc<RefCell<Vec<String>>>
+}
+
+impl Recorder {
+    fn record(&self, event: String) -> bool {
+        self.events.borrow_mut().push(event);
+        true
+    }
+}
+
+impl HostedUi<()> for Recorder {
+    fn preferred_size(&self) -> math::Size {
+        math::Size::new(40.0, 20.0)
+    }
+
+    fn click(&mut self, _model: &mut (), position: math::Point) -> bool {
+        self.record(format!("click {} {}", position.x, position.y))
+    }
+
+    fn hover(&mut self, _model: &mut (), position: Option<math::Point>) -> bool {
+        self.record(match position {
+            Some(position) => format!("hover {} {}", position.x, position.y),
+            None => "leave".to_string()
+        })
+    }
+
+    fn press(&mut self, _model: &mut (), position: math::Point) -> bool {
+        self.record(format!("press {} {}", position.x, position.y))
+    }
+
+    fn drag(&mut self, _model: &mut (), position: math::Point) -> bool {
+        self.record(format!("drag {} {}", position.x, position.y))
+    }
+
+    fn release(&mut self, _model: &mut (), position: math::Point) -> bool {
+        self.record(format!("release {} {}", position.x, position.y))
+    }
+
+    fn scroll(&mut self, _model: &mut (), delta: math::Vector, position: math::Point) -> bool {
+        self.record(format!("scroll {} at {} {}", delta.y, position.x, position.y))
+    }
+
+    fn key(&mut self, _model: &mut (), input: &KeyInput) -> bool {
+        input.logical == Key::Named(NamedKey::Enter) && self.record("enter".to_string())
+    }
+
+    fn text(&mut self, _model: &mut (), text: &str, _modifiers: Modifiers) -> bool {
+        self.record(format!("text {text}"))
+    }
+
+    fn paint(&mut self, _canvas: &mut PixmapMut, _scale_factor: f32) { }
+}
+
+fn press(logical: Key) -> KeyInput {
+    KeyInput { logical, physical: None, modifiers: Modifiers::default(), repeat: false }
+}
+
+#[test]
+fn host_forwards_input_in_local_coordinates() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    // the host starts 13 pixels in, past the margin, border and padding of the wrapper
+    let mut wrapper = div!(padding=10.0);
+    wrapper.add_child(Host::new(Recorder { events: Rc::clone(&events) }));
+    let mut root = Root::new(wrapper.into());
+    let model = &mut ();
+    root.update(model);
+    root.layout(&TextContext::new(), math::Size::new(100.0, 100.0), 1.0);
+    root.interactions();
+
+    let point = |x, y| math::Point::new(x, y);
+    root.handle_interaction(&Interaction::Hover(Some(point(18.0, 15.0))), model);
+    root.handle_interaction(&Interaction::Press(point(18.0, 15.0)), model);
+    root.handle_interaction(&Interaction::Drag(point(83.0, 15.0)), model);
+    root.handle_interaction(&Interaction::Release(point(83.0, 15.0)), model);
+    root.handle_interaction(&Interaction::Hover(Some(point(83.0, 15.0))), model);
+    root.handle_interaction(&Interaction::Scroll { delta: math::Vector::new(0.0, 40.0), position: point(23.0, 23.0) }, model);
+    root.handle_interaction(&Interaction::Click(point(15.0, 17.0)), model);
+    assert!(root.handle_key(press(Key::Named(NamedKey::Enter)), model));
+    assert!(!root.handle_key(press(Key::Character('s')), model));
+    root.handle_text("s".to_string(), Modifiers::default(), model);
+
+    assert_eq!(*events.borrow(), [
+        "hover 5 2", "press 5 2", "drag 70 2", "release 70 2", "leave", "scroll 40 at 10 10",
+        "click 2 4", "enter", "text s"
+    ]);
+}
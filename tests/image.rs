@@ -0,0 +1,36 @@
+use tiny_skia::{Pixmap, PremultipliedColorU8};
+use yoru::{math, Color, Overlays, PixmapPool, RenderContext, Root, TextContext, Theme};
+use yoru::widgets::{Image, ImageFit, ImageSampling};
+
+// a red pixel next to a blue one, stretched to 20 by 10 pixels
+fn draw(sampling: ImageSampling) -> Pixmap {
+    let mut source = Pixmap::new(2, 1).unwrap();
+    source.pixels_mut()[0] = PremultipliedColorU8::from_rgba(255, 0, 0, 255).unwrap();
+    source.pixels_mut()[1] = PremultipliedColorU8::from_rgba(0, 0, 255, 255).unwrap();
+    let image = Image::new(source).with_fit(ImageFit::Fill).with_sampling(sampling);
+
+    let text = TextContext::new();
+    let mut root = Root::new(image.into());
+    let mut canvas = Pixmap::new(20, 10).unwrap();
+    root.update(&mut ());
+    root.layout(&text, math::Size::new(20.0, 10.0), 1.0);
+    let theme = Theme::default();
+    root.draw(&mut RenderContext { canvas: canvas.as_mut(), text: &text, pool: &PixmapPool::new(), theme: &theme, overlays: &Overlays::new() });
+    canvas
+}
+
+#[test]
+fn nearest_sampling_keeps_pixels_sharp() {
+    let red = tiny_skia::Color::from(Color::RED).premultiply().to_color_u8();
+    let nearest = draw(ImageSampling::Nearest);
+    assert_eq!(nearest.pixel(9, 5).unwrap(), red);
+
+    let bilinear = draw(ImageSampling::Bilinear);
+    assert_ne!(bilinear.pixel(9, 5).unwrap(), red);
+}
+
+#[cfg(feature = "jpeg")]
+#[test]
+fn invalid_jpeg_is_an_error() {
+    assert!(matches!(Image::from_jpeg(b"not a jpeg"), Err(yoru::Error::InvalidImage(_))));
+}
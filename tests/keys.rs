@@ -0,0 +1,35 @@
+use yoru::{div, math, FocusId, Handled, Key, KeyInput, Modifiers, NamedKey, Root, Sizing, TextContext};
+use yoru::tracking::{ReadableSignal, RwSignal};
+use yoru::widgets::Focusable;
+
+struct Model {
+    enters: RwSignal<u32>
+}
+
+fn press(logical: Key) -> KeyInput {
+    KeyInput { logical, physical: None, modifiers: Modifiers::default(), repeat: false }
+}
+
+#[test]
+fn unused_keys_pass_the_focused_widget() {
+    let id = FocusId::unique();
+    let field = Focusable::new(div!(width=Sizing::Fixed(20.0), height=Sizing::Fixed(20.0)))
+        .with_id(id)
+        .on_key_down(|model: &mut Model, input| match input.logical {
+            Key::Named(NamedKey::Enter) => {
+                model.enters.update(|enters| *enters += 1);
+                Handled::Yes
+            }
+            _ => Handled::No
+        });
+    let mut model = Model { enters: RwSignal::new(0) };
+    let mut root = Root::new(field.into());
+    root.update(&mut model);
+    root.layout(&TextContext::new(), math::Size::new(100.0, 100.0), 1.0);
+    root.interactions();
+    root.set_focus(Some(id), &mut model);
+
+    assert!(root.handle_key(press(Key::Named(NamedKey::Enter)), &mut model));
+    assert_eq!(model.enters.get_untracked(), 1);
+    assert!(!root.handle_key(press(Key::Character('s')), &mut model));
+}
@@ -0,0 +1,27 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use yoru::{div, math, OverflowCheck, Root, Sizing, TextContext};
+
+#[test]
+fn overflowing_children_are_reported() {
+    let reported = Rc::new(RefCell::new(Vec::new()));
+    // the root's own element always fills the viewport, so the overflow happens a level down
+    let mut parent = div!(width=Sizing::Fixed(50.0), height=Sizing::Fixed(50.0));
+    parent.add_child(div!(width=Sizing::Fixed(100.0), height=Sizing::Fixed(10.0)));
+    let mut top = div!();
+    top.add_child(parent);
+    let mut root = Root::new(top.into())
+        .with_overflow_check(OverflowCheck::Report)
+        .on_overflow({
+            let reported = Rc::clone(&reported);
+            move |overflow| reported.borrow_mut().push(overflow.path.clone())
+        });
+    let text = TextContext::new();
+    root.update(&mut ());
+    root.layout(&text, math::Size::new(200.0, 200.0), 1.0);
+    root.layout(&text, math::Size::new(300.0, 200.0), 1.0);
+
+    assert_eq!(root.overflows().len(), 1);
+    assert_eq!(*reported.borrow(), [root.overflows()[0].path.clone()]);
+}
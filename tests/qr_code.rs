@@ -0,0 +1,29 @@
+#![cfg(feature = "qr")]
+
+use yoru::{math, Root, TextContext};
+use yoru::tracking::{ReadableSignal, RwSignal};
+use yoru::widgets::QrCode;
+
+struct Model {
+    text: RwSignal<String>,
+    validity: Vec<bool>
+}
+
+#[test]
+fn text_too_long_to_encode_is_reported() {
+    let code = QrCode::new(|model: &mut Model| model.text.get())
+        .on_validity_change(|model, valid| model.validity.push(valid));
+    let mut model = Model { text: RwSignal::new("yoru".to_string()), validity: Vec::new() };
+    let mut root = Root::new(code.into());
+    let text = TextContext::new();
+    root.update(&mut model);
+    assert_ne!(root.measure(&text, math::Size::new(1000.0, 1000.0), 1.0), math::Size::new(0.0, 0.0));
+
+    model.text.update(|text| *text = "a".repeat(4000));
+    root.update(&mut model);
+    assert_eq!(root.measure(&text, math::Size::new(1000.0, 1000.0), 1.0), math::Size::new(0.0, 0.0));
+
+    model.text.update(|text| *text = "yoru".to_string());
+    root.update(&mut model);
+    assert_eq!(model.validity, [false, true]);
+}
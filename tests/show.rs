@@ -0,0 +1,47 @@
+use yoru::{div, math, Element, Root, Sizing, TextContext};
+use yoru::tracking::{ReadableSignal, RwSignal};
+use yoru::widgets::Show;
+
+struct Model {
+    visible: RwSignal<bool>
+}
+
+fn fixed(width: f32, height: f32) -> Element<Model> {
+    div!(width=Sizing::Fixed(width), height=Sizing::Fixed(height)).into()
+}
+
+fn measure(root: &mut Root<Model>, model: &mut Model, text: &TextContext) -> math::Size {
+    root.update(model);
+    root.measure(text, math::Size::new(200.0, 200.0), 1.0)
+}
+
+#[test]
+fn show_follows_the_predicate() {
+    let text = TextContext::new();
+    let mut model = Model { visible: RwSignal::new(false) };
+    let shown = measure(&mut Root::new(fixed(60.0, 40.0)), &mut model, &text);
+    let fallback = measure(&mut Root::new(fixed(20.0, 20.0)), &mut model, &text);
+
+    let show = Show::new(|model: &mut Model| model.visible.get(), fixed(60.0, 40.0))
+        .with_fallback(fixed(20.0, 20.0));
+    let mut root = Root::new(show.into());
+    assert_eq!(measure(&mut root, &mut model, &text), fallback);
+
+    model.visible.update(|visible| *visible = true);
+    assert_eq!(measure(&mut root, &mut model, &text), shown);
+    root.layout(&text, shown, 1.0);
+
+    model.visible.update(|visible| *visible = false);
+    assert_eq!(measure(&mut root, &mut model, &text), fallback);
+}
+
+#[test]
+fn show_without_fallback_takes_no_space() {
+    let text = TextContext::new();
+    let mut model = Model { visible: RwSignal::new(true) };
+    let mut root = Root::new(Show::new(|model: &mut Model| model.visible.get(), fixed(60.0, 40.0)).into());
+    assert_ne!(measure(&mut root, &mut model, &text), math::Size::new(0.0, 0.0));
+
+    model.visible.update(|visible| *visible = false);
+    assert_eq!(measure(&mut root, &mut model, &text), math::Size::new(0.0, 0.0));
+}
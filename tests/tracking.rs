@@ -0,0 +1,45 @@
+use yoru::tracking::{self, Computed, ReadableSignal, RwSignal};
+
+// reads `count`, and bumps it the first time
+fn read_and_bump(count: &RwSignal<i32>) -> i32 {
+    let value = count.get();
+    if value == 0 {
+        count.update(|count| *count += 1);
+    }
+    value
+}
+
+#[test]
+fn write_during_recompute_leaves_it_dirty() {
+    let count = RwSignal::new(0);
+    let computed = Computed::new();
+    computed.maybe_update(|| read_and_bump(&count));
+    assert_eq!(computed.get_untracked(), 0);
+    assert!(computed.is_dirty());
+
+    computed.maybe_update(|| read_and_bump(&count));
+    assert_eq!(computed.get_untracked(), 1);
+    assert!(!computed.is_dirty());
+}
+
+#[test]
+fn batch_defers_dirtiness_until_it_ends() {
+    let count = RwSignal::new(0);
+    let computed = Computed::new();
+    computed.maybe_update(|| count.get());
+    assert!(!computed.is_dirty());
+
+    tracking::batch(|| {
+        count.update(|count| *count += 1);
+        assert_eq!(count.get_untracked(), 1);
+        assert!(!computed.is_dirty());
+    });
+    assert!(computed.is_dirty());
+}
+
+#[test]
+#[should_panic(expected = "a signal was written from inside `with` on the same signal")]
+fn write_inside_own_with_panics() {
+    let count = RwSignal::new(0);
+    count.with(|_| count.update(|count| *count += 1));
+}
@@ -0,0 +1,509 @@
+use bytemuck::Zeroable;
+use crate::math;
+use crate::style::LayoutStyle;
+
+#[derive(Copy, Clone, PartialEq, Debug, Zeroable, Default)]
+pub struct PrelayoutInput {
+    pub available: math::Size,
+    pub scale_factor: f32,
+}
+
+/// Vertical font metrics of an element containing text, in physical pixels.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct TextMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    /// The distance from the top of the margin box to the first baseline.
+    pub first_baseline: f32
+}
+
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct LayoutCharacteristics {
+    pub layout_style: LayoutStyle,
+    pub min_size: math::Size,
+    pub text_metrics: Option<TextMetrics>
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, Zeroable)]
+pub struct LayoutInput {
+    pub allocated: math::Rect,
+    pub scale_factor: f32,
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Default, Zeroable)]
+pub struct Layout {
+    pub margin_box: math::Rect,
+    pub border_box: math::Rect,
+    pub half_border_box: math::Rect,
+    pub padding_box: math::Rect,
+    pub content_box: math::Rect,
+    pub scale_factor: f32,
+}
+
+impl Layout {
+    pub fn from_margin_box(style: &LayoutStyle, scale_factor: f32, margin_box: math::Rect) -> Layout {
+        let border_box = margin_box.shrink_by(scale_factor * style.margin);
+        let half_border_box = margin_box.shrink_by(scale_factor * (style.margin + 0.5 * math::SizeRect::from_border(style.border_size)));
+        let padding_box = margin_box.shrink_by(scale_factor * (style.margin + math::SizeRect::from_border(style.border_size)));
+        let content_box = margin_box.shrink_by(scale_factor * (style.margin + style.padding + math::SizeRect::from_border(style.border_size)));
+        Layout {
+            margin_box: margin_box.clamp_positive(),
+            border_box: border_box.clamp_positive(),
+            half_border_box: half_border_box.clamp_positive(),
+            padding_box: padding_box.clamp_positive(),
+            content_box: content_box.clamp_positive(),
+            scale_factor
+        }
+    }
+
+    pub fn from_layout_input(style: &LayoutStyle, input: LayoutInput) -> Layout {
+        Layout::from_margin_box(style, input.scale_factor, input.allocated)
+    }
+}
+
+/// Something that can be measured by the container layout algorithm, given a context `C` holding
+/// whatever measuring needs (e.g. fonts).
+pub trait LayoutNode<C: ?Sized> {
+    fn prelayout(&self, ctx: &C, input: PrelayoutInput) -> LayoutCharacteristics;
+}
+
+pub mod leaf {
+    use crate::math;
+    use crate::layout::{LayoutCharacteristics, LayoutInput, PrelayoutInput};
+    use crate::style::LayoutStyle;
+
+    pub fn do_prelayout(style: &LayoutStyle, input: PrelayoutInput, measure: impl FnOnce(math::Size, f32) -> math::Size) -> LayoutCharacteristics {
+        let spacing = input.scale_factor * (style.margin + style.padding + math::SizeRect::from_border(style.border_size));
+        let content_box = input.available - spacing.sum_axes();
+        let measured_size = measure(content_box, input.scale_factor);
+        LayoutCharacteristics { layout_style: *style, min_size: measured_size, text_metrics: None }
+    }
+
+    pub fn do_layout(_style: &LayoutStyle, _input: LayoutInput) {
+
+    }
+}
+
+
+pub mod container {
+    use alloc::vec::Vec;
+    use core::ops::Range;
+
+    use crate::math;
+    use crate::layout::{LayoutCharacteristics, LayoutInput, LayoutNode, PrelayoutInput, TextMetrics};
+    use crate::style::{ContainerLayoutStyle, Direction, Justify, LayoutStyle, Position, Sizing, Wrap};
+
+    #[allow(dead_code)]
+    struct MeasuredChildren {
+        content_size: math::Size,
+        child_content_sizes: Vec<(Sizing, Sizing, math::Size)>,
+        child_metrics: Vec<Option<TextMetrics>>,
+        // the children taken out of the flow, with their index among all children
+        positioned: Vec<(usize, LayoutStyle, math::Size)>,
+        // the lowest first baseline among the children, when aligning them by baseline
+        max_baseline: f32,
+        total_main_space: f32,
+        // taken by the children which don't expand
+        fixed_main_space: f32,
+        max_cross_space: f32,
+        total_expand_factor: f32,
+        max_space_per_expand: f32,
+    }
+
+    fn measure_children<'a, C: ?Sized, N: LayoutNode<C> + 'a>(style: &ContainerLayoutStyle, ctx: &C, available: math::Size, scale_factor: f32, children: impl IntoIterator<Item=&'a N>) -> MeasuredChildren {
+        use crate::math::Axis;
+
+        let spacing = scale_factor * (style.layout_style.margin + style.layout_style.padding + math::SizeRect::from_border(style.layout_style.border_size));
+        let main_axis = style.main_axis;
+        let cross_axis = main_axis.cross();
+        let (main_sizing, cross_sizing) = {
+            match main_axis {
+                Axis::Vertical => (style.layout_style.height, style.layout_style.width),
+                Axis::Horizontal => (style.layout_style.width, style.layout_style.height)
+            }
+        };
+
+        let available_content_size = available - spacing.sum_axes();
+        let cross_available = available_content_size.axis(cross_axis);
+        // what percentages of the content box are taken of
+        let main_limit = main_sizing.as_definite(scale_factor).unwrap_or(available_content_size.axis(main_axis));
+        let cross_limit = cross_sizing.as_definite(scale_factor).unwrap_or(cross_available);
+
+        let mut child_content_sizes = Vec::new();
+        let mut child_metrics = Vec::new();
+        let mut positioned = Vec::new();
+        let mut total_main_space: f32 = 0.0;
+        let mut max_cross_space: f32 = 0.0;
+        let mut total_expand_factor: f32 = 0.0;
+        let mut max_space_per_expand: f32 = 0.0;
+        for (index, child) in children.into_iter().enumerate() {
+            let child_characteristics = child.prelayout(ctx, PrelayoutInput {
+                available: math::Size::from_axes(main_axis, f32::INFINITY, cross_available),
+                scale_factor
+            });
+            if let Position::Anchored { .. } = child_characteristics.layout_style.position {
+                positioned.push((index, child_characteristics.layout_style, child_characteristics.min_size));
+                continue;
+            }
+
+            let (child_main_sizing, child_cross_sizing) = {
+                let child_style = child_characteristics.layout_style;
+                match main_axis {
+                    Axis::Vertical => (child_style.height, child_style.width),
+                    Axis::Horizontal => (child_style.width, child_style.height)
+                }
+            };
+
+            let child_main_space = child_main_sizing.as_percent_of(main_limit)
+                .unwrap_or(child_characteristics.min_size.axis(main_axis));
+            let child_cross_space = child_cross_sizing.as_percent_of(cross_limit)
+                .unwrap_or(child_characteristics.min_size.axis(cross_axis));
+
+            if let Sizing::Expand(weight) = child_main_sizing {
+                total_expand_factor += weight;
+                if weight > 0.0 {
+                    max_space_per_expand = max_space_per_expand.max(child_main_space / weight);
+                }
+            } else {
+                total_main_space += child_main_space;
+            }
+
+            max_cross_space = max_cross_space.max(child_cross_space);
+
+            child_content_sizes.push((
+                child_main_sizing,
+                child_cross_sizing,
+                math::Size::from_axes(main_axis, child_main_space, child_cross_space))
+            );
+            child_metrics.push(child_characteristics.text_metrics);
+        }
+        // the gaps are taken before the expanding children split what is left
+        total_main_space += scale_factor * style.gap * child_content_sizes.len().saturating_sub(1) as f32;
+        let fixed_main_space = total_main_space;
+        total_main_space += total_expand_factor * max_space_per_expand;
+
+        let max_baseline = child_metrics.iter().flatten().map(|metrics| metrics.first_baseline).fold(0.0, f32::max);
+        if baseline_aligned(style) {
+            // children are pushed down to line up their baselines, which can make the row taller
+            max_cross_space = child_content_sizes.iter().zip(&child_metrics)
+                .map(|((_, _, size), metrics)| baseline_offset(max_baseline, *metrics) + size.axis(cross_axis))
+                .fold(0.0, f32::max);
+        }
+
+        if let Wrap::Wrap { spacing: line_spacing } = style.wrap {
+            let lines = break_lines(style, scale_factor, &child_content_sizes, &child_metrics, main_limit);
+            total_main_space = lines.iter().map(|line| line.main_space).fold(0.0, f32::max);
+            max_cross_space = lines.iter().map(|line| line.cross_space).sum::<f32>()
+                + scale_factor * line_spacing * lines.len().saturating_sub(1) as f32;
+        }
+
+        let main_content_size = main_sizing.as_definite(scale_factor).unwrap_or(total_main_space);
+        let cross_content_size = cross_sizing.as_definite(scale_factor).unwrap_or(max_cross_space);
+        let content_size = math::Size::from_axes(main_axis, main_content_size, cross_content_size);
+
+        MeasuredChildren {
+            content_size,
+            child_content_sizes,
+            child_metrics,
+            positioned,
+            max_baseline,
+            total_main_space,
+            fixed_main_space,
+            max_cross_space,
+            total_expand_factor,
+            max_space_per_expand,
+        }
+    }
+
+    // the children which share a line of a wrapping container
+    struct Line {
+        children: Range<usize>,
+        // counting expanding children at their minimum size
+        main_space: f32,
+        cross_space: f32,
+        max_baseline: f32,
+        expand_weight: f32
+    }
+
+    // how far a line may pass the limit, so that rounding doesn't wrap a line measured to fit
+    const LINE_TOLERANCE: f32 = 0.01;
+
+    fn break_lines(style: &ContainerLayoutStyle, scale_factor: f32, sizes: &[(Sizing, Sizing, math::Size)], metrics: &[Option<TextMetrics>], main_limit: f32) -> Vec<Line> {
+        let gap = scale_factor * style.gap;
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut main_space = 0.0;
+        for (index, (_, _, size)) in sizes.iter().enumerate() {
+            let child_main_space = size.axis(style.main_axis);
+            // a child which doesn't fit even on its own still gets a line to itself
+            if index > start && main_space + gap + child_main_space > main_limit + LINE_TOLERANCE {
+                lines.push(measure_line(style, scale_factor, sizes, metrics, start..index));
+                start = index;
+                main_space = 0.0;
+            }
+            if index > start {
+                main_space += gap;
+            }
+            main_space += child_main_space;
+        }
+        if start < sizes.len() {
+            lines.push(measure_line(style, scale_factor, sizes, metrics, start..sizes.len()));
+        }
+        lines
+    }
+
+    fn measure_line(style: &ContainerLayoutStyle, scale_factor: f32, sizes: &[(Sizing, Sizing, math::Size)], metrics: &[Option<TextMetrics>], children: Range<usize>) -> Line {
+        let main_axis = style.main_axis;
+        let cross_axis = main_axis.cross();
+        let sizes = &sizes[children.clone()];
+        let metrics = &metrics[children.clone()];
+
+        let gaps = scale_factor * style.gap * sizes.len().saturating_sub(1) as f32;
+        let main_space = sizes.iter().map(|(_, _, size)| size.axis(main_axis)).sum::<f32>() + gaps;
+        let expand_weight = sizes.iter().map(|(main_sizing, _, _)| match main_sizing {
+            Sizing::Expand(weight) => *weight,
+            _ => 0.0
+        }).sum();
+        let max_baseline = metrics.iter().flatten().map(|metrics| metrics.first_baseline).fold(0.0, f32::max);
+        let cross_space = sizes.iter().zip(metrics)
+            .map(|((_, _, size), metrics)| {
+                let offset = if baseline_aligned(style) { baseline_offset(max_baseline, *metrics) } else { 0.0 };
+                offset + size.axis(cross_axis)
+            })
+            .fold(0.0, f32::max);
+
+        Line { children, main_space, cross_space, max_baseline, expand_weight }
+    }
+
+    fn baseline_aligned(style: &ContainerLayoutStyle) -> bool {
+        style.cross_justify == Justify::Baseline && style.main_axis == math::Axis::Horizontal
+    }
+
+    fn baseline_offset(max_baseline: f32, metrics: Option<TextMetrics>) -> f32 {
+        metrics.map_or(0.0, |metrics| max_baseline - metrics.first_baseline)
+    }
+
+    // the metrics of the first child with text, assuming children are packed from the top
+    fn container_metrics(style: &ContainerLayoutStyle, scale_factor: f32, spacing: math::SizeRect, measured: &MeasuredChildren) -> Option<TextMetrics> {
+        use crate::math::Axis;
+
+        let mut offset = spacing.top;
+        for ((_, _, size), metrics) in measured.child_content_sizes.iter().zip(&measured.child_metrics) {
+            if let Some(metrics) = metrics {
+                let first_baseline = match style.main_axis {
+                    Axis::Vertical => offset + metrics.first_baseline,
+                    Axis::Horizontal if baseline_aligned(style) => offset + measured.max_baseline,
+                    Axis::Horizontal => offset + metrics.first_baseline
+                };
+                return Some(TextMetrics { first_baseline, ..*metrics });
+            }
+            if style.main_axis == Axis::Vertical {
+                offset += size.axis(Axis::Vertical) + scale_factor * style.gap;
+            }
+        }
+        None
+    }
+
+    pub fn do_prelayout<'b, C: ?Sized, N: LayoutNode<C> + 'b>(style: &ContainerLayoutStyle, ctx: &C, input: PrelayoutInput, children: impl IntoIterator<Item=&'b N>) -> LayoutCharacteristics {
+        let spacing = input.scale_factor * (style.layout_style.margin + style.layout_style.padding + math::SizeRect::from_border(style.layout_style.border_size));
+        let measured = measure_children(style, ctx, input.available, input.scale_factor, children);
+        let min_size = measured.content_size + spacing.sum_axes();
+        let text_metrics = match style.main_direction {
+            Direction::Positive => container_metrics(style, input.scale_factor, spacing, &measured),
+            Direction::Negative => None
+        };
+
+        LayoutCharacteristics { layout_style: style.layout_style, min_size, text_metrics }
+    }
+
+    pub fn do_layout<'a, C: ?Sized, N: LayoutNode<C> + 'a>(style: &ContainerLayoutStyle, ctx: &C, input: LayoutInput, children: impl IntoIterator<Item=&'a N>) -> Vec<LayoutInput> {
+        use crate::math::Axis;
+
+        let spacing = input.scale_factor * (style.layout_style.margin + style.layout_style.padding + math::SizeRect::from_border(style.layout_style.border_size));
+        let main_axis = style.main_axis;
+        let cross_axis = main_axis.cross();
+
+        let measured = measure_children(style, ctx, input.allocated.size(), input.scale_factor, children);
+        if let Wrap::Wrap { spacing: line_spacing } = style.wrap {
+            let child_layouts = layout_lines(style, input.allocated.shrink_by(spacing), input.scale_factor, line_spacing, &measured);
+            return with_positioned(style, input, &measured, child_layouts);
+        }
+        let allocated = input.allocated;
+
+        let (allocated, space_per_expand) = {
+            let content_length = allocated.shrink_by(spacing).size().axis(main_axis);
+            let remaining = content_length - measured.content_size.axis(main_axis);
+            if measured.total_expand_factor > 0.0 {
+                // expanding children split whatever the others leave, but never go below their own size
+                let expand_space = (content_length - measured.fixed_main_space).max(0.0);
+                (allocated, (expand_space / measured.total_expand_factor).max(measured.max_space_per_expand))
+            } else if remaining > 0.0 {
+                let (min_shrink, max_shrink) = match style.main_justify {
+                    Justify::Min | Justify::Baseline => (0.0, remaining),
+                    Justify::Max => (remaining, 0.0),
+                    Justify::Center => (remaining / 2.0, remaining / 2.0)
+                };
+                (allocated.shrink_by(math::SizeRect::from_axis(main_axis, min_shrink, max_shrink)), 0.0)
+            } else {
+                (allocated, 0.0)
+            }
+        };
+        let content_box = allocated.shrink_by(spacing);
+
+        let mut curr = match (main_axis, style.main_direction) {
+            (Axis::Horizontal, Direction::Positive) => content_box.left(),
+            (Axis::Horizontal, Direction::Negative) => content_box.right(),
+            (Axis::Vertical, Direction::Positive) => content_box.top(),
+            (Axis::Vertical, Direction::Negative) => content_box.bottom()
+        };
+
+        let mut child_layouts = Vec::new();
+        for ((child_main_sizing, child_cross_sizing, child_content_size), child_metrics) in measured.child_content_sizes.iter().copied().zip(measured.child_metrics.iter().copied()) {
+            let main_amount = match child_main_sizing {
+                Sizing::Expand(weight) => space_per_expand * weight,
+                Sizing::Fixed(_) | Sizing::Fit | Sizing::Percent(_) => child_content_size.axis(main_axis)
+            };
+            let cross_amount = match child_cross_sizing {
+                Sizing::Expand(_) => content_box.size().axis(cross_axis),
+                Sizing::Fixed(_) | Sizing::Fit | Sizing::Percent(_) => child_content_size.axis(cross_axis)
+            };
+            let cross_start = match cross_axis {
+                Axis::Horizontal => content_box.left(),
+                Axis::Vertical => content_box.top()
+            } + match style.cross_justify {
+                Justify::Min => 0.0,
+                Justify::Max => measured.content_size.axis(cross_axis) - cross_amount,
+                Justify::Center => (measured.content_size.axis(cross_axis) - cross_amount) / 2.0,
+                Justify::Baseline if baseline_aligned(style) => baseline_offset(measured.max_baseline, child_metrics),
+                Justify::Baseline => 0.0
+            };
+
+            let child_allocated = child_rect(style, curr, main_amount, cross_start, cross_amount);
+            let advance = main_amount + input.scale_factor * style.gap;
+            match style.main_direction {
+                Direction::Positive => curr += advance,
+                Direction::Negative => curr -= advance
+            };
+            child_layouts.push(LayoutInput { allocated: child_allocated, scale_factor: input.scale_factor });
+        }
+
+        with_positioned(style, input, &measured, child_layouts)
+    }
+
+    // adds the layouts of the children taken out of the flow to those of the others
+    fn with_positioned(style: &ContainerLayoutStyle, input: LayoutInput, measured: &MeasuredChildren, mut child_layouts: Vec<LayoutInput>) -> Vec<LayoutInput> {
+        let scale_factor = input.scale_factor;
+        let padding_box = input.allocated.shrink_by(scale_factor * (style.layout_style.margin + math::SizeRect::from_border(style.layout_style.border_size)));
+        // in increasing order of index, so every child before the inserted one is already in place
+        for &(index, child_style, min_size) in &measured.positioned {
+            let Position::Anchored { left, top, right, bottom } = child_style.position else { continue; };
+            let (child_left, child_right) = anchor(left, right, padding_box.left(), padding_box.right(), child_style.width, min_size.width(), scale_factor);
+            let (child_top, child_bottom) = anchor(top, bottom, padding_box.top(), padding_box.bottom(), child_style.height, min_size.height(), scale_factor);
+            child_layouts.insert(index, LayoutInput {
+                allocated: math::Rect::from_lrtb(child_left, child_right, child_top, child_bottom),
+                scale_factor
+            });
+        }
+        child_layouts
+    }
+
+    // where a positioned child starts and ends along one axis of the box from `start` to `end`
+    fn anchor(inset_start: Option<f32>, inset_end: Option<f32>, start: f32, end: f32, sizing: Sizing, min_length: f32, scale_factor: f32) -> (f32, f32) {
+        let length = match sizing {
+            Sizing::Expand(_) => (end - start).max(min_length),
+            Sizing::Percent(_) => sizing.as_percent_of(end - start).unwrap_or(min_length),
+            Sizing::Fit | Sizing::Fixed(_) => min_length
+        };
+        match (inset_start, inset_end) {
+            (Some(inset_start), Some(inset_end)) => (start + scale_factor * inset_start, end - scale_factor * inset_end),
+            (Some(inset_start), None) => (start + scale_factor * inset_start, start + scale_factor * inset_start + length),
+            (None, Some(inset_end)) => (end - scale_factor * inset_end - length, end - scale_factor * inset_end),
+            (None, None) => (start, start + length)
+        }
+    }
+    // the rect of a child which takes `main_amount` from `curr` on in the main direction
+    fn child_rect(style: &ContainerLayoutStyle, curr: f32, main_amount: f32, cross_start: f32, cross_amount: f32) -> math::Rect {
+        use crate::math::Axis;
+
+        match (style.main_axis, style.main_direction) {
+            (Axis::Horizontal, Direction::Positive) => {
+                math::Rect::from_lrtb(curr, curr + main_amount, cross_start, cross_start + cross_amount)
+            }
+            (Axis::Horizontal, Direction::Negative) => {
+                math::Rect::from_lrtb(curr - main_amount, curr, cross_start, cross_start + cross_amount)
+            }
+            (Axis::Vertical, Direction::Positive) => {
+                math::Rect::from_lrtb(cross_start, cross_start + cross_amount, curr, curr + main_amount)
+            }
+            (Axis::Vertical, Direction::Negative) => {
+                math::Rect::from_lrtb(cross_start, cross_start + cross_amount, curr - main_amount, curr)
+            }
+        }
+    }
+
+    // lays the children out in lines which are stacked along the cross axis, each justified on
+    // its own along the main axis
+    fn layout_lines(style: &ContainerLayoutStyle, content_box: math::Rect, scale_factor: f32, line_spacing: f32, measured: &MeasuredChildren) -> Vec<LayoutInput> {
+        use crate::math::Axis;
+
+        let main_axis = style.main_axis;
+        let cross_axis = main_axis.cross();
+        let main_length = content_box.size().axis(main_axis);
+        let lines = break_lines(style, scale_factor, &measured.child_content_sizes, &measured.child_metrics, main_length);
+
+        let mut line_start = match cross_axis {
+            Axis::Horizontal => content_box.left(),
+            Axis::Vertical => content_box.top()
+        };
+        let mut child_layouts = Vec::with_capacity(measured.child_content_sizes.len());
+        for line in lines {
+            let remaining = (main_length - line.main_space).max(0.0);
+            let (offset, space_per_expand) = if line.expand_weight > 0.0 {
+                (0.0, remaining / line.expand_weight)
+            } else {
+                match style.main_justify {
+                    Justify::Min | Justify::Baseline => (0.0, 0.0),
+                    Justify::Max => (remaining, 0.0),
+                    Justify::Center => (remaining / 2.0, 0.0)
+                }
+            };
+            let mut curr = match (main_axis, style.main_direction) {
+                (Axis::Horizontal, Direction::Positive) => content_box.left() + offset,
+                (Axis::Horizontal, Direction::Negative) => content_box.right() - offset,
+                (Axis::Vertical, Direction::Positive) => content_box.top() + offset,
+                (Axis::Vertical, Direction::Negative) => content_box.bottom() - offset
+            };
+
+            for index in line.children.clone() {
+                let (child_main_sizing, child_cross_sizing, child_content_size) = measured.child_content_sizes[index];
+                let main_amount = match child_main_sizing {
+                    Sizing::Expand(weight) => child_content_size.axis(main_axis) + space_per_expand * weight,
+                    Sizing::Fixed(_) | Sizing::Fit | Sizing::Percent(_) => child_content_size.axis(main_axis)
+                };
+                let cross_amount = match child_cross_sizing {
+                    Sizing::Expand(_) => line.cross_space,
+                    Sizing::Fixed(_) | Sizing::Fit | Sizing::Percent(_) => child_content_size.axis(cross_axis)
+                };
+                let cross_start = line_start + match style.cross_justify {
+                    Justify::Min => 0.0,
+                    Justify::Max => line.cross_space - cross_amount,
+                    Justify::Center => (line.cross_space - cross_amount) / 2.0,
+                    Justify::Baseline if baseline_aligned(style) => baseline_offset(line.max_baseline, measured.child_metrics[index]),
+                    Justify::Baseline => 0.0
+                };
+
+                child_layouts.push(LayoutInput { allocated: child_rect(style, curr, main_amount, cross_start, cross_amount), scale_factor });
+                let advance = main_amount + scale_factor * style.gap;
+                match style.main_direction {
+                    Direction::Positive => curr += advance,
+                    Direction::Negative => curr -= advance
+                };
+            }
+            line_start += line.cross_space + scale_factor * line_spacing;
+        }
+
+        child_layouts
+    }
+}
@@ -0,0 +1,12 @@
+//! The platform-independent parts of yoru: geometry, styles and the layout algorithms.
+//!
+//! This crate is `no_std` (it only needs `alloc`) so the layout engine can be reused on targets
+//! without an operating system. The optional `tiny-skia`, `kurbo` and `cosmic-text` features add
+//! conversions into those crates.
+#![no_std]
+
+extern crate alloc;
+
+pub mod math;
+pub mod style;
+pub mod layout;
@@ -1,4 +1,5 @@
-use std::ops::{Add, Mul, Sub};
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
 use bytemuck::{Pod, Zeroable};
 
 
@@ -285,6 +286,21 @@ impl Rect {
         (self.left()..=self.right()).contains(&point.x) && (self.top()..=self.bottom()).contains(&point.y)
     }
 
+    /// Whether the two rects share some area, not just an edge.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.left() < other.right() && other.left() < self.right() && self.top() < other.bottom() && other.top() < self.bottom()
+    }
+
+    /// The area the two rects share, if any.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        self.intersects(other).then(|| Rect::from_lrtb(
+            self.left().max(other.left()),
+            self.right().min(other.right()),
+            self.top().max(other.top()),
+            self.bottom().min(other.bottom())
+        ))
+    }
+
     pub fn clamp_positive(&self) -> Rect {
         Rect {
             x: self.x,
@@ -303,12 +319,27 @@ impl Rect {
     }
 }
 
-impl From<Rect> for tiny_skia::Rect {
-    fn from(value: Rect) -> Self {
-        tiny_skia::Rect::from_xywh(value.x, value.y, value.w, value.h).unwrap()
+/// Returned when a rect can't be represented by a rendering backend, e.g. because it is empty or
+/// not finite.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct InvalidGeometry;
+
+impl fmt::Display for InvalidGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid geometry")
+    }
+}
+
+#[cfg(feature = "tiny-skia")]
+impl TryFrom<Rect> for tiny_skia::Rect {
+    type Error = InvalidGeometry;
+
+    fn try_from(value: Rect) -> Result<Self, Self::Error> {
+        tiny_skia::Rect::from_xywh(value.x, value.y, value.w, value.h).ok_or(InvalidGeometry)
     }
 }
 
+#[cfg(feature = "kurbo")]
 impl From<Rect> for kurbo::Rect {
     fn from(value: Rect) -> Self {
         kurbo::Rect::new(value.left() as f64, value.top() as f64, value.right() as f64, value.bottom() as f64)
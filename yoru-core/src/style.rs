@@ -0,0 +1,304 @@
+use crate::math;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::from_rgb8(0, 0, 0);
+    pub const DARK_GRAY: Color = Color::from_rgb8(105, 105, 105);
+    pub const GRAY: Color = Color::from_rgb8(128, 128, 128);
+    pub const LIGHT_GRAY: Color = Color::from_rgb8(211, 211, 211);
+    pub const WHITE: Color = Color::from_rgb8(255, 255, 255);
+    pub const RED: Color = Color::from_rgb8(255, 0, 0);
+    pub const GREEN: Color = Color::from_rgb8(0, 255, 0);
+    pub const BLUE: Color = Color::from_rgb8(0, 0, 255);
+    pub const TRANSPARENT: Color = Color::from_rgba8(0, 0, 0, 0);
+
+    pub const fn from_rgb8(r: u8, g: u8, b: u8) -> Color {
+        Color::from_rgba8(r, g, b, 255)
+    }
+
+    pub const fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+
+    /// Linearly interpolates towards `other`, where `t` is clamped between 0 and 1.
+    pub fn lerp(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        // rounds to nearest; the value is never negative so adding a half and truncating suffices
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t + 0.5) as u8;
+        Color::from_rgba8(mix(self.r, other.r), mix(self.g, other.g), mix(self.b, other.b), mix(self.a, other.a))
+    }
+}
+
+#[cfg(feature = "cosmic-text")]
+impl From<Color> for cosmic_text::Color {
+    fn from(value: Color) -> Self {
+        cosmic_text::Color::rgba(value.r, value.g, value.b, value.a)
+    }
+}
+
+#[cfg(feature = "tiny-skia")]
+impl From<Color> for tiny_skia::Color {
+    fn from(value: Color) -> Self {
+        // when writing to a `softbuffer::Buffer` we need to swap b and r
+        tiny_skia::Color::from_rgba8(value.b, value.g, value.r, value.a)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Sizing {
+    /// Takes a share of the space left over by the other children, in proportion to the weight,
+    /// so children with `Expand(2.0)` and `Expand(1.0)` split it 2:1.
+    Expand(f32),
+    Fit,
+    Fixed(f32),
+    /// A percentage of the length of the parent's content box. Where that isn't known yet, e.g.
+    /// inside a scroll view, the element is measured as with `Fit`.
+    Percent(f32)
+}
+
+impl Sizing {
+    pub fn as_definite(&self, scale_factor: f32) -> Option<f32> {
+        match self {
+            Sizing::Expand(_) => None,
+            Sizing::Fit => None,
+            Sizing::Fixed(size) => Some(*size * scale_factor),
+            Sizing::Percent(_) => None
+        }
+    }
+
+    /// The length a `Percent` takes of a parent content box which is `parent` long.
+    pub fn as_percent_of(&self, parent: f32) -> Option<f32> {
+        match self {
+            Sizing::Percent(percent) if parent.is_finite() => Some(parent * percent / 100.0),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Justify {
+    Min,
+    Max,
+    Center,
+    /// Lines up the first baselines of children which contain text. Only applies to the vertical
+    /// cross axis; anywhere else it behaves like `Min`.
+    Baseline
+}
+
+/// What a container does with children which don't fit on one line along its main axis.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Wrap {
+    /// Keeps every child on one line, even if that overflows the container.
+    #[default]
+    NoWrap,
+    /// Moves children which would pass the end of the main axis onto a new line, with `spacing`
+    /// logical pixels between lines. Lines are stacked along the cross axis, and each line is
+    /// justified on its own.
+    Wrap { spacing: f32 }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    Positive,
+    Negative,
+}
+
+/// Whether an element (and everything inside it) can be the target of pointer interactions.
+/// Elements with `PointerEvents::None` are skipped by hit testing, so clicks reach whatever is
+/// beneath them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum PointerEvents {
+    #[default]
+    Auto,
+    None
+}
+
+/// Where an element is placed inside its parent container.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Position {
+    /// After the previous child, in the normal flow of the container.
+    #[default]
+    Flow,
+    /// Taken out of the flow and placed against the container's padding box, with each given
+    /// side that many logical pixels in from the matching edge. With both sides of an axis given
+    /// the element stretches between them; with neither it sits at the start of the axis.
+    Anchored { left: Option<f32>, top: Option<f32>, right: Option<f32>, bottom: Option<f32> }
+}
+
+/// Where a paginated render may split an element across pages.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum PageBreak {
+    #[default]
+    Auto,
+    /// Always start the element on a new page.
+    Before,
+    /// Move the element to the next page rather than splitting it, if it fits on one page.
+    Avoid
+}
+
+/// A blurred copy of an element's border box drawn beneath it, which makes panels and popups
+/// look raised above the content around them. Lengths are in logical pixels.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Shadow {
+    /// How far the shadow is moved from the element, usually down and to the right.
+    pub offset: math::Vector,
+    /// How soft the edge of the shadow is. With 0 the edge is sharp.
+    pub blur: f32,
+    /// How much larger than the border box the shadow is before it is blurred.
+    pub spread: f32,
+    pub color: Color
+}
+
+impl Shadow {
+    pub fn new(offset: math::Vector, blur: f32, spread: f32, color: Color) -> Shadow {
+        Shadow { offset, blur: blur.max(0.0), spread, color }
+    }
+}
+
+/// Overrides applied to an element by `Element::style_when` while its condition holds.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct StylePatch {
+    pub background: Option<Color>,
+    pub border: Option<(Color, f32)>,
+    pub opacity: Option<f32>
+}
+
+impl StylePatch {
+    pub fn new() -> StylePatch {
+        StylePatch::default()
+    }
+
+    pub fn with_background(mut self, color: Color) -> StylePatch {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn with_border(mut self, color: Color, width: f32) -> StylePatch {
+        self.border = Some((color, width));
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> StylePatch {
+        self.opacity = Some(opacity.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Takes each override from `self`, or from `base` where `self` doesn't set it.
+    pub fn or(self, base: StylePatch) -> StylePatch {
+        StylePatch {
+            background: self.background.or(base.background),
+            border: self.border.or(base.border),
+            opacity: self.opacity.or(base.opacity)
+        }
+    }
+}
+
+/// The state a widget is drawn in, from how the user is interacting with it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum WidgetState {
+    #[default]
+    Normal,
+    /// The cursor is over the widget.
+    Hovered,
+    /// The primary button went down on the widget and hasn't been released yet.
+    Pressed,
+    /// The widget, or a widget inside it, holds the keyboard focus.
+    Focused,
+    Disabled
+}
+
+/// How a widget is drawn in each `WidgetState`, e.g. for `Button::with_style_sheet`.
+///
+/// The patch for the current state is applied over `normal`, so it only has to set what differs.
+/// When several states hold at once, disabled wins over pressed, pressed over hovered and
+/// hovered over focused.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct StyleSheet {
+    pub normal: StylePatch,
+    pub hovered: StylePatch,
+    pub pressed: StylePatch,
+    pub focused: StylePatch,
+    pub disabled: StylePatch
+}
+
+impl StyleSheet {
+    pub fn new() -> StyleSheet {
+        StyleSheet::default()
+    }
+
+    pub fn with_normal(mut self, patch: StylePatch) -> StyleSheet {
+        self.normal = patch;
+        self
+    }
+
+    pub fn with_hovered(mut self, patch: StylePatch) -> StyleSheet {
+        self.hovered = patch;
+        self
+    }
+
+    pub fn with_pressed(mut self, patch: StylePatch) -> StyleSheet {
+        self.pressed = patch;
+        self
+    }
+
+    pub fn with_focused(mut self, patch: StylePatch) -> StyleSheet {
+        self.focused = patch;
+        self
+    }
+
+    pub fn with_disabled(mut self, patch: StylePatch) -> StyleSheet {
+        self.disabled = patch;
+        self
+    }
+
+    /// The overrides to draw with in `state`.
+    pub fn resolve(&self, state: WidgetState) -> StylePatch {
+        match state {
+            WidgetState::Normal => self.normal,
+            WidgetState::Hovered => self.hovered.or(self.normal),
+            WidgetState::Pressed => self.pressed.or(self.normal),
+            WidgetState::Focused => self.focused.or(self.normal),
+            WidgetState::Disabled => self.disabled.or(self.normal)
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct LayoutStyle {
+    pub border_size: f32,
+    pub padding: math::SizeRect,
+    pub margin: math::SizeRect,
+
+    pub width: Sizing,
+    pub height: Sizing,
+
+    pub pointer_events: PointerEvents,
+    pub position: Position
+}
+
+impl LayoutStyle {
+    pub fn spacing_size(&self) -> math::SizeRect {
+        self.margin + math::SizeRect::from_border(self.border_size) + self.padding
+    }
+}
+
+
+#[derive(Debug, Copy, Clone)]
+pub struct ContainerLayoutStyle {
+    pub layout_style: LayoutStyle,
+
+    pub main_axis: math::Axis,
+    pub main_direction: Direction,
+    pub main_justify: Justify,
+    pub cross_justify: Justify,
+    pub wrap: Wrap,
+    /// Logical pixels left between neighbouring children along the main axis.
+    pub gap: f32
+}
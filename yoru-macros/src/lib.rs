@@ -0,0 +1,71 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
+
+
+/// Generates a `Tracked<Name>` counterpart of a plain struct in which every field is an `RwSignal`.
+///
+/// The tracked struct has an accessor returning each field's signal and a `set_<field>` method,
+/// and can be created from the plain struct with `From` or `Trackable::into_tracked`.
+#[proc_macro_derive(Trackable)]
+pub fn derive_trackable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into()
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "Trackable can only be derived for structs with named fields"))
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "Trackable can only be derived for structs"))
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let tracked_name = format_ident!("Tracked{}", name);
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let field_names: Vec<_> = fields.iter().map(|field| field.ident.as_ref().unwrap()).collect();
+    let field_vis: Vec<_> = fields.iter().map(|field| &field.vis).collect();
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let setters: Vec<_> = field_names.iter().map(|name| format_ident!("set_{}", name)).collect();
+
+    Ok(quote! {
+        #vis struct #tracked_name #impl_generics #where_clause {
+            #( #field_vis #field_names: ::yoru::tracking::RwSignal<#field_types>, )*
+        }
+
+        impl #impl_generics #tracked_name #type_generics #where_clause {
+            #(
+                #field_vis fn #field_names(&self) -> &::yoru::tracking::RwSignal<#field_types> {
+                    &self.#field_names
+                }
+
+                #field_vis fn #setters(&self, value: #field_types) {
+                    self.#field_names.update(|field| *field = value);
+                }
+            )*
+        }
+
+        impl #impl_generics ::core::convert::From<#name #type_generics> for #tracked_name #type_generics #where_clause {
+            fn from(value: #name #type_generics) -> Self {
+                #tracked_name {
+                    #( #field_names: ::yoru::tracking::RwSignal::new(value.#field_names), )*
+                }
+            }
+        }
+
+        impl #impl_generics ::yoru::tracking::Trackable for #name #type_generics #where_clause {
+            type Tracked = #tracked_name #type_generics;
+
+            fn into_tracked(self) -> Self::Tracked {
+                self.into()
+            }
+        }
+    })
+}